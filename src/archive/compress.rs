@@ -0,0 +1,92 @@
+//! Compressed date archives: `<storage>/<date>.tar.gz`
+//!
+//! Entries are stored relative to the storage directory, so a tarball holds the flat
+//! date directory as well as each project's directory for that date.
+
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Path of the compressed archive for a date
+pub fn tarball_path(storage: &Path, date: &str) -> PathBuf {
+    storage.join(format!("{}.tar.gz", date))
+}
+
+/// Date of a `<date>.tar.gz` file name, if it is one
+pub fn tarball_date(file_name: &str) -> Option<&str> {
+    file_name.strip_suffix(".tar.gz")
+}
+
+/// Pack `dirs` (all under `storage`) into `archive`.
+///
+/// The tarball is written next to its destination and renamed into place, so an
+/// interrupted run never leaves a truncated archive behind.
+pub fn pack(storage: &Path, dirs: &[PathBuf], archive: &Path) -> Result<()> {
+    let file_name = archive
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp = archive.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+
+    let result = (|| -> Result<()> {
+        let file = File::create(&tmp).context(format!("Failed to create {}", tmp.display()))?;
+        let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+        for dir in dirs {
+            let relative = dir.strip_prefix(storage).context(format!(
+                "{} is outside the storage directory",
+                dir.display()
+            ))?;
+            builder
+                .append_dir_all(relative, dir)
+                .context(format!("Failed to add {} to archive", dir.display()))?;
+        }
+        let file = builder.into_inner()?.finish()?;
+        file.sync_all()?;
+        Ok(())
+    })();
+
+    if let Err(e) = result {
+        let _ = fs::remove_file(&tmp);
+        return Err(e);
+    }
+    fs::rename(&tmp, archive).context(format!("Failed to write {}", archive.display()))
+}
+
+/// Paths of all regular files in an archive
+pub fn list_files(archive: &Path) -> Result<Vec<PathBuf>> {
+    let mut tar = open(archive)?;
+    let mut files = Vec::new();
+    for entry in tar.entries()? {
+        let entry = entry?;
+        if entry.header().entry_type().is_file() {
+            files.push(entry.path()?.into_owned());
+        }
+    }
+    Ok(files)
+}
+
+/// Read the first file in an archive whose path satisfies `matches`
+pub fn read_file(archive: &Path, matches: impl Fn(&Path) -> bool) -> Result<Option<String>> {
+    let mut tar = open(archive)?;
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() || !matches(&entry.path()?) {
+            continue;
+        }
+        let mut content = String::new();
+        entry
+            .read_to_string(&mut content)
+            .context(format!("Failed to read entry from {}", archive.display()))?;
+        return Ok(Some(content));
+    }
+    Ok(None)
+}
+
+fn open(archive: &Path) -> Result<tar::Archive<GzDecoder<File>>> {
+    let file = File::open(archive).context(format!("Failed to open {}", archive.display()))?;
+    Ok(tar::Archive::new(GzDecoder::new(file)))
+}
@@ -0,0 +1,397 @@
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+
+/// A frontmatter value: either a scalar or a list
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrontmatterValue {
+    Scalar(String),
+    List(Vec<String>),
+}
+
+/// Parsed YAML frontmatter of an archive markdown file
+#[derive(Debug, Clone, Default)]
+pub struct Frontmatter {
+    values: HashMap<String, FrontmatterValue>,
+}
+
+impl Frontmatter {
+    /// Parse the frontmatter block at the start of `content`
+    pub fn parse(content: &str) -> Self {
+        match split(content) {
+            Some((raw, _)) => Self::parse_block(raw),
+            None => Self::default(),
+        }
+    }
+
+    /// Parse a raw frontmatter block (without `---` delimiters)
+    fn parse_block(raw: &str) -> Self {
+        match serde_yaml::from_str::<Mapping>(raw) {
+            Ok(mapping) => Self::from_mapping(mapping),
+            // Older archives wrote values unescaped, which is not always valid YAML
+            Err(_) => Self::parse_lines(raw),
+        }
+    }
+
+    /// Convert a parsed YAML mapping, keeping top-level scalars and lists
+    fn from_mapping(mapping: Mapping) -> Self {
+        let mut values = HashMap::new();
+
+        for (key, value) in mapping {
+            let Some(key) = scalar_to_string(&key) else {
+                continue;
+            };
+            let value = match value {
+                // `key:` with nothing after it is an empty block list
+                Value::Null => FrontmatterValue::List(Vec::new()),
+                Value::Sequence(items) => FrontmatterValue::List(
+                    items
+                        .iter()
+                        .filter_map(scalar_to_string)
+                        .filter(|item| !item.is_empty())
+                        .collect(),
+                ),
+                other => match scalar_to_string(&other) {
+                    Some(value) => FrontmatterValue::Scalar(value),
+                    None => continue,
+                },
+            };
+            values.insert(key, value);
+        }
+
+        Self { values }
+    }
+
+    /// Line-based fallback parser for frontmatter that is not valid YAML
+    fn parse_lines(raw: &str) -> Self {
+        let mut values = HashMap::new();
+        let mut current_list: Option<(String, Vec<String>)> = None;
+
+        for line in raw.lines() {
+            let trimmed = line.trim();
+
+            // Block list item belonging to the previous key
+            let item = if trimmed == "-" {
+                Some("")
+            } else {
+                trimmed.strip_prefix("- ")
+            };
+            if let Some(item) = item {
+                if let Some((_, items)) = current_list.as_mut() {
+                    let item = unquote(item);
+                    if !item.is_empty() {
+                        items.push(item);
+                    }
+                }
+                continue;
+            }
+
+            if let Some((key, items)) = current_list.take() {
+                values.insert(key, FrontmatterValue::List(items));
+            }
+
+            // Only top-level keys are supported
+            if line.starts_with(' ') || line.starts_with('\t') || trimmed.is_empty() {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_string();
+            let value = value.trim();
+
+            if value.is_empty() {
+                current_list = Some((key, Vec::new()));
+            } else if let Some(inner) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+                let items = inner
+                    .split(',')
+                    .map(unquote)
+                    .filter(|item| !item.is_empty())
+                    .collect();
+                values.insert(key, FrontmatterValue::List(items));
+            } else {
+                values.insert(key, FrontmatterValue::Scalar(unquote(value)));
+            }
+        }
+
+        if let Some((key, items)) = current_list {
+            values.insert(key, FrontmatterValue::List(items));
+        }
+
+        Self { values }
+    }
+
+    /// Get a scalar value by key
+    pub fn get(&self, key: &str) -> Option<&str> {
+        match self.values.get(key) {
+            Some(FrontmatterValue::Scalar(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get a list value by key (empty if missing or not a list)
+    pub fn get_list(&self, key: &str) -> Vec<String> {
+        match self.values.get(key) {
+            Some(FrontmatterValue::List(items)) => items.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Deserialize the frontmatter into a typed struct
+    pub fn to_struct<T: DeserializeOwned>(&self) -> Result<T> {
+        let mapping: Mapping = self
+            .values
+            .iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    FrontmatterValue::Scalar(value) => Value::String(value.clone()),
+                    FrontmatterValue::List(items) => {
+                        Value::Sequence(items.iter().cloned().map(Value::String).collect())
+                    }
+                };
+                (Value::String(key.clone()), value)
+            })
+            .collect();
+
+        Ok(serde_yaml::from_value(Value::Mapping(mapping))?)
+    }
+}
+
+/// Render a YAML scalar value as a string (None for nulls and nested values)
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Quote a string as a double-quoted YAML scalar
+pub fn quote(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            _ => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Split content into the raw frontmatter block and the body after it
+pub fn split(content: &str) -> Option<(&str, &str)> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let rest = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))?;
+
+    if let Some(body) = rest.strip_prefix("---") {
+        return Some(("", body.trim_start_matches(['\r', '\n'])));
+    }
+
+    let end = rest.find("\n---")?;
+    let raw = &rest[..end];
+    let after = &rest[end + 4..];
+    let body = after
+        .strip_prefix("\r\n")
+        .or_else(|| after.strip_prefix('\n'))
+        .unwrap_or(after);
+
+    Some((raw, body))
+}
+
+/// Replace (or append) a list value in the frontmatter of `content`.
+///
+/// The list is written inline (`key: [a, b]`); the rest of the file is kept as-is.
+/// Returns `None` if the content has no frontmatter block.
+pub fn set_list(content: &str, key: &str, items: &[String]) -> Option<String> {
+    set_line(content, key, format!("{}: [{}]", key, items.join(", ")))
+}
+
+/// Replace (or append) a scalar value in the frontmatter of `content`, quoting it.
+///
+/// Returns `None` if the content has no frontmatter block.
+pub fn set(content: &str, key: &str, value: &str) -> Option<String> {
+    set_line(content, key, format!("{}: {}", key, quote(value)))
+}
+
+/// Replace the `key:` entry (including any block list items) with `new_line`
+fn set_line(content: &str, key: &str, new_line: String) -> Option<String> {
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let lines: Vec<&str> = content.split(newline).collect();
+
+    if lines.first().map(|l| l.trim_end()) != Some("---") {
+        return None;
+    }
+    let end = lines.iter().skip(1).position(|l| l.trim_end() == "---")? + 1;
+
+    let prefix = format!("{}:", key);
+    let mut out: Vec<String> = Vec::with_capacity(lines.len() + 1);
+    let mut replaced = false;
+    let mut i = 1;
+
+    out.push(lines[0].to_string());
+    while i < end {
+        let line = lines[i];
+        if !replaced && line.starts_with(&prefix) {
+            out.push(new_line.clone());
+            replaced = true;
+            i += 1;
+            // Drop block list items belonging to the old value
+            while i < end && (lines[i].trim_start().starts_with("- ") || lines[i].trim() == "-") {
+                i += 1;
+            }
+            continue;
+        }
+        out.push(line.to_string());
+        i += 1;
+    }
+    if !replaced {
+        out.push(new_line);
+    }
+    out.extend(lines[end..].iter().map(|l| l.to_string()));
+
+    Some(out.join(newline))
+}
+
+/// Strip surrounding whitespace and quotes from a value
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scalars_and_inline_list() {
+        let content = "---\ntitle: \"Fix: auth bug\"\ndate: 2026-01-16\ntags: [claude-code, session-archive]\n---\n\n# Body\n";
+        let fm = Frontmatter::parse(content);
+
+        assert_eq!(fm.get("title"), Some("Fix: auth bug"));
+        assert_eq!(fm.get("date"), Some("2026-01-16"));
+        assert_eq!(fm.get_list("tags"), vec!["claude-code", "session-archive"]);
+    }
+
+    #[test]
+    fn test_parse_block_list() {
+        let content = "---\nsessions:\n  - \"10_00-one\"\n  - 11_00-two\ntags: [daily]\n---\n";
+        let fm = Frontmatter::parse(content);
+
+        assert_eq!(fm.get_list("sessions"), vec!["10_00-one", "11_00-two"]);
+        assert_eq!(fm.get_list("tags"), vec!["daily"]);
+    }
+
+    #[test]
+    fn test_split_without_frontmatter() {
+        assert!(split("# Just a heading\n").is_none());
+        assert!(Frontmatter::parse("# Just a heading\n")
+            .get("title")
+            .is_none());
+    }
+
+    #[test]
+    fn test_set_list() {
+        let tags = vec!["claude-code".to_string(), "database".to_string()];
+
+        let inline = "---\ntitle: x\ntags: [claude-code, db]\ncreated: now\n---\n\n# Body\n";
+        assert_eq!(
+            set_list(inline, "tags", &tags).unwrap(),
+            "---\ntitle: x\ntags: [claude-code, database]\ncreated: now\n---\n\n# Body\n"
+        );
+
+        let block = "---\ntags:\n  - claude-code\n  - db\ntitle: x\n---\nBody";
+        assert_eq!(
+            set_list(block, "tags", &tags).unwrap(),
+            "---\ntags: [claude-code, database]\ntitle: x\n---\nBody"
+        );
+
+        let missing = "---\r\ntitle: x\r\n---\r\nBody";
+        assert_eq!(
+            set_list(missing, "tags", &tags).unwrap(),
+            "---\r\ntitle: x\r\ntags: [claude-code, database]\r\n---\r\nBody"
+        );
+
+        assert!(set_list("# No frontmatter", "tags", &tags).is_none());
+    }
+
+    #[test]
+    fn test_set_scalar() {
+        let content = "---\ntitle: \"old\"\ndate: 2026-01-16\n---\n\n# old\n";
+        let updated = set(content, "title", "new: name").unwrap();
+        assert_eq!(
+            updated,
+            "---\ntitle: \"new: name\"\ndate: 2026-01-16\n---\n\n# old\n"
+        );
+        assert_eq!(Frontmatter::parse(&updated).get("title"), Some("new: name"));
+        assert!(set("# no frontmatter\n", "title", "x").is_none());
+    }
+
+    #[test]
+    fn test_parse_values_with_colons_and_escapes() {
+        let content = format!(
+            "---\ntitle: {}\ncwd: {}\ncreated: 2026-01-16T10:00:00+08:00\nsession_id: 42\n---\n",
+            quote("Say \"hi\": done"),
+            quote(r"C:\Users\dev\project"),
+        );
+        let fm = Frontmatter::parse(&content);
+
+        assert_eq!(fm.get("title"), Some("Say \"hi\": done"));
+        assert_eq!(fm.get("cwd"), Some(r"C:\Users\dev\project"));
+        assert_eq!(fm.get("created"), Some("2026-01-16T10:00:00+08:00"));
+        assert_eq!(fm.get("session_id"), Some("42"));
+    }
+
+    #[test]
+    fn test_parse_falls_back_for_invalid_yaml() {
+        // Unescaped backslashes and unquoted colons from older archives
+        let content = "---\ntitle: Fix: auth bug\ntranscript_path: \"C:\\Users\\x.jsonl\"\nsessions:\n  - one\n---\n";
+        let fm = Frontmatter::parse(content);
+
+        assert_eq!(fm.get("title"), Some("Fix: auth bug"));
+        assert_eq!(fm.get("transcript_path"), Some(r"C:\Users\x.jsonl"));
+        assert_eq!(fm.get_list("sessions"), vec!["one"]);
+    }
+
+    #[test]
+    fn test_to_struct() {
+        #[derive(serde::Deserialize)]
+        struct Meta {
+            title: String,
+            #[serde(default)]
+            cwd: Option<String>,
+            tags: Vec<String>,
+        }
+
+        let fm = Frontmatter::parse("---\ntitle: x\ntags: [a, b]\n---\n");
+        let meta: Meta = fm.to_struct().unwrap();
+        assert_eq!(meta.title, "x");
+        assert_eq!(meta.cwd, None);
+        assert_eq!(meta.tags, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_parse_with_bom() {
+        let fm = Frontmatter::parse("\u{feff}---\ndate: 2026-01-16\n---\n# Body\n");
+        assert_eq!(fm.get("date"), Some("2026-01-16"));
+    }
+}
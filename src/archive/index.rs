@@ -0,0 +1,244 @@
+//! Inverted index over archive files, cached in `<storage>/.index.json`
+//!
+//! Maps lowercased tokens to the `(date, session)` files containing them, so a search
+//! only reads files that can match. Files are re-indexed when their mtime changes.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use super::manager::ArchiveManager;
+use crate::fs_util::write_atomic;
+
+/// Index file name under the storage directory
+pub const INDEX_FILE: &str = ".index.json";
+
+/// An indexed file: date and session name ("daily" for the daily summary)
+pub type Posting = (String, String);
+
+/// Token → files containing it, plus when each file was indexed
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct FullTextIndex {
+    /// Modification time (ms since epoch) of each file when it was indexed
+    files: BTreeMap<String, u64>,
+    tokens: BTreeMap<String, BTreeSet<Posting>>,
+}
+
+impl FullTextIndex {
+    /// Load the cached index, or an empty one if missing or unreadable
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        write_atomic(path, serde_json::to_string(self)?).context("Failed to save search index")
+    }
+
+    /// Number of indexed files
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Number of distinct tokens
+    pub fn token_count(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Re-index files added or modified since they were indexed and drop deleted ones.
+    ///
+    /// Returns whether anything changed.
+    pub fn update(&mut self, manager: &ArchiveManager) -> Result<bool> {
+        let mut current = BTreeMap::new();
+        for date in manager.list_dates()? {
+            let mut names = manager.list_sessions(&date)?;
+            names.push("daily".to_string());
+            for name in names {
+                if let Some(modified) = modified_ms(&source_path(manager, &date, &name)) {
+                    current.insert((date.clone(), name), modified);
+                }
+            }
+        }
+
+        let stale: HashSet<Posting> = self
+            .files
+            .iter()
+            .filter_map(|(key, modified)| {
+                let posting = split_key(key)?;
+                (current.get(&posting) != Some(modified)).then_some(posting)
+            })
+            .collect();
+        let added: Vec<(Posting, u64)> = current
+            .into_iter()
+            .filter(|(posting, modified)| self.files.get(&file_key(posting)) != Some(modified))
+            .collect();
+        if stale.is_empty() && added.is_empty() {
+            return Ok(false);
+        }
+
+        if !stale.is_empty() {
+            for posting in &stale {
+                self.files.remove(&file_key(posting));
+            }
+            self.tokens.retain(|_, postings| {
+                postings.retain(|posting| !stale.contains(posting));
+                !postings.is_empty()
+            });
+        }
+
+        for ((date, name), modified) in added {
+            let content = if name == "daily" {
+                manager.read_daily_summary(&date)
+            } else {
+                manager.read_session(&date, &name)
+            };
+            // Recorded even if unreadable, so it isn't retried until it changes
+            self.files
+                .insert(file_key(&(date.clone(), name.clone())), modified);
+            let Ok(content) = content else {
+                continue;
+            };
+            // Search falls back to the file name when there is no title
+            for token in tokenize(&content).into_iter().chain(tokenize(&name)) {
+                self.tokens
+                    .entry(token)
+                    .or_default()
+                    .insert((date.clone(), name.clone()));
+            }
+        }
+        Ok(true)
+    }
+
+    /// Files that may contain `query` as a case-insensitive substring.
+    ///
+    /// Each query token must be part of some token of the file, so substring matches
+    /// across token boundaries (e.g. "auth" in "OAuth") are kept.
+    pub fn candidates(&self, query: &str) -> HashSet<Posting> {
+        let mut result: Option<HashSet<Posting>> = None;
+        for part in tokenize(query) {
+            let matching: HashSet<Posting> = self
+                .tokens
+                .iter()
+                .filter(|(token, _)| token.contains(part.as_str()))
+                .flat_map(|(_, postings)| postings.iter().cloned())
+                .collect();
+            result = Some(match result {
+                Some(found) => found.intersection(&matching).cloned().collect(),
+                None => matching,
+            });
+        }
+        // A query without tokens (only punctuation) can't be narrowed down
+        result.unwrap_or_else(|| self.files.keys().filter_map(|key| split_key(key)).collect())
+    }
+}
+
+/// Lowercased runs of alphanumeric characters
+fn tokenize(text: &str) -> BTreeSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(|token| token.chars().flat_map(char::to_lowercase).collect())
+        .collect()
+}
+
+fn file_key((date, name): &Posting) -> String {
+    format!("{}/{}", date, name)
+}
+
+fn split_key(key: &str) -> Option<Posting> {
+    let (date, name) = key.split_once('/')?;
+    Some((date.to_string(), name.to_string()))
+}
+
+/// The file a session or daily summary is read from; the date's tarball once compressed
+fn source_path(manager: &ArchiveManager, date: &str, name: &str) -> PathBuf {
+    let path = if name == "daily" {
+        manager.daily_summary_path(date)
+    } else {
+        manager.session_archive_path(date, name)
+    };
+    if path.exists() {
+        path
+    } else {
+        manager.compressed_path(date)
+    }
+}
+
+fn modified_ms(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_millis() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_tokenize() {
+        let tokens: Vec<String> = tokenize("Fixed the OAuth-token bug; 修复登录")
+            .into_iter()
+            .collect();
+        assert_eq!(
+            tokens,
+            vec!["bug", "fixed", "oauth", "the", "token", "修复登录"]
+        );
+    }
+
+    #[test]
+    fn test_index_update_and_candidates() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+        let manager = ArchiveManager::new(config);
+
+        manager
+            .write_session(
+                "2026-01-16",
+                "proj",
+                "10_00-auth",
+                "## Summary\n\nFixed OAuth refresh.\n",
+            )
+            .unwrap();
+        manager
+            .write_session(
+                "2026-01-16",
+                "proj",
+                "11_00-db",
+                "## Summary\n\nTuned the DB pool.\n",
+            )
+            .unwrap();
+
+        let mut index = FullTextIndex::default();
+        assert!(index.update(&manager).unwrap());
+        assert!(!index.update(&manager).unwrap());
+        // Both sessions and the date's daily.md
+        assert_eq!(index.file_count(), 3);
+
+        let auth = ("2026-01-16".to_string(), "10_00-auth".to_string());
+        let db = ("2026-01-16".to_string(), "11_00-db".to_string());
+        assert_eq!(
+            index.candidates("auth refresh"),
+            HashSet::from([auth.clone()])
+        );
+        assert_eq!(index.candidates("the db"), HashSet::from([db.clone()]));
+        assert!(index.candidates("kubernetes").is_empty());
+
+        // Rewritten and deleted files are re-indexed
+        let path = manager.session_archive_path("2026-01-16", "11_00-db");
+        fs::write(&path, "## Summary\n\nMoved to Kubernetes.\n").unwrap();
+        let file = fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(5))
+            .unwrap();
+        fs::remove_file(manager.session_archive_path("2026-01-16", "10_00-auth")).unwrap();
+        assert!(index.update(&manager).unwrap());
+        assert_eq!(index.candidates("kubernetes"), HashSet::from([db]));
+        assert!(index.candidates("oauth").is_empty());
+        assert_eq!(index.file_count(), 2);
+    }
+}
@@ -1,9 +1,43 @@
 use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use super::compress;
+use super::frontmatter::{self, Frontmatter};
+use super::index::{FullTextIndex, INDEX_FILE};
 use super::templates::Templates;
-use crate::config::Config;
+use crate::config::{date_relative_path, Config, STORAGE_LAYOUTS};
+use crate::fs_util::write_atomic;
+use crate::summarizer::TemplateEngine;
+
+const UTF8_BOM: &str = "\u{feff}";
+
+/// Path template placeholders understood by the archive layout
+const DATE_VAR: &str = "{{date}}";
+const PROJECT_VAR: &str = "{{project}}";
+
+/// Storage subdirectories that are never project folders
+const RESERVED_DIRS: &[&str] = &["jobs", "weekly", "pending-skills", "prompts"];
+
+/// A session archive identified by its resume-chain metadata
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionLink {
+    pub date: String,
+    pub name: String,
+    pub title: String,
+    pub session_id: Option<String>,
+    pub parent_session_id: Option<String>,
+}
+
+/// What was removed by `ArchiveManager::delete_date`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeletedDate {
+    /// Session archive files removed
+    pub sessions: usize,
+    /// Whether the date had a generated digest
+    pub had_digest: bool,
+}
 
 /// Manages archive directory structure and file operations
 pub struct ArchiveManager {
@@ -33,9 +67,9 @@ impl ArchiveManager {
 
             // Initialize daily.md
             let daily_md = today_dir.join("daily.md");
-            let today = chrono::Local::now().format("%Y-%m-%d").to_string();
-            let content = Templates::daily_init(&today);
-            fs::write(&daily_md, content).context("Failed to write daily.md")?;
+            let content = Templates::daily_init(&self.config.today());
+            self.write_text(&daily_md, &content)
+                .context("Failed to write daily.md")?;
         }
 
         Ok(today_dir)
@@ -51,15 +85,104 @@ impl ArchiveManager {
             // Initialize daily.md
             let daily_md = date_dir.join("daily.md");
             let content = Templates::daily_init(date);
-            fs::write(&daily_md, content).context("Failed to write daily.md")?;
+            self.write_text(&daily_md, &content)
+                .context("Failed to write daily.md")?;
         }
 
         Ok(date_dir)
     }
 
-    /// Get path for a session archive file
+    /// Get path for a session archive file.
+    ///
+    /// Finds the session in the templated layout, falling back to the flat date directory.
     pub fn session_archive_path(&self, date: &str, task_name: &str) -> PathBuf {
-        self.config.date_dir(date).join(format!("{}.md", task_name))
+        let file_name = format!("{}.md", task_name);
+        self.session_dirs(Some(date))
+            .into_iter()
+            .map(|(dir, _)| dir.join(&file_name))
+            .find(|path| path.exists())
+            .unwrap_or_else(|| self.config.date_dir(date).join(file_name))
+    }
+
+    /// Get path for a new session archive of a project, following `output.path_template`
+    pub fn project_session_path(&self, date: &str, project: &str, task_name: &str) -> PathBuf {
+        let date_path = date_relative_path(&self.config.storage.layout, date);
+        let date_path = date_path.to_string_lossy();
+        let project = project_dir_name(project);
+        let mut vars = HashMap::new();
+        vars.insert("date", date_path.as_ref());
+        vars.insert("project", project.as_str());
+        let relative = TemplateEngine::render(&self.config.output.path_template, &vars);
+
+        self.config
+            .storage_path()
+            .join(relative)
+            .join(format!("{}.md", task_name))
+    }
+
+    /// Directories that may hold session archives, with the date each belongs to.
+    ///
+    /// Walks `output.path_template` component by component; `{{date}}` matches the
+    /// given date (or any date when `None`) in either storage layout, and `{{project}}`
+    /// matches any directory that isn't a date or year. The date directories directly
+    /// under storage are always included.
+    fn session_dirs(&self, date: Option<&str>) -> Vec<(PathBuf, String)> {
+        let storage = self.config.storage_path();
+        let mut found: Vec<(PathBuf, Option<String>)> = vec![(storage.clone(), None)];
+
+        for component in self.config.output.path_template.split('/') {
+            let mut next = Vec::new();
+            for (dir, matched_date) in found {
+                match component {
+                    DATE_VAR => match date {
+                        Some(d) => {
+                            for candidate in date_candidates(&dir, d) {
+                                next.push((candidate, Some(d.to_string())));
+                            }
+                        }
+                        None => {
+                            for (date_dir, name) in date_subdirs(&dir) {
+                                next.push((date_dir, Some(name)));
+                            }
+                        }
+                    },
+                    PROJECT_VAR => {
+                        for name in subdirectories(&dir) {
+                            if !is_date_dir(&name)
+                                && !is_year_dir(&name)
+                                && !RESERVED_DIRS.contains(&name.as_str())
+                            {
+                                next.push((dir.join(name), matched_date.clone()));
+                            }
+                        }
+                    }
+                    literal => next.push((dir.join(literal), matched_date)),
+                }
+            }
+            found = next;
+        }
+
+        let mut dirs: Vec<(PathBuf, String)> = found
+            .into_iter()
+            .filter_map(|(dir, d)| d.map(|d| (dir, d)))
+            .filter(|(dir, _)| dir.is_dir())
+            .collect();
+
+        // Date directories: archives written before a template was configured
+        let storage_dates = match date {
+            Some(d) => date_candidates(&storage, d)
+                .into_iter()
+                .map(|dir| (dir, d.to_string()))
+                .collect(),
+            None => date_subdirs(&storage),
+        };
+        for (dir, d) in storage_dates {
+            if dir.is_dir() && !dirs.iter().any(|(existing, _)| *existing == dir) {
+                dirs.push((dir, d));
+            }
+        }
+
+        dirs
     }
 
     /// Get path for the daily summary file
@@ -67,25 +190,208 @@ impl ArchiveManager {
         self.config.date_dir(date).join("daily.md")
     }
 
+    /// Get path for a digest preview, written next to daily.md without replacing it
+    pub fn daily_preview_path(&self, date: &str) -> PathBuf {
+        self.config.date_dir(date).join(".daily-preview.md")
+    }
+
+    /// Get path where `daily undigest` sets a daily summary aside
+    pub fn undigested_summary_path(&self, date: &str) -> PathBuf {
+        self.config.date_dir(date).join(".daily-undigested.md")
+    }
+
+    /// Set daily.md aside so the next digest is built from the session files alone.
+    ///
+    /// Digest never deletes sessions, so undoing one only needs the summary moved out
+    /// of the way. Refuses when the date has no session files, since daily.md would then
+    /// be the only record of that day, and when an earlier undigest's backup is still there.
+    pub fn undigest(&self, date: &str) -> Result<PathBuf> {
+        let path = self.daily_summary_path(date);
+        if !path.exists() {
+            anyhow::bail!("No daily.md for {}, nothing to undigest", date);
+        }
+        if self.list_sessions(date)?.is_empty() {
+            anyhow::bail!(
+                "No session files for {}: daily.md is the only record of that day, so it was left in place",
+                date
+            );
+        }
+
+        let backup = self.undigested_summary_path(date);
+        if backup.exists() {
+            anyhow::bail!(
+                "{} already holds a daily summary set aside earlier; move or delete it first",
+                backup.display()
+            );
+        }
+        fs::rename(&path, &backup).context(format!(
+            "Failed to move {} to {}",
+            path.display(),
+            backup.display()
+        ))?;
+        Ok(backup)
+    }
+
+    /// Delete every directory of a date's archive: the date directory and, with a
+    /// project layout, the date's directory under each project.
+    ///
+    /// Only `yyyy-mm-dd` dates are accepted, so a crafted date cannot escape storage.
+    pub fn delete_date(&self, date: &str) -> Result<DeletedDate> {
+        if date.contains('/')
+            || date.contains('\\')
+            || date.contains("..")
+            || chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err()
+        {
+            anyhow::bail!("Invalid date '{}', expected yyyy-mm-dd", date);
+        }
+
+        let dirs = self.session_dirs(Some(date));
+        let compressed = self.is_compressed(date);
+        if dirs.is_empty() && !compressed {
+            anyhow::bail!("No archive found for {}", date);
+        }
+
+        let deleted = DeletedDate {
+            sessions: self.list_sessions(date)?.len(),
+            had_digest: self.has_digest(date),
+        };
+        for (dir, _) in dirs {
+            fs::remove_dir_all(&dir).context(format!("Failed to delete {}", dir.display()))?;
+        }
+        if compressed {
+            let archive = self.compressed_path(date);
+            fs::remove_file(&archive).context(format!("Failed to delete {}", archive.display()))?;
+        }
+        Ok(deleted)
+    }
+
+    /// Get path of a date's compressed archive (`<storage>/<date>.tar.gz`)
+    pub fn compressed_path(&self, date: &str) -> PathBuf {
+        compress::tarball_path(&self.config.storage_path(), date)
+    }
+
+    /// Whether a date has been compressed by `daily archive`
+    pub fn is_compressed(&self, date: &str) -> bool {
+        self.compressed_path(date).is_file()
+    }
+
+    /// Pack every directory of a date into `<date>.tar.gz` and remove the originals.
+    ///
+    /// Reads fall back to the tarball, so the date stays viewable afterwards.
+    pub fn compress_date(&self, date: &str) -> Result<PathBuf> {
+        let archive = self.compressed_path(date);
+        if archive.exists() {
+            anyhow::bail!("{} is already compressed", date);
+        }
+        let dirs: Vec<PathBuf> = self
+            .session_dirs(Some(date))
+            .into_iter()
+            .map(|(dir, _)| dir)
+            .collect();
+        if dirs.is_empty() {
+            anyhow::bail!("No archive found for {}", date);
+        }
+
+        compress::pack(&self.config.storage_path(), &dirs, &archive)?;
+        for dir in &dirs {
+            fs::remove_dir_all(dir).context(format!("Failed to remove {}", dir.display()))?;
+        }
+        Ok(archive)
+    }
+
+    /// Date directories that move when storage switches to `layout`, as (from, to) pairs.
+    ///
+    /// Covers the date directories under storage and, with a project path template, under
+    /// each project. Fails if any destination already exists, so nothing is merged.
+    pub fn layout_moves(&self, layout: &str) -> Result<Vec<(PathBuf, PathBuf)>> {
+        let mut moves = Vec::new();
+        for (dir, date) in self.session_dirs(None) {
+            let base = if dir.ends_with(date_relative_path("nested", &date)) {
+                dir.ancestors().nth(3)
+            } else {
+                dir.parent()
+            };
+            let Some(base) = base else {
+                continue;
+            };
+            let target = base.join(date_relative_path(layout, &date));
+            if target != dir {
+                moves.push((dir, target));
+            }
+        }
+
+        if let Some((from, to)) = moves.iter().find(|(_, to)| to.exists()) {
+            anyhow::bail!(
+                "Cannot move {} to {}: the destination already exists. Merge the two directories by hand, then run again",
+                from.display(),
+                to.display()
+            );
+        }
+        Ok(moves)
+    }
+
+    /// Move every date directory into `layout`, removing year and month directories
+    /// left empty. Returns the moves made.
+    pub fn migrate_layout(&self, layout: &str) -> Result<Vec<(PathBuf, PathBuf)>> {
+        let moves = self.layout_moves(layout)?;
+        for (from, to) in &moves {
+            if let Some(parent) = to.parent() {
+                fs::create_dir_all(parent)
+                    .context(format!("Failed to create {}", parent.display()))?;
+            }
+            fs::rename(from, to).context(format!(
+                "Failed to move {} to {}",
+                from.display(),
+                to.display()
+            ))?;
+            // remove_dir only succeeds on empty directories
+            for dir in from.ancestors().skip(1).take(2) {
+                let is_layout_dir = dir
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().chars().all(|c| c.is_ascii_digit()));
+                if !is_layout_dir || fs::remove_dir(dir).is_err() {
+                    break;
+                }
+            }
+        }
+        Ok(moves)
+    }
+
     /// List all session archives for a date
     pub fn list_sessions(&self, date: &str) -> Result<Vec<String>> {
-        let date_dir = self.config.date_dir(date);
+        let mut sessions = Vec::new();
 
-        if !date_dir.exists() {
-            return Ok(Vec::new());
+        let archive = self.compressed_path(date);
+        if archive.is_file() {
+            for path in compress::list_files(&archive)? {
+                if path.extension().is_some_and(|ext| ext == "md") {
+                    if let Some(name) = path.file_stem() {
+                        let name_str = name.to_string_lossy().to_string();
+                        if name_str != "daily"
+                            && !name_str.starts_with('.')
+                            && !sessions.contains(&name_str)
+                        {
+                            sessions.push(name_str);
+                        }
+                    }
+                }
+            }
         }
 
-        let mut sessions = Vec::new();
-        for entry in fs::read_dir(&date_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-
-            if path.is_file() {
-                if let Some(name) = path.file_stem() {
-                    let name_str = name.to_string_lossy().to_string();
-                    // Skip daily.md
-                    if name_str != "daily" {
-                        sessions.push(name_str);
+        for (dir, _) in self.session_dirs(Some(date)) {
+            for entry in fs::read_dir(&dir)? {
+                let path = entry?.path();
+
+                if path.is_file() && path.extension().is_some_and(|ext| ext == "md") {
+                    if let Some(name) = path.file_stem() {
+                        let name_str = name.to_string_lossy().to_string();
+                        // Skip daily.md and hidden files such as the digest preview
+                        if name_str != "daily"
+                            && !name_str.starts_with('.')
+                            && !sessions.contains(&name_str)
+                        {
+                            sessions.push(name_str);
+                        }
                     }
                 }
             }
@@ -97,54 +403,117 @@ impl ArchiveManager {
 
     /// List all available dates in the archive
     pub fn list_dates(&self) -> Result<Vec<String>> {
-        let storage_path = self.config.storage_path();
-
-        if !storage_path.exists() {
+        if !self.config.storage_path().exists() {
             return Ok(Vec::new());
         }
 
-        let mut dates = Vec::new();
-        for entry in fs::read_dir(&storage_path)? {
-            let entry = entry?;
-            let path = entry.path();
+        let mut dates: Vec<String> = self
+            .session_dirs(None)
+            .into_iter()
+            .map(|(_, date)| date)
+            .collect();
 
-            if path.is_dir() {
-                if let Some(name) = path.file_name() {
-                    let name_str = name.to_string_lossy().to_string();
-                    // Check if it looks like a date (yyyy-mm-dd)
-                    if name_str.len() == 10 && name_str.chars().nth(4) == Some('-') {
-                        dates.push(name_str);
-                    }
-                }
-            }
-        }
+        // Dates packed by `daily archive`
+        dates.extend(
+            file_names(&self.config.storage_path())
+                .iter()
+                .filter_map(|name| compress::tarball_date(name))
+                .filter(|date| is_date_dir(date))
+                .map(str::to_string),
+        );
 
         dates.sort();
+        dates.dedup();
         dates.reverse(); // Most recent first
         Ok(dates)
     }
 
+    /// The full-text search index, updated for files changed since it was last saved
+    pub fn full_text_index(&self) -> Result<FullTextIndex> {
+        let path = self.config.storage_path().join(INDEX_FILE);
+        let mut index = FullTextIndex::load(&path);
+        if index.update(self)? {
+            index.save(&path)?;
+        }
+        Ok(index)
+    }
+
+    /// Build the full-text search index from scratch, replacing the cached one
+    pub fn rebuild_full_text_index(&self) -> Result<FullTextIndex> {
+        let mut index = FullTextIndex::default();
+        index.update(self)?;
+        self.ensure_storage_dir()?;
+        index.save(&self.config.storage_path().join(INDEX_FILE))?;
+        Ok(index)
+    }
+
     /// Read a session archive file
     pub fn read_session(&self, date: &str, task_name: &str) -> Result<String> {
         let path = self.session_archive_path(date, task_name);
-        fs::read_to_string(&path).context(format!(
+        if !path.exists() {
+            let file_name = format!("{}.md", task_name);
+            if let Some(content) = self.read_compressed(date, |entry| {
+                entry
+                    .file_name()
+                    .is_some_and(|name| name == file_name.as_str())
+            })? {
+                return Ok(content);
+            }
+        }
+        let content = fs::read_to_string(&path).context(format!(
             "Failed to read session archive: {}",
             path.display()
-        ))
+        ))?;
+        Ok(strip_bom(&content).to_string())
     }
 
     /// Read the daily summary file
     pub fn read_daily_summary(&self, date: &str) -> Result<String> {
         let path = self.daily_summary_path(date);
-        fs::read_to_string(&path)
-            .context(format!("Failed to read daily summary: {}", path.display()))
+        if !path.exists() {
+            let daily: Vec<PathBuf> = date_candidates(Path::new(""), date)
+                .into_iter()
+                .map(|dir| dir.join("daily.md"))
+                .collect();
+            if let Some(content) =
+                self.read_compressed(date, |entry| daily.iter().any(|d| d == entry))?
+            {
+                return Ok(content);
+            }
+        }
+        let content = fs::read_to_string(&path)
+            .context(format!("Failed to read daily summary: {}", path.display()))?;
+        Ok(strip_bom(&content).to_string())
     }
 
-    /// Write a session archive file
-    pub fn write_session(&self, date: &str, task_name: &str, content: &str) -> Result<PathBuf> {
+    /// Read a file from a date's tarball, if the date is compressed and has one matching
+    fn read_compressed(
+        &self,
+        date: &str,
+        matches: impl Fn(&Path) -> bool,
+    ) -> Result<Option<String>> {
+        let archive = self.compressed_path(date);
+        if !archive.is_file() {
+            return Ok(None);
+        }
+        Ok(compress::read_file(&archive, matches)?.map(|content| strip_bom(&content).to_string()))
+    }
+
+    /// Write a session archive into its project's templated location
+    pub fn write_session(
+        &self,
+        date: &str,
+        project: &str,
+        task_name: &str,
+        content: &str,
+    ) -> Result<PathBuf> {
+        // daily.md stays in the date directory regardless of the layout
         self.ensure_date_dir(date)?;
-        let path = self.session_archive_path(date, task_name);
-        fs::write(&path, content).context(format!(
+        let path = self.project_session_path(date, project, task_name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create session directory")?;
+        }
+        self.write_text(&path, content).context(format!(
             "Failed to write session archive: {}",
             path.display()
         ))?;
@@ -155,11 +524,301 @@ impl ArchiveManager {
     pub fn write_daily_summary(&self, date: &str, content: &str) -> Result<PathBuf> {
         self.ensure_date_dir(date)?;
         let path = self.daily_summary_path(date);
-        fs::write(&path, content)
+        self.write_text(&path, content)
             .context(format!("Failed to write daily summary: {}", path.display()))?;
         Ok(path)
     }
 
+    /// Write a digest preview file
+    pub fn write_daily_preview(&self, date: &str, content: &str) -> Result<PathBuf> {
+        self.ensure_date_dir(date)?;
+        let path = self.daily_preview_path(date);
+        self.write_text(&path, content).context(format!(
+            "Failed to write digest preview: {}",
+            path.display()
+        ))?;
+        Ok(path)
+    }
+
+    /// Get path for a weekly digest file (e.g. weekly/2026-W03.md)
+    pub fn weekly_summary_path(&self, week: &str) -> PathBuf {
+        self.config
+            .storage_path()
+            .join("weekly")
+            .join(format!("{}.md", week))
+    }
+
+    /// Write a weekly digest file
+    pub fn write_weekly_summary(&self, week: &str, content: &str) -> Result<PathBuf> {
+        let path = self.weekly_summary_path(week);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create weekly directory")?;
+        }
+        self.write_text(&path, content)
+            .context(format!("Failed to write weekly digest: {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Read the resume-chain metadata of every session archive, oldest first
+    pub fn session_links(&self) -> Result<Vec<SessionLink>> {
+        let mut links = Vec::new();
+
+        for date in self.list_dates()?.into_iter().rev() {
+            for name in self.list_sessions(&date)? {
+                let Ok(content) = self.read_session(&date, &name) else {
+                    continue;
+                };
+                let frontmatter = Frontmatter::parse(&content);
+                links.push(session_link(&date, name, &frontmatter));
+            }
+        }
+
+        Ok(links)
+    }
+
+    /// Sessions whose tags include `tag`, on one date or across all dates (newest first)
+    pub fn sessions_with_tag(&self, tag: &str, date: Option<&str>) -> Result<Vec<SessionLink>> {
+        let dates = match date {
+            Some(date) => vec![date.to_string()],
+            None => self.list_dates()?,
+        };
+        let mut found = Vec::new();
+
+        for date in dates {
+            for name in self.list_sessions(&date)? {
+                let Ok(content) = self.read_session(&date, &name) else {
+                    continue;
+                };
+                let frontmatter = Frontmatter::parse(&content);
+                if frontmatter.get_list("tags").iter().any(|t| t == tag) {
+                    found.push(session_link(&date, name, &frontmatter));
+                }
+            }
+        }
+
+        Ok(found)
+    }
+
+    /// Number of session archives carrying each tag, most used first
+    pub fn tag_counts(&self) -> Result<Vec<(String, usize)>> {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+
+        for date in self.list_dates()? {
+            for name in self.list_sessions(&date)? {
+                let Ok(content) = self.read_session(&date, &name) else {
+                    continue;
+                };
+                for tag in Frontmatter::parse(&content).get_list("tags") {
+                    *counts.entry(tag).or_default() += 1;
+                }
+            }
+        }
+
+        let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(counts)
+    }
+
+    /// Find a session on a date whose frontmatter records the given content hash
+    pub fn session_exists_with_hash(&self, date: &str, hash: &str) -> Result<Option<String>> {
+        for name in self.list_sessions(date)? {
+            let Ok(content) = self.read_session(date, &name) else {
+                continue;
+            };
+            if Frontmatter::parse(&content).get("content_hash") == Some(hash) {
+                return Ok(Some(name));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Find the archive of a session by its session ID
+    pub fn find_session_by_id(&self, session_id: &str) -> Result<Option<SessionLink>> {
+        Ok(self
+            .session_links()?
+            .into_iter()
+            .find(|link| link.session_id.as_deref() == Some(session_id)))
+    }
+
+    /// Walk the resume chain containing a session, from the original session onward
+    pub fn resume_chain(&self, date: &str, name: &str) -> Result<Vec<SessionLink>> {
+        let links = self.session_links()?;
+        let start = links
+            .iter()
+            .find(|link| link.date == date && link.name == name)
+            .cloned()
+            .with_context(|| format!("Session not found: {}/{}", date, name))?;
+
+        let by_id = |id: &str| {
+            links
+                .iter()
+                .find(|link| link.session_id.as_deref() == Some(id))
+                .cloned()
+        };
+
+        let mut seen = HashSet::new();
+        seen.insert((start.date.clone(), start.name.clone()));
+
+        // Walk back to the original session
+        let mut chain = vec![start.clone()];
+        while let Some(parent) = chain[0].parent_session_id.as_deref().and_then(by_id) {
+            if !seen.insert((parent.date.clone(), parent.name.clone())) {
+                break;
+            }
+            chain.insert(0, parent);
+        }
+
+        // Walk forward through sessions resumed from the last one
+        let mut current = start;
+        while let Some(child) = current.session_id.as_deref().and_then(|id| {
+            links
+                .iter()
+                .find(|link| link.parent_session_id.as_deref() == Some(id))
+                .cloned()
+        }) {
+            if !seen.insert((child.date.clone(), child.name.clone())) {
+                break;
+            }
+            chain.push(child.clone());
+            current = child;
+        }
+
+        Ok(chain)
+    }
+
+    /// Rename a tag across all session archives, returning the number of files changed
+    pub fn retag(&self, old: &str, new: &str) -> Result<usize> {
+        let mut changed = 0;
+        for date in self.list_dates()? {
+            changed += self.update_session_tags(&date, |tags| {
+                if !tags.iter().any(|t| t == old) {
+                    return false;
+                }
+                let had_new = tags.iter().any(|t| t == new);
+                tags.retain(|t| t != old || !had_new);
+                for tag in tags.iter_mut().filter(|t| *t == old) {
+                    *tag = new.to_string();
+                }
+                true
+            })?;
+        }
+        Ok(changed)
+    }
+
+    /// Add a tag to every session archive of a date, returning the number of files changed
+    pub fn add_tag(&self, tag: &str, date: &str) -> Result<usize> {
+        self.update_session_tags(date, |tags| {
+            if tags.iter().any(|t| t == tag) {
+                return false;
+            }
+            tags.push(tag.to_string());
+            true
+        })
+    }
+
+    /// Rename a session archive, updating its `title:` frontmatter and `# ` heading.
+    ///
+    /// The file stays in its directory; the new name must be a safe file name that no
+    /// other session of the date already uses.
+    pub fn rename_session(&self, date: &str, old: &str, new: &str) -> Result<PathBuf> {
+        if !is_safe_session_name(new) {
+            anyhow::bail!(
+                "Invalid session name '{}': use letters, digits, '-', '_' or spaces",
+                new
+            );
+        }
+        if old == new {
+            anyhow::bail!("Session is already named '{}'", new);
+        }
+
+        let sessions = self.list_sessions(date)?;
+        if !sessions.iter().any(|s| s == old) {
+            anyhow::bail!("Session not found: {}/{}", date, old);
+        }
+        if sessions.iter().any(|s| s == new) {
+            anyhow::bail!("A session named '{}' already exists on {}", new, date);
+        }
+
+        let path = self.session_archive_path(date, old);
+        if !path.exists() {
+            anyhow::bail!("{} is compressed; extract it before renaming", date);
+        }
+        let content = self.read_session(date, old)?;
+        let content = frontmatter::set(&content, "title", new).unwrap_or(content);
+        let content = replace_heading(&content, new);
+
+        let new_path = path.with_file_name(format!("{}.md", new));
+        self.write_text(&new_path, &content)
+            .context(format!("Failed to write {}", new_path.display()))?;
+        fs::remove_file(&path).context(format!("Failed to remove {}", path.display()))?;
+        Ok(new_path)
+    }
+
+    /// Overwrite an existing session file in place, wherever the layout put it
+    pub fn replace_session(&self, date: &str, name: &str, content: &str) -> Result<PathBuf> {
+        let path = self.session_archive_path(date, name);
+        if !path.exists() {
+            if self.is_compressed(date) {
+                anyhow::bail!(
+                    "{} is compressed; extract it before replacing sessions",
+                    date
+                );
+            }
+            anyhow::bail!("Session not found: {}/{}", date, name);
+        }
+        self.write_text(&path, content)
+            .context(format!("Failed to replace {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Apply a tag edit to each session of a date; files are rewritten only if `edit` returns true
+    fn update_session_tags(
+        &self,
+        date: &str,
+        mut edit: impl FnMut(&mut Vec<String>) -> bool,
+    ) -> Result<usize> {
+        let mut changed = 0;
+
+        for name in self.list_sessions(date)? {
+            let content = self.read_session(date, &name)?;
+            let mut tags = Frontmatter::parse(&content).get_list("tags");
+            if !edit(&mut tags) {
+                continue;
+            }
+
+            let Some(updated) = frontmatter::set_list(&content, "tags", &tags) else {
+                continue;
+            };
+            let path = self.session_archive_path(date, &name);
+            self.write_text(&path, &updated)
+                .context(format!("Failed to replace {}", path.display()))?;
+            changed += 1;
+        }
+
+        Ok(changed)
+    }
+
+    /// Write an archive file atomically, adding a UTF-8 BOM when `output.utf8_bom` is set
+    fn write_text(&self, path: &Path, content: &str) -> std::io::Result<()> {
+        let content = strip_bom(content);
+        if self.config.output.utf8_bom {
+            write_atomic(path, format!("{}{}", UTF8_BOM, content))
+        } else {
+            write_atomic(path, content)
+        }
+    }
+
+    /// Check if a date's daily.md contains a generated digest rather than a placeholder
+    pub fn has_digest(&self, date: &str) -> bool {
+        self.read_daily_summary(date)
+            .map(|content| {
+                content.contains("## Overview")
+                    && !content.contains("No sessions recorded yet")
+                    && !content.contains("_No sessions archived yet._")
+            })
+            .unwrap_or(false)
+    }
+
     /// Check if a date has session files (un-digested sessions)
     pub fn has_sessions(&self, date: &str) -> bool {
         match self.list_sessions(date) {
@@ -169,6 +828,141 @@ impl ArchiveManager {
     }
 }
 
+/// Build the resume-chain link of a session from its frontmatter
+fn session_link(date: &str, name: String, frontmatter: &Frontmatter) -> SessionLink {
+    SessionLink {
+        title: frontmatter.get("title").unwrap_or(&name).to_string(),
+        session_id: frontmatter.get("session_id").map(String::from),
+        parent_session_id: frontmatter.get("parent_session_id").map(String::from),
+        date: date.to_string(),
+        name,
+    }
+}
+
+/// Names of the immediate subdirectories of a directory
+fn subdirectories(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect()
+}
+
+/// Date directories below `dir` in either layout: `<date>` or `<yyyy>/<mm>/<date>`
+fn date_subdirs(dir: &Path) -> Vec<(PathBuf, String)> {
+    let mut found = Vec::new();
+    for name in subdirectories(dir) {
+        if is_date_dir(&name) {
+            found.push((dir.join(&name), name));
+        } else if is_year_dir(&name) {
+            let year_dir = dir.join(&name);
+            for month in subdirectories(&year_dir) {
+                let prefix = format!("{}-{}-", name, month);
+                let month_dir = year_dir.join(&month);
+                for date in subdirectories(&month_dir) {
+                    if is_date_dir(&date) && date.starts_with(&prefix) {
+                        found.push((month_dir.join(&date), date));
+                    }
+                }
+            }
+        }
+    }
+    found
+}
+
+/// Where a date's directory can be below `dir`, one path per storage layout
+fn date_candidates(dir: &Path, date: &str) -> Vec<PathBuf> {
+    let mut candidates: Vec<PathBuf> = STORAGE_LAYOUTS
+        .iter()
+        .map(|layout| dir.join(date_relative_path(layout, date)))
+        .collect();
+    candidates.dedup();
+    candidates
+}
+
+/// Names of the regular files directly inside `dir`
+fn file_names(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect()
+}
+
+/// Whether a session name is safe as a file name on every platform
+fn is_safe_session_name(name: &str) -> bool {
+    !name.trim().is_empty()
+        && name.trim() == name
+        && name != "daily"
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | ' ' | '.'))
+        && !name.contains("..")
+}
+
+/// Replace the first `# ` heading of a markdown body
+fn replace_heading(content: &str, title: &str) -> String {
+    let mut replaced = false;
+    let lines: Vec<String> = content
+        .split('\n')
+        .map(|line| {
+            if !replaced && line.starts_with("# ") {
+                replaced = true;
+                let ending = if line.ends_with('\r') { "\r" } else { "" };
+                format!("# {}{}", title, ending)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Check if a directory name looks like a date (yyyy-mm-dd)
+fn is_date_dir(name: &str) -> bool {
+    name.len() == 10 && name.chars().nth(4) == Some('-')
+}
+
+/// Whether a directory name is the year level of the nested layout
+fn is_year_dir(name: &str) -> bool {
+    name.len() == 4 && name.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Directory name for `{{project}}`: a single safe path component that can't be
+/// mistaken for a date, a year or a reserved storage directory (`2026` → `project-2026`)
+fn project_dir_name(project: &str) -> String {
+    let name: String = project
+        .chars()
+        .map(|c| {
+            if c.is_control() || "/\\:*?\"<>|".contains(c) {
+                '-'
+            } else {
+                c
+            }
+        })
+        .collect();
+    let name = name.trim().trim_start_matches('.');
+    if name.is_empty() {
+        return "unknown".to_string();
+    }
+    if is_date_dir(name) || is_year_dir(name) || RESERVED_DIRS.contains(&name) {
+        return format!("project-{}", name);
+    }
+    name.to_string()
+}
+
+/// Remove a leading UTF-8 byte order mark, if any
+fn strip_bom(content: &str) -> &str {
+    content.strip_prefix(UTF8_BOM).unwrap_or(content)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -191,6 +985,73 @@ mod tests {
         assert!(today_dir.join("daily.md").exists());
     }
 
+    #[test]
+    fn test_undigest_sets_daily_summary_aside() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = ArchiveManager::new(test_config(&temp_dir));
+
+        manager
+            .write_daily_summary("2026-01-16", "## Overview\n\nOnly record.\n")
+            .unwrap();
+        let err = manager.undigest("2026-01-16").unwrap_err().to_string();
+        assert!(err.contains("only record"));
+        assert!(manager.read_daily_summary("2026-01-16").is_ok());
+
+        manager
+            .write_session("2026-01-16", "proj", "10_00-task", "## Summary\n\nWork.\n")
+            .unwrap();
+        let backup = manager.undigest("2026-01-16").unwrap();
+        assert_eq!(backup, manager.undigested_summary_path("2026-01-16"));
+        assert!(backup.exists());
+        assert!(manager.read_daily_summary("2026-01-16").is_err());
+        assert_eq!(
+            manager.list_sessions("2026-01-16").unwrap(),
+            vec!["10_00-task"]
+        );
+        assert!(manager.undigest("2026-01-16").is_err());
+
+        // A second undigest keeps the first backup
+        manager
+            .write_daily_summary("2026-01-16", "## Overview\n\nRedigested.\n")
+            .unwrap();
+        let err = manager.undigest("2026-01-16").unwrap_err().to_string();
+        assert!(err.contains("set aside earlier"));
+        assert!(fs::read_to_string(&backup)
+            .unwrap()
+            .contains("Only record."));
+        assert!(manager.read_daily_summary("2026-01-16").is_ok());
+    }
+
+    #[test]
+    fn test_session_exists_with_hash() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = ArchiveManager::new(test_config(&temp_dir));
+
+        manager
+            .write_session(
+                "2026-01-16",
+                "proj",
+                "10_00-task",
+                "---\ntitle: \"Task\"\ncontent_hash: ab12\n---\n\n# Task\n",
+            )
+            .unwrap();
+
+        assert_eq!(
+            manager
+                .session_exists_with_hash("2026-01-16", "ab12")
+                .unwrap(),
+            Some("10_00-task".to_string())
+        );
+        assert!(manager
+            .session_exists_with_hash("2026-01-16", "cd34")
+            .unwrap()
+            .is_none());
+        assert!(manager
+            .session_exists_with_hash("2026-01-17", "ab12")
+            .unwrap()
+            .is_none());
+    }
+
     #[test]
     fn test_list_sessions_empty() {
         let temp_dir = TempDir::new().unwrap();
@@ -200,4 +1061,399 @@ mod tests {
         let sessions = manager.list_sessions("2026-01-16").unwrap();
         assert!(sessions.is_empty());
     }
+
+    fn write_linked_session(
+        manager: &ArchiveManager,
+        date: &str,
+        name: &str,
+        id: &str,
+        parent: Option<&str>,
+    ) {
+        let parent_line = parent
+            .map(|p| format!("parent_session_id: {}\n", p))
+            .unwrap_or_default();
+        let content = format!(
+            "---\ntitle: \"{}\"\nsession_id: {}\n{}---\n\n# {}\n",
+            name, id, parent_line, name
+        );
+        manager.write_session(date, "proj", name, &content).unwrap();
+    }
+
+    #[test]
+    fn test_resume_chain() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = ArchiveManager::new(test_config(&temp_dir));
+
+        write_linked_session(&manager, "2026-01-15", "10_00-start", "a", None);
+        write_linked_session(&manager, "2026-01-15", "14_00-resume", "b", Some("a"));
+        write_linked_session(&manager, "2026-01-16", "09_00-finish", "c", Some("b"));
+        write_linked_session(&manager, "2026-01-16", "11_00-other", "d", None);
+
+        let chain = manager.resume_chain("2026-01-15", "14_00-resume").unwrap();
+        let names: Vec<&str> = chain.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(names, vec!["10_00-start", "14_00-resume", "09_00-finish"]);
+
+        let alone = manager.resume_chain("2026-01-16", "11_00-other").unwrap();
+        assert_eq!(alone.len(), 1);
+
+        let found = manager.find_session_by_id("b").unwrap().unwrap();
+        assert_eq!(found.date, "2026-01-15");
+        assert!(manager.find_session_by_id("missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_delete_date() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = ArchiveManager::new(test_config(&temp_dir));
+
+        manager
+            .write_session("2026-01-16", "proj", "10_00-a", "# a\n")
+            .unwrap();
+        // Only the daily_init placeholder so far
+        assert!(!manager.has_digest("2026-01-16"));
+        manager
+            .write_daily_summary("2026-01-16", "## Overview\n\nDone.\n")
+            .unwrap();
+        manager
+            .write_session("2026-01-17", "proj", "10_00-b", "# b\n")
+            .unwrap();
+
+        assert_eq!(
+            manager.delete_date("2026-01-16").unwrap(),
+            DeletedDate {
+                sessions: 1,
+                had_digest: true
+            }
+        );
+        assert_eq!(manager.list_dates().unwrap(), vec!["2026-01-17"]);
+
+        assert!(manager.delete_date("2026-01-16").is_err());
+        assert!(manager.delete_date("../2026-01-17").is_err());
+        assert!(manager.delete_date("2026-01-17/..").is_err());
+        assert!(manager.delete_date("jobs").is_err());
+        assert!(temp_dir.path().join("2026-01-17").is_dir());
+    }
+
+    #[test]
+    fn test_compress_date_keeps_archive_readable() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = ArchiveManager::new(test_config(&temp_dir));
+
+        manager
+            .write_session("2026-01-16", "proj", "10_00-a", "# a\n")
+            .unwrap();
+        manager
+            .write_daily_summary("2026-01-16", "## Overview\n\nDone.\n")
+            .unwrap();
+        manager
+            .write_session("2026-01-17", "proj", "10_00-b", "# b\n")
+            .unwrap();
+
+        let archive = manager.compress_date("2026-01-16").unwrap();
+        assert!(archive.is_file());
+        assert!(!manager.config.date_dir("2026-01-16").exists());
+        assert!(manager.compress_date("2026-01-16").is_err());
+
+        assert_eq!(
+            manager.list_dates().unwrap(),
+            vec!["2026-01-17", "2026-01-16"]
+        );
+        assert_eq!(
+            manager.list_sessions("2026-01-16").unwrap(),
+            vec!["10_00-a"]
+        );
+        assert_eq!(
+            manager.read_session("2026-01-16", "10_00-a").unwrap(),
+            "# a\n"
+        );
+        assert!(manager.has_digest("2026-01-16"));
+        assert!(manager.read_session("2026-01-16", "missing").is_err());
+
+        let deleted = manager.delete_date("2026-01-16").unwrap();
+        assert_eq!(deleted.sessions, 1);
+        assert!(!archive.exists());
+        assert_eq!(manager.list_dates().unwrap(), vec!["2026-01-17"]);
+    }
+
+    #[test]
+    fn test_rename_session() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = ArchiveManager::new(test_config(&temp_dir));
+
+        let session = |title: &str| {
+            format!(
+                "---\ntitle: \"{}\"\ndate: 2026-01-16\n---\n\n# {}\n\n## Summary\n\n# not a heading to touch\n",
+                title, title
+            )
+        };
+        manager
+            .write_session(
+                "2026-01-16",
+                "proj",
+                "10_00-wrong-title",
+                &session("10_00-wrong-title"),
+            )
+            .unwrap();
+        manager
+            .write_session("2026-01-16", "proj", "11_00-other", &session("11_00-other"))
+            .unwrap();
+
+        assert!(manager
+            .rename_session("2026-01-16", "10_00-wrong-title", "11_00-other")
+            .is_err());
+        assert!(manager
+            .rename_session("2026-01-16", "10_00-wrong-title", "../escape")
+            .is_err());
+        assert!(manager
+            .rename_session("2026-01-16", "missing", "10_00-new")
+            .is_err());
+        assert!(manager
+            .replace_session("2026-01-16", "missing", "content")
+            .is_err());
+        let replaced = manager
+            .replace_session("2026-01-16", "11_00-other", "replaced")
+            .unwrap();
+        assert!(replaced.ends_with("11_00-other.md"));
+        assert_eq!(
+            manager.read_session("2026-01-16", "11_00-other").unwrap(),
+            "replaced"
+        );
+
+        let path = manager
+            .rename_session("2026-01-16", "10_00-wrong-title", "10_00-right-title")
+            .unwrap();
+        assert!(path.ends_with("10_00-right-title.md"));
+        assert_eq!(
+            manager.list_sessions("2026-01-16").unwrap(),
+            vec!["10_00-right-title", "11_00-other"]
+        );
+        let content = manager
+            .read_session("2026-01-16", "10_00-right-title")
+            .unwrap();
+        assert_eq!(
+            Frontmatter::parse(&content).get("title"),
+            Some("10_00-right-title")
+        );
+        assert!(content.contains("\n# 10_00-right-title\n"));
+        assert!(content.contains("\n# not a heading to touch\n"));
+    }
+
+    #[test]
+    fn test_retag_and_add_tag() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = ArchiveManager::new(test_config(&temp_dir));
+
+        let session = |tags: &str| format!("---\ntitle: \"x\"\ntags: [{}]\n---\n\n# x\n", tags);
+        manager
+            .write_session("2026-01-15", "proj", "10_00-a", &session("claude-code, db"))
+            .unwrap();
+        manager
+            .write_session("2026-01-16", "proj", "10_00-b", &session("db, database"))
+            .unwrap();
+        manager
+            .write_session("2026-01-16", "proj", "11_00-c", &session("claude-code"))
+            .unwrap();
+
+        assert_eq!(manager.retag("db", "database").unwrap(), 2);
+        let tags = |date: &str, name: &str| {
+            Frontmatter::parse(&manager.read_session(date, name).unwrap()).get_list("tags")
+        };
+        assert_eq!(
+            tags("2026-01-15", "10_00-a"),
+            vec!["claude-code", "database"]
+        );
+        assert_eq!(tags("2026-01-16", "10_00-b"), vec!["database"]);
+
+        let names = |links: Vec<SessionLink>| links.into_iter().map(|l| l.name).collect::<Vec<_>>();
+        assert_eq!(
+            names(manager.sessions_with_tag("database", None).unwrap()),
+            vec!["10_00-b", "10_00-a"]
+        );
+        assert_eq!(
+            names(
+                manager
+                    .sessions_with_tag("database", Some("2026-01-15"))
+                    .unwrap()
+            ),
+            vec!["10_00-a"]
+        );
+        assert_eq!(
+            manager.tag_counts().unwrap(),
+            vec![("claude-code".to_string(), 2), ("database".to_string(), 2)]
+        );
+
+        assert_eq!(manager.add_tag("archived", "2026-01-16").unwrap(), 2);
+        assert_eq!(manager.add_tag("archived", "2026-01-16").unwrap(), 0);
+        assert_eq!(
+            tags("2026-01-16", "11_00-c"),
+            vec!["claude-code", "archived"]
+        );
+        assert_eq!(
+            tags("2026-01-15", "10_00-a"),
+            vec!["claude-code", "database"]
+        );
+    }
+
+    #[test]
+    fn test_utf8_bom_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = test_config(&temp_dir);
+        config.output.utf8_bom = true;
+        let manager = ArchiveManager::new(config);
+
+        let content = "---\ntitle: \"x\"\ntags: [a]\n---\n\n# x\n";
+        let path = manager
+            .write_session("2026-01-16", "proj", "10_00-x", content)
+            .unwrap();
+
+        let raw = fs::read(&path).unwrap();
+        assert!(raw.starts_with(&[0xEF, 0xBB, 0xBF]));
+        assert_eq!(
+            manager.read_session("2026-01-16", "10_00-x").unwrap(),
+            content
+        );
+
+        // Tag rewrites keep a single BOM
+        manager.add_tag("b", "2026-01-16").unwrap();
+        let raw = fs::read_to_string(&path).unwrap();
+        assert!(raw.starts_with(UTF8_BOM));
+        assert!(!raw[UTF8_BOM.len()..].starts_with(UTF8_BOM));
+        assert!(raw.contains("tags: [a, b]"));
+    }
+
+    #[test]
+    fn test_project_path_template() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = test_config(&temp_dir);
+        config.output.path_template = "{{project}}/{{date}}".to_string();
+        let manager = ArchiveManager::new(config);
+
+        let path = manager
+            .write_session("2026-01-16", "api", "10_00-auth", "# auth\n")
+            .unwrap();
+        assert_eq!(path, temp_dir.path().join("api/2026-01-16/10_00-auth.md"));
+        manager
+            .write_session("2026-01-16", "web", "11_00-ui", "# ui\n")
+            .unwrap();
+        // Archives from the flat layout are still found
+        fs::create_dir_all(temp_dir.path().join("2026-01-15")).unwrap();
+        fs::write(temp_dir.path().join("2026-01-15/09_00-old.md"), "# old\n").unwrap();
+
+        assert_eq!(
+            manager.list_dates().unwrap(),
+            vec!["2026-01-16", "2026-01-15"]
+        );
+        assert_eq!(
+            manager.list_sessions("2026-01-16").unwrap(),
+            vec!["10_00-auth", "11_00-ui"]
+        );
+        assert_eq!(
+            manager.read_session("2026-01-16", "11_00-ui").unwrap(),
+            "# ui\n"
+        );
+        assert!(manager.daily_summary_path("2026-01-16").exists());
+    }
+
+    #[test]
+    fn test_project_dir_name() {
+        assert_eq!(project_dir_name("api"), "api");
+        assert_eq!(project_dir_name("jobs"), "project-jobs");
+        assert_eq!(project_dir_name("2026"), "project-2026");
+        assert_eq!(project_dir_name("2026-01-16"), "project-2026-01-16");
+        assert_eq!(project_dir_name("../etc"), "-etc");
+        assert_eq!(project_dir_name("a/b"), "a-b");
+        assert_eq!(project_dir_name(".."), "unknown");
+
+        // A project named like a year still lists with its sessions
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = test_config(&temp_dir);
+        config.output.path_template = "{{project}}/{{date}}".to_string();
+        let manager = ArchiveManager::new(config);
+        let path = manager
+            .write_session("2026-01-16", "2026", "10_00-x", "# x\n")
+            .unwrap();
+        assert_eq!(
+            path,
+            temp_dir.path().join("project-2026/2026-01-16/10_00-x.md")
+        );
+        assert_eq!(
+            manager.list_sessions("2026-01-16").unwrap(),
+            vec!["10_00-x"]
+        );
+    }
+
+    #[test]
+    fn test_nested_layout_and_migration() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = test_config(&temp_dir);
+        config.storage.layout = "nested".to_string();
+        config.output.path_template = "{{project}}/{{date}}".to_string();
+        let manager = ArchiveManager::new(config.clone());
+
+        let path = manager
+            .write_session("2026-01-16", "api", "10_00-auth", "# auth\n")
+            .unwrap();
+        assert_eq!(
+            path,
+            temp_dir.path().join("api/2026/01/2026-01-16/10_00-auth.md")
+        );
+        assert!(temp_dir.path().join("2026/01/2026-01-16/daily.md").exists());
+        // Not yet migrated: a flat date directory is still listed
+        fs::create_dir_all(temp_dir.path().join("2025-12-31")).unwrap();
+        fs::write(temp_dir.path().join("2025-12-31/09_00-old.md"), "# old\n").unwrap();
+
+        assert_eq!(
+            manager.list_dates().unwrap(),
+            vec!["2026-01-16", "2025-12-31"]
+        );
+        assert_eq!(
+            manager.list_sessions("2026-01-16").unwrap(),
+            vec!["10_00-auth"]
+        );
+
+        let moved = manager.migrate_layout("nested").unwrap();
+        assert_eq!(moved.len(), 1);
+        assert!(temp_dir
+            .path()
+            .join("2025/12/2025-12-31/09_00-old.md")
+            .exists());
+        assert!(!temp_dir.path().join("2025-12-31").exists());
+
+        let moved = manager.migrate_layout("flat").unwrap();
+        assert_eq!(moved.len(), 3);
+        assert!(temp_dir
+            .path()
+            .join("api/2026-01-16/10_00-auth.md")
+            .exists());
+        assert!(temp_dir.path().join("2026-01-16/daily.md").exists());
+        assert!(!temp_dir.path().join("2026").exists());
+        assert!(!temp_dir.path().join("api/2026").exists());
+
+        // Nothing is merged into an existing directory
+        fs::create_dir_all(temp_dir.path().join("2026/01/2026-01-16")).unwrap();
+        assert!(manager.migrate_layout("nested").is_err());
+        assert!(temp_dir.path().join("2026-01-16/daily.md").exists());
+    }
+
+    #[test]
+    fn test_preview_not_listed_as_session() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = ArchiveManager::new(test_config(&temp_dir));
+
+        manager
+            .write_session("2026-01-16", "proj", "10_00-a", "# a\n")
+            .unwrap();
+        manager
+            .write_daily_preview("2026-01-16", "# preview\n")
+            .unwrap();
+
+        assert_eq!(
+            manager.list_sessions("2026-01-16").unwrap(),
+            vec!["10_00-a"]
+        );
+        assert!(!manager
+            .read_daily_summary("2026-01-16")
+            .unwrap()
+            .contains("# preview"));
+    }
 }
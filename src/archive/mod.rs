@@ -1,8 +1,15 @@
+mod compress;
 mod daily;
+pub mod frontmatter;
+pub mod index;
 mod manager;
+pub mod search;
 pub mod session;
+pub mod skills;
 mod templates;
+mod weekly;
 
 pub use daily::DailySummary;
-pub use manager::ArchiveManager;
+pub use manager::{ArchiveManager, SessionLink};
 pub use session::SessionArchive;
+pub use weekly::WeeklySummary;
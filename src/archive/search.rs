@@ -0,0 +1,416 @@
+use anyhow::{Context, Result};
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+use tracing::warn;
+
+use super::frontmatter::{self, Frontmatter};
+use super::manager::ArchiveManager;
+
+/// Maximum snippet length in characters
+const SNIPPET_MAX_CHARS: usize = 160;
+
+/// Options for searching the archive
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    /// Text to search for (case-insensitive)
+    pub query: String,
+    /// Only search dates on or after this date (yyyy-mm-dd)
+    pub date_from: Option<String>,
+    /// Only search dates on or before this date (yyyy-mm-dd)
+    pub date_to: Option<String>,
+    /// Only include files whose frontmatter tags contain this tag
+    pub tag: Option<String>,
+    /// Treat the query as a regular expression (case-insensitive)
+    pub regex: bool,
+}
+
+/// Compiled form of a search query
+#[derive(Debug, Clone)]
+pub enum QueryMatcher {
+    /// Case-insensitive substring
+    Text(String),
+    /// Case-insensitive regular expression
+    Pattern(Regex),
+}
+
+impl QueryMatcher {
+    /// Build a matcher, compiling the query when `regex` is set
+    pub fn new(query: &str, regex: bool) -> Result<Self> {
+        if !regex {
+            return Ok(QueryMatcher::Text(query.to_string()));
+        }
+        let pattern = RegexBuilder::new(query)
+            .case_insensitive(true)
+            .build()
+            .context(format!("Invalid regex: {}", query))?;
+        Ok(QueryMatcher::Pattern(pattern))
+    }
+
+    /// Byte ranges of all matches in `text`
+    pub fn ranges(&self, text: &str) -> Vec<(usize, usize)> {
+        match self {
+            QueryMatcher::Text(query) => match_ranges(text, query),
+            QueryMatcher::Pattern(pattern) => pattern
+                .find_iter(text)
+                .filter(|m| !m.is_empty())
+                .map(|m| (m.start(), m.end()))
+                .collect(),
+        }
+    }
+}
+
+/// A single archive file matching a search
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub date: String,
+    /// Session file stem, or "daily" for the daily summary
+    pub session_name: String,
+    pub title: String,
+    pub session_id: Option<String>,
+    /// First matching line, trimmed around the match
+    pub snippet: String,
+    /// 1-based line number of the snippet within the file
+    pub line_number: usize,
+    pub match_count: usize,
+    pub score: usize,
+}
+
+/// Search session archives and daily summaries for a query.
+///
+/// Results are ordered by date (most recent first), then by score.
+pub fn search(manager: &ArchiveManager, options: &SearchOptions) -> Result<Vec<SearchHit>> {
+    let mut hits = Vec::new();
+
+    if options.query.trim().is_empty() {
+        return Ok(hits);
+    }
+    let matcher = QueryMatcher::new(&options.query, options.regex)?;
+
+    // Plain queries only read files the index says can match; regexes scan everything
+    let candidates = if options.regex {
+        None
+    } else {
+        match manager.full_text_index() {
+            Ok(index) => Some(index.candidates(&options.query)),
+            Err(e) => {
+                warn!("Search index unavailable, scanning all files: {}", e);
+                None
+            }
+        }
+    };
+
+    for date in manager.list_dates()? {
+        if !date_in_range(&date, options) {
+            continue;
+        }
+
+        let mut names = manager.list_sessions(&date)?;
+        names.push("daily".to_string());
+
+        for name in names {
+            if candidates
+                .as_ref()
+                .is_some_and(|found| !found.contains(&(date.clone(), name.clone())))
+            {
+                continue;
+            }
+            let content = if name == "daily" {
+                manager.read_daily_summary(&date)
+            } else {
+                manager.read_session(&date, &name)
+            };
+            let content = match content {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+
+            if let Some(tag) = &options.tag {
+                if !Frontmatter::parse(&content).get_list("tags").contains(tag) {
+                    continue;
+                }
+            }
+
+            if let Some(hit) = match_content(&date, &name, &content, &matcher) {
+                hits.push(hit);
+            }
+        }
+    }
+
+    hits.sort_by(|a, b| b.date.cmp(&a.date).then(b.score.cmp(&a.score)));
+    Ok(hits)
+}
+
+/// Check whether a date falls within the optional search window
+fn date_in_range(date: &str, options: &SearchOptions) -> bool {
+    if let Some(from) = &options.date_from {
+        if date < from.as_str() {
+            return false;
+        }
+    }
+    if let Some(to) = &options.date_to {
+        if date > to.as_str() {
+            return false;
+        }
+    }
+    true
+}
+
+/// Match a single file's content against the query
+fn match_content(
+    date: &str,
+    name: &str,
+    content: &str,
+    matcher: &QueryMatcher,
+) -> Option<SearchHit> {
+    let (frontmatter, body, body_offset) = split_frontmatter(content);
+
+    let title = frontmatter_value(&frontmatter, "title").unwrap_or_else(|| name.to_string());
+    let title_matches = matcher.ranges(&title).len();
+
+    let mut match_count = 0;
+    let mut first_match: Option<(usize, String)> = None;
+    let mut in_summary = false;
+    let mut summary_matches = 0;
+
+    for (i, line) in body.lines().enumerate() {
+        if line.starts_with("## ") {
+            in_summary = line.trim() == "## Summary" || line.trim() == "## Overview";
+        }
+
+        let count = matcher.ranges(line).len();
+        if count == 0 {
+            continue;
+        }
+
+        match_count += count;
+        if in_summary {
+            summary_matches += count;
+        }
+        if first_match.is_none() {
+            first_match = Some((body_offset + i + 1, make_snippet(line, matcher)));
+        }
+    }
+
+    if match_count == 0 && title_matches == 0 {
+        return None;
+    }
+
+    let (line_number, snippet) = first_match.unwrap_or((1, title.clone()));
+
+    Some(SearchHit {
+        date: date.to_string(),
+        session_name: name.to_string(),
+        title,
+        session_id: frontmatter_value(&frontmatter, "session_id"),
+        snippet,
+        line_number,
+        match_count: match_count + title_matches,
+        // Title hits weigh most, then summary/overview hits, then body hits
+        score: title_matches * 5 + summary_matches * 2 + match_count,
+    })
+}
+
+/// Find byte ranges of case-insensitive occurrences of `query` in `text`
+pub fn match_ranges(text: &str, query: &str) -> Vec<(usize, usize)> {
+    let needle: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+
+    let mut ranges = Vec::new();
+    let mut search_from = 0;
+
+    for (start, _) in text.char_indices() {
+        if start < search_from {
+            continue;
+        }
+
+        let mut needle_iter = needle.iter();
+        let mut end = start;
+        let mut matched = false;
+
+        for (offset, c) in text[start..].char_indices() {
+            let mut lower = c.to_lowercase();
+            let all_match = lower.all(|lc| needle_iter.next() == Some(&lc));
+            if !all_match {
+                break;
+            }
+            if needle_iter.len() == 0 {
+                end = start + offset + c.len_utf8();
+                matched = true;
+                break;
+            }
+        }
+
+        if matched {
+            ranges.push((start, end));
+            search_from = end;
+        }
+    }
+
+    ranges
+}
+
+/// Build a snippet of a line centered on the first match
+fn make_snippet(line: &str, matcher: &QueryMatcher) -> String {
+    let line = line.trim();
+    let total = line.chars().count();
+    if total <= SNIPPET_MAX_CHARS {
+        return line.to_string();
+    }
+
+    let match_start = matcher
+        .ranges(line)
+        .first()
+        .map(|(start, _)| line[..*start].chars().count())
+        .unwrap_or(0);
+
+    let start = match_start.saturating_sub(SNIPPET_MAX_CHARS / 3);
+    let start = start.min(total - SNIPPET_MAX_CHARS);
+    let end = start + SNIPPET_MAX_CHARS;
+
+    let mut snippet: String = line.chars().skip(start).take(end - start).collect();
+    if start > 0 {
+        snippet = format!("...{}", snippet);
+    }
+    if end < total {
+        snippet.push_str("...");
+    }
+    snippet
+}
+
+/// Split content into (frontmatter, body, body line offset)
+fn split_frontmatter(content: &str) -> (Frontmatter, &str, usize) {
+    match frontmatter::split(content) {
+        Some((_, body)) => {
+            let offset = content[..content.len() - body.len()].lines().count();
+            (Frontmatter::parse(content), body, offset)
+        }
+        None => (Frontmatter::default(), content, 0),
+    }
+}
+
+/// Get a non-empty scalar value from frontmatter
+fn frontmatter_value(frontmatter: &Frontmatter, key: &str) -> Option<String> {
+    frontmatter
+        .get(key)
+        .filter(|value| !value.is_empty() && *value != "N/A")
+        .map(|value| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use tempfile::TempDir;
+
+    const SESSION: &str = r#"---
+title: "14_55-fix-auth-bug"
+date: 2026-01-16
+session_id: abc123
+tags: [claude-code, session-archive]
+---
+
+# 14_55-fix-auth-bug
+
+## Summary
+
+Fixed the OAuth token refresh bug.
+
+## Learnings
+
+- Token expiry must be checked before refresh
+"#;
+
+    fn test_manager(temp_dir: &TempDir) -> ArchiveManager {
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+        ArchiveManager::new(config)
+    }
+
+    #[test]
+    fn test_match_ranges_case_insensitive() {
+        let ranges = match_ranges("Token and TOKEN and token", "token");
+        assert_eq!(ranges, vec![(0, 5), (10, 15), (20, 25)]);
+    }
+
+    #[test]
+    fn test_match_ranges_unicode() {
+        let ranges = match_ranges("修复 Bug 和 bug", "bug");
+        assert_eq!(ranges.len(), 2);
+    }
+
+    #[test]
+    fn test_make_snippet_long_line() {
+        let line = format!("{} needle {}", "a".repeat(300), "b".repeat(300));
+        let snippet = make_snippet(&line, &QueryMatcher::Text("needle".to_string()));
+        assert!(snippet.contains("needle"));
+        assert!(snippet.starts_with("..."));
+        assert!(snippet.ends_with("..."));
+    }
+
+    #[test]
+    fn test_regex_matcher() {
+        let matcher = QueryMatcher::new(r"oauth\s+tok(en)?", true).unwrap();
+        assert_eq!(
+            matcher.ranges("Fixed the OAuth token refresh"),
+            vec![(10, 21)]
+        );
+        assert!(QueryMatcher::new("(unclosed", true).is_err());
+        // Without --regex, metacharacters are literal
+        let literal = QueryMatcher::new("a.b", false).unwrap();
+        assert!(literal.ranges("axb").is_empty());
+        assert_eq!(literal.ranges("a.b"), vec![(0, 3)]);
+    }
+
+    #[test]
+    fn test_search_finds_session() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = test_manager(&temp_dir);
+        manager
+            .write_session("2026-01-16", "proj", "14_55-fix-auth-bug", SESSION)
+            .unwrap();
+
+        let options = SearchOptions {
+            query: "token".to_string(),
+            ..Default::default()
+        };
+        let hits = search(&manager, &options).unwrap();
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].session_name, "14_55-fix-auth-bug");
+        assert_eq!(hits[0].session_id.as_deref(), Some("abc123"));
+        assert_eq!(hits[0].match_count, 2);
+        assert!(hits[0].snippet.contains("OAuth token"));
+    }
+
+    #[test]
+    fn test_search_date_and_tag_filters() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = test_manager(&temp_dir);
+        manager
+            .write_session("2026-01-16", "proj", "14_55-fix-auth-bug", SESSION)
+            .unwrap();
+
+        let out_of_range = SearchOptions {
+            query: "token".to_string(),
+            date_from: Some("2026-01-17".to_string()),
+            ..Default::default()
+        };
+        assert!(search(&manager, &out_of_range).unwrap().is_empty());
+
+        let wrong_tag = SearchOptions {
+            query: "token".to_string(),
+            tag: Some("database".to_string()),
+            ..Default::default()
+        };
+        assert!(search(&manager, &wrong_tag).unwrap().is_empty());
+
+        let right_tag = SearchOptions {
+            query: "token".to_string(),
+            tag: Some("session-archive".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(search(&manager, &right_tag).unwrap().len(), 1);
+    }
+}
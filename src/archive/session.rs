@@ -1,10 +1,12 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
+use super::frontmatter::{self, Frontmatter};
 use super::manager::ArchiveManager;
 use super::templates::Templates;
 use crate::config::Config;
-use crate::transcript::TranscriptData;
+use crate::transcript::{SessionKind, TranscriptData};
 
 /// Represents a summarized session ready for archiving
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +22,71 @@ pub struct SessionArchive {
     pub code_changes: String,
     pub learnings: String,
     pub skill_hints: String,
+    /// Self-assessed summary confidence: low, medium or high
+    #[serde(default)]
+    pub confidence: Option<String>,
+    /// `git diff --stat` output captured at archive time
+    #[serde(default)]
+    pub changes: Option<String>,
+    /// Session this one was resumed from
+    #[serde(default)]
+    pub parent_session_id: Option<String>,
+    /// Tags added on top of the default archive tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Session classification: chat or implementation
+    #[serde(default)]
+    pub session_kind: Option<String>,
+    /// SHA-256 of the condensed transcript, used to spot duplicate summaries
+    #[serde(default)]
+    pub content_hash: Option<String>,
+    /// Time between the first and last transcript entries, e.g. "1h 23m"
+    #[serde(default)]
+    pub duration: Option<String>,
+    /// Tokens (and estimated cost) spent summarizing this session
+    #[serde(default)]
+    pub usage: Option<SessionUsage>,
+}
+
+/// Claude usage of a session summarization
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SessionUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// `None` when `summarization.prices` has no entry for the model
+    pub cost_usd: Option<f64>,
+}
+
+impl SessionUsage {
+    /// Usage recorded in a session's frontmatter, if any
+    pub fn from_frontmatter(fm: &Frontmatter) -> Option<Self> {
+        let number = |key: &str| fm.get(key).and_then(|v| v.parse::<u64>().ok());
+        Some(Self {
+            input_tokens: number("input_tokens")?,
+            output_tokens: number("output_tokens")?,
+            cost_usd: fm.get("cost_usd").and_then(|v| v.parse().ok()),
+        })
+    }
+}
+
+/// Lines added and removed in one file, from `git diff --numstat`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileStat {
+    pub path: String,
+    /// `None` for binary files, which numstat reports as `-`
+    pub added: Option<u64>,
+    pub removed: Option<u64>,
+}
+
+/// Caller-supplied values that replace what the summarizer would generate
+#[derive(Debug, Clone, Default)]
+pub struct SessionOverrides {
+    /// Topic used in place of the AI-generated one
+    pub title: Option<String>,
+    /// Archive date (yyyy-mm-dd) used in place of today
+    pub date: Option<String>,
+    /// Extra tags for the archive
+    pub tags: Vec<String>,
 }
 
 impl SessionArchive {
@@ -37,9 +104,65 @@ impl SessionArchive {
             code_changes: String::new(),
             learnings: String::new(),
             skill_hints: String::new(),
+            confidence: None,
+            changes: None,
+            parent_session_id: None,
+            tags: Vec::new(),
+            session_kind: None,
+            content_hash: None,
+            duration: None,
+            usage: None,
         }
     }
 
+    /// Rebuild an archive from a session file written by `to_markdown`.
+    ///
+    /// `name` is used when the file has no `title:`; `N/A` placeholders become `None`.
+    pub fn from_markdown(date: &str, name: &str, content: &str) -> Self {
+        let fm = Frontmatter::parse(content);
+        let body = frontmatter::split(content)
+            .map(|(_, body)| body)
+            .unwrap_or(content);
+        let field = |key: &str| {
+            fm.get(key)
+                .filter(|v| !v.is_empty() && *v != "N/A")
+                .map(str::to_string)
+        };
+
+        let mut archive = Self::new(
+            field("title").unwrap_or_else(|| name.to_string()),
+            field("date").unwrap_or_else(|| date.to_string()),
+            field("session_id").unwrap_or_default(),
+            field("cwd").unwrap_or_default(),
+        );
+        archive.git_branch = field("git_branch");
+        archive.transcript_path = field("transcript_path");
+        archive.summary = markdown_section(body, "Summary");
+        archive.decisions = markdown_section(body, "Key Decisions & Trade-offs");
+        archive.code_changes = markdown_section(body, "Code Changes");
+        archive.learnings = markdown_section(body, "Learnings");
+        archive.skill_hints = markdown_section(body, "Potential Skills/Commands");
+        archive.confidence = field("confidence");
+        archive.changes = Some(markdown_section(body, "Changes"))
+            .and_then(|section| {
+                let start = section.find("```\n")? + 4;
+                let end = section.rfind("\n```")?;
+                (start <= end).then(|| section[start..end].to_string())
+            })
+            .filter(|diff| !diff.is_empty());
+        archive.parent_session_id = field("parent_session_id");
+        archive.tags = fm
+            .get_list("tags")
+            .into_iter()
+            .filter(|tag| tag != "claude-code" && tag != "session-archive")
+            .collect();
+        archive.session_kind = field("session_kind");
+        archive.content_hash = field("content_hash");
+        archive.duration = field("duration");
+        archive.usage = SessionUsage::from_frontmatter(&fm);
+        archive
+    }
+
     /// Set the transcript path
     pub fn with_transcript_path(mut self, path: impl Into<String>) -> Self {
         self.transcript_path = Some(path.into());
@@ -60,6 +183,54 @@ impl SessionArchive {
             self.code_changes = "_No files modified._".to_string();
         }
 
+        self.parent_session_id = data.parent_session_id.clone();
+
+        self
+    }
+
+    /// Render the session's modified files as a table of line counts.
+    ///
+    /// Files are matched against `git diff --numstat` paths by suffix, since the
+    /// transcript may record absolute paths. The plain file list is kept when no
+    /// modified file has uncommitted changes (e.g. everything was already committed).
+    pub fn with_line_counts(mut self, data: &TranscriptData, stats: &[FileStat]) -> Self {
+        let rows: Vec<(&String, Option<&FileStat>)> = data
+            .files_modified
+            .iter()
+            .map(|file| (file, stats.iter().find(|stat| same_file(file, &stat.path))))
+            .collect();
+        if rows.iter().all(|(_, stat)| stat.is_none()) {
+            return self;
+        }
+
+        let count = |n: Option<u64>, sign: char| match n {
+            Some(n) => format!("{}{}", sign, n),
+            None => "bin".to_string(),
+        };
+        let mut table = vec![
+            "| File | Added | Removed |".to_string(),
+            "| ---- | ----- | ------- |".to_string(),
+        ];
+        for (file, stat) in rows {
+            let (added, removed) = match stat {
+                Some(stat) => (count(stat.added, '+'), count(stat.removed, '-')),
+                None => ("-".to_string(), "-".to_string()),
+            };
+            table.push(format!("| `{}` | {} | {} |", file, added, removed));
+        }
+        self.code_changes = table.join("\n");
+        self
+    }
+
+    /// Set the session classification
+    pub fn with_session_kind(mut self, kind: SessionKind) -> Self {
+        self.session_kind = Some(kind.as_str().to_string());
+        self
+    }
+
+    /// Set the hash of the condensed transcript
+    pub fn with_content_hash(mut self, hash: String) -> Self {
+        self.content_hash = Some(hash);
         self
     }
 
@@ -78,6 +249,28 @@ impl SessionArchive {
         self
     }
 
+    /// Set the summary confidence
+    pub fn with_confidence(mut self, confidence: Option<String>) -> Self {
+        self.confidence = confidence;
+        self
+    }
+
+    /// Set the git diff stat captured at archive time
+    pub fn with_changes(mut self, changes: Option<String>) -> Self {
+        self.changes = changes;
+        self
+    }
+
+    /// Add extra frontmatter tags, skipping duplicates
+    pub fn with_tags(mut self, tags: &[String]) -> Self {
+        for tag in tags {
+            if !self.tags.contains(tag) {
+                self.tags.push(tag.clone());
+            }
+        }
+        self
+    }
+
     /// Generate Markdown content for this archive
     pub fn to_markdown(&self) -> String {
         Templates::session_archive(
@@ -92,17 +285,42 @@ impl SessionArchive {
             &self.code_changes,
             &self.learnings,
             &self.skill_hints,
+            self.confidence.as_deref(),
+            self.changes.as_deref(),
+            self.parent_session_id.as_deref(),
+            &self.tags,
+            self.session_kind.as_deref(),
+            self.content_hash.as_deref(),
+            self.duration.as_deref(),
+            self.usage.as_ref(),
         )
     }
 
+    /// Project name derived from the working directory's basename
+    pub fn project(&self) -> String {
+        std::path::Path::new(&self.cwd)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+
     /// Save this archive to disk
     pub fn save(&self, config: &Config) -> Result<std::path::PathBuf> {
         let manager = ArchiveManager::new(config.clone());
         let content = self.to_markdown();
-        manager.write_session(&self.date, &self.title, &content)
+        manager.write_session(&self.date, &self.project(), &self.title, &content)
     }
 }
 
+/// Hex SHA-256 of a condensed transcript, stored as `content_hash` in session frontmatter
+pub fn content_hash(text: &str) -> String {
+    Sha256::digest(text.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
 /// Get git branch from working directory
 pub fn get_git_branch(cwd: &str) -> Option<String> {
     std::process::Command::new("git")
@@ -119,10 +337,189 @@ pub fn get_git_branch(cwd: &str) -> Option<String> {
         })
 }
 
+/// Get `git diff --stat` for uncommitted changes, truncated to `max_chars`
+pub fn get_git_diffstat(cwd: &str, max_chars: usize) -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(["diff", "--stat", "HEAD"])
+        .current_dir(cwd)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let stat = String::from_utf8_lossy(&output.stdout)
+        .trim_end()
+        .to_string();
+    if stat.is_empty() {
+        return None;
+    }
+
+    Some(truncate_diff(&stat, max_chars))
+}
+
+/// Per-file line counts of uncommitted changes; `None` outside a git repository
+pub fn get_git_numstat(cwd: &str) -> Option<Vec<FileStat>> {
+    let output = std::process::Command::new("git")
+        .args(["diff", "--numstat", "HEAD"])
+        .current_dir(cwd)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(parse_numstat(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `added<TAB>removed<TAB>path` lines
+fn parse_numstat(output: &str) -> Vec<FileStat> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let added = fields.next()?;
+            let removed = fields.next()?;
+            let path = fields.next()?;
+            Some(FileStat {
+                path: path.to_string(),
+                added: added.parse().ok(),
+                removed: removed.parse().ok(),
+            })
+        })
+        .collect()
+}
+
+/// Whether a transcript file path refers to a repo-relative numstat path
+fn same_file(file: &str, repo_path: &str) -> bool {
+    file == repo_path
+        || file
+            .strip_suffix(repo_path)
+            .is_some_and(|prefix| prefix.ends_with('/') || prefix.ends_with('\\'))
+}
+
+/// Text under a `## heading`, up to the next heading or the archive footer
+pub fn markdown_section(body: &str, heading: &str) -> String {
+    let marker = format!("## {}\n", heading);
+    let start = if body.starts_with(&marker) {
+        marker.len()
+    } else {
+        match body.find(&format!("\n{}", marker)) {
+            Some(pos) => pos + 1 + marker.len(),
+            None => return String::new(),
+        }
+    };
+    let rest = &body[start..];
+    let end = ["\n## ", "\n---\n*Archived"]
+        .iter()
+        .filter_map(|stop| rest.find(stop))
+        .min()
+        .unwrap_or(rest.len());
+    rest[..end].trim().to_string()
+}
+
+/// Truncate diff output on a line boundary, noting how much was dropped
+fn truncate_diff(diff: &str, max_chars: usize) -> String {
+    if diff.chars().count() <= max_chars {
+        return diff.to_string();
+    }
+
+    let mut kept = String::new();
+    let mut kept_lines = 0;
+    for line in diff.lines() {
+        if kept.chars().count() + line.chars().count() + 1 > max_chars {
+            break;
+        }
+        kept.push_str(line);
+        kept.push('\n');
+        kept_lines += 1;
+    }
+
+    let dropped = diff.lines().count() - kept_lines;
+    format!("{}... ({} more lines truncated)", kept, dropped)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_content_hash() {
+        let text = "User: Help me fix a bug\nAssistant: I'll help you.";
+        let hash = content_hash(text);
+
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, content_hash(text));
+        assert_ne!(hash, content_hash("User: Something else"));
+        assert_eq!(
+            content_hash("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_parse_numstat() {
+        let stats = parse_numstat("12\t3\tsrc/main.rs\n-\t-\tlogo.png\n");
+        assert_eq!(
+            stats,
+            vec![
+                FileStat {
+                    path: "src/main.rs".to_string(),
+                    added: Some(12),
+                    removed: Some(3),
+                },
+                FileStat {
+                    path: "logo.png".to_string(),
+                    added: None,
+                    removed: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_with_line_counts() {
+        let data = TranscriptData {
+            entries: vec![],
+            user_messages: vec![],
+            assistant_messages: vec![],
+            tool_calls: vec![],
+            files_modified: vec![
+                "/home/user/project/src/main.rs".to_string(),
+                "notes.md".to_string(),
+            ],
+            summary: None,
+            parent_session_id: None,
+            thinking_blocks: vec![],
+        };
+        let archive = || {
+            SessionArchive::new(
+                "t".to_string(),
+                "2026-01-16".to_string(),
+                "abc".to_string(),
+                "/home/user/project".to_string(),
+            )
+            .with_transcript_data(&data)
+        };
+
+        let stats = parse_numstat("12\t3\tsrc/main.rs\n1\t0\tmain.rs\n");
+        let with_counts = archive().with_line_counts(&data, &stats);
+        assert_eq!(
+            with_counts.code_changes,
+            "| File | Added | Removed |\n| ---- | ----- | ------- |\n\
+             | `/home/user/project/src/main.rs` | +12 | -3 |\n\
+             | `notes.md` | - | - |"
+        );
+
+        let committed = archive().with_line_counts(&data, &parse_numstat("4\t4\tother.rs\n"));
+        assert_eq!(
+            committed.code_changes,
+            "- `/home/user/project/src/main.rs`\n- `notes.md`"
+        );
+    }
+
     #[test]
     fn test_session_archive_new() {
         let archive = SessionArchive::new(
@@ -147,5 +544,75 @@ mod tests {
         let md = archive.to_markdown();
         assert!(md.contains("title: \"test-session\""));
         assert!(md.contains("# test-session"));
+        assert!(!md.contains("## Changes"));
+    }
+
+    #[test]
+    fn test_session_archive_changes_section() {
+        let archive = SessionArchive::new(
+            "test-session".to_string(),
+            "2026-01-16".to_string(),
+            "abc123".to_string(),
+            "/home/user/project".to_string(),
+        )
+        .with_changes(Some(" src/main.rs | 2 +-".to_string()));
+
+        let md = archive.to_markdown();
+        assert!(md.contains("## Changes"));
+        assert!(md.contains(" src/main.rs | 2 +-"));
+    }
+
+    #[test]
+    fn test_from_markdown_round_trip() {
+        let mut archive = SessionArchive::new(
+            "10_00-fix-auth".to_string(),
+            "2026-01-16".to_string(),
+            "abc123".to_string(),
+            "/home/user/project".to_string(),
+        )
+        .with_summary(
+            "Fixed the login bug.".to_string(),
+            "- Kept sessions in cookies".to_string(),
+            "- Check expiry first".to_string(),
+            "None identified".to_string(),
+        )
+        .with_changes(Some(" src/main.rs | 2 +-".to_string()))
+        .with_tags(&["auth".to_string()]);
+        archive.code_changes = "- `src/main.rs`".to_string();
+        archive.duration = Some("42m".to_string());
+        archive.usage = Some(SessionUsage {
+            input_tokens: 1200,
+            output_tokens: 340,
+            cost_usd: Some(0.0087),
+        });
+
+        let parsed = SessionArchive::from_markdown("2026-01-16", "x", &archive.to_markdown());
+        assert_eq!(parsed.title, "10_00-fix-auth");
+        assert_eq!(parsed.session_id, "abc123");
+        assert_eq!(parsed.cwd, "/home/user/project");
+        assert_eq!(parsed.git_branch, None);
+        assert_eq!(parsed.summary, "Fixed the login bug.");
+        assert_eq!(parsed.decisions, "- Kept sessions in cookies");
+        assert_eq!(parsed.code_changes, "- `src/main.rs`");
+        assert_eq!(parsed.learnings, "- Check expiry first");
+        assert_eq!(parsed.skill_hints, "None identified");
+        assert_eq!(parsed.changes.as_deref(), Some(" src/main.rs | 2 +-"));
+        assert_eq!(parsed.tags, vec!["auth"]);
+        assert_eq!(parsed.duration.as_deref(), Some("42m"));
+        assert_eq!(parsed.usage, archive.usage);
+    }
+
+    #[test]
+    fn test_truncate_diff() {
+        let diff = (0..10)
+            .map(|i| format!(" file{}.rs | 1 +", i))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        assert_eq!(truncate_diff(&diff, 10_000), diff);
+
+        let truncated = truncate_diff(&diff, 40);
+        assert!(truncated.starts_with(" file0.rs | 1 +\n file1.rs | 1 +\n"));
+        assert!(truncated.ends_with("(8 more lines truncated)"));
     }
 }
@@ -0,0 +1,246 @@
+//! Skills extracted from sessions, waiting for review in `<storage>/pending-skills`
+//!
+//! Each pending skill lives at `pending-skills/<date>/<name>/SKILL.md`, the same layout
+//! Claude expects under `~/.claude/skills/`. Skills saved before that layout, as
+//! `pending-skills/<date>/<name>.md`, are still listed, installed and deleted.
+
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+
+const SKILL_FILE: &str = "SKILL.md";
+
+/// A skill waiting for review
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingSkill {
+    pub date: String,
+    pub name: String,
+    /// The skill's `SKILL.md` (or legacy `<name>.md`)
+    pub path: PathBuf,
+}
+
+impl PendingSkill {
+    /// Identifier used by the dashboard API, e.g. `2026-01-18:fix-flaky-tests`
+    pub fn id(&self) -> String {
+        format!("{}:{}", self.date, self.name)
+    }
+}
+
+/// Parse a skill reference: `yyyy-mm-dd/name` (CLI) or `yyyy-mm-dd:name` (API id)
+pub fn parse_skill_ref(skill_ref: &str) -> Result<(String, String)> {
+    let Some((date, name)) = skill_ref.split_once(['/', ':']) else {
+        bail!("Invalid skill reference. Use format: YYYY-MM-DD/skill-name");
+    };
+    if chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+        bail!("Invalid date in skill reference: {}", date);
+    }
+    if !is_safe_skill_name(name) {
+        bail!("Invalid skill name: {}", name);
+    }
+    Ok((date.to_string(), name.to_string()))
+}
+
+/// Plain directory names only, so a reference can't escape the skills directory
+fn is_safe_skill_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != "."
+        && !name.contains("..")
+        && !name.contains(['/', '\\', ':', '\0'])
+}
+
+/// Pending skills of one storage directory, and where installed skills go
+pub struct SkillStore {
+    pending_dir: PathBuf,
+    installed_dir: PathBuf,
+}
+
+impl SkillStore {
+    pub fn new(config: &Config) -> Self {
+        let installed_dir = dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".claude")
+            .join("skills");
+        Self::with_dirs(config.storage_path().join("pending-skills"), installed_dir)
+    }
+
+    pub fn with_dirs(pending_dir: PathBuf, installed_dir: PathBuf) -> Self {
+        Self {
+            pending_dir,
+            installed_dir,
+        }
+    }
+
+    /// All pending skills, newest date first
+    pub fn list(&self) -> Result<Vec<PendingSkill>> {
+        let mut skills = Vec::new();
+        if !self.pending_dir.exists() {
+            return Ok(skills);
+        }
+
+        for date_entry in fs::read_dir(&self.pending_dir)?.flatten() {
+            let date_dir = date_entry.path();
+            if !date_dir.is_dir() {
+                continue;
+            }
+            let date = date_entry.file_name().to_string_lossy().to_string();
+            for entry in fs::read_dir(&date_dir)?.flatten() {
+                let path = entry.path();
+                let name = if path.is_dir() {
+                    entry.file_name().to_string_lossy().to_string()
+                } else if path.extension().is_some_and(|e| e == "md") {
+                    path.file_stem()
+                        .map(|s| s.to_string_lossy().to_string())
+                        .unwrap_or_default()
+                } else {
+                    continue;
+                };
+                if let Some(path) = self.find(&date, &name) {
+                    skills.push(PendingSkill {
+                        date: date.clone(),
+                        name,
+                        path,
+                    });
+                }
+            }
+        }
+
+        skills.sort_by(|a, b| b.date.cmp(&a.date).then_with(|| a.name.cmp(&b.name)));
+        skills.dedup_by(|a, b| a.date == b.date && a.name == b.name);
+        Ok(skills)
+    }
+
+    /// Store an extracted skill for review
+    pub fn save(&self, date: &str, name: &str, content: &str) -> Result<PathBuf> {
+        if !is_safe_skill_name(name) {
+            bail!("Invalid skill name: {}", name);
+        }
+        let dir = self.pending_dir.join(date).join(name);
+        fs::create_dir_all(&dir).context(format!("Failed to create {}", dir.display()))?;
+        let path = dir.join(SKILL_FILE);
+        fs::write(&path, content).context(format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Path of a pending skill's file, in either layout
+    pub fn find(&self, date: &str, name: &str) -> Option<PathBuf> {
+        let date_dir = self.pending_dir.join(date);
+        [
+            date_dir.join(name).join(SKILL_FILE),
+            date_dir.join(format!("{}.md", name)),
+        ]
+        .into_iter()
+        .find(|path| path.is_file())
+    }
+
+    /// Copy a pending skill to `~/.claude/skills/<name>/` and remove it from review
+    pub fn install(&self, date: &str, name: &str) -> Result<PathBuf> {
+        let path = self.require(date, name)?;
+        let target_dir = self.installed_dir.join(name);
+        fs::create_dir_all(&target_dir)
+            .context(format!("Failed to create {}", target_dir.display()))?;
+
+        if path.file_name().is_some_and(|n| n == SKILL_FILE) {
+            // Bring along any supporting files next to SKILL.md
+            let skill_dir = path.parent().unwrap_or(&self.pending_dir);
+            for entry in fs::read_dir(skill_dir)?.flatten() {
+                if entry.path().is_file() {
+                    fs::copy(entry.path(), target_dir.join(entry.file_name()))?;
+                }
+            }
+        } else {
+            fs::copy(&path, target_dir.join(SKILL_FILE))?;
+        }
+
+        self.remove(date, name, &path)?;
+        Ok(target_dir.join(SKILL_FILE))
+    }
+
+    /// Discard a pending skill
+    pub fn delete(&self, date: &str, name: &str) -> Result<()> {
+        let path = self.require(date, name)?;
+        self.remove(date, name, &path)
+    }
+
+    fn require(&self, date: &str, name: &str) -> Result<PathBuf> {
+        self.find(date, name)
+            .context(format!("Skill not found: {}/{}", date, name))
+    }
+
+    /// Remove a skill's file (or directory), then its date directory if now empty
+    fn remove(&self, date: &str, name: &str, path: &Path) -> Result<()> {
+        let skill_dir = self.pending_dir.join(date).join(name);
+        if path.starts_with(&skill_dir) {
+            fs::remove_dir_all(&skill_dir)?;
+        } else {
+            fs::remove_file(path)?;
+        }
+
+        let date_dir = self.pending_dir.join(date);
+        if fs::read_dir(&date_dir)?.next().is_none() {
+            fs::remove_dir(&date_dir)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_skill_ref() {
+        assert_eq!(
+            parse_skill_ref("2026-01-18/fix-tests").unwrap(),
+            ("2026-01-18".to_string(), "fix-tests".to_string())
+        );
+        assert_eq!(
+            parse_skill_ref("2026-01-18:fix-tests").unwrap(),
+            ("2026-01-18".to_string(), "fix-tests".to_string())
+        );
+        assert!(parse_skill_ref("fix-tests").is_err());
+        assert!(parse_skill_ref("yesterday/fix-tests").is_err());
+        assert!(parse_skill_ref("2026-01-18/../etc").is_err());
+    }
+
+    #[test]
+    fn test_skill_store_lifecycle() {
+        let temp_dir = TempDir::new().unwrap();
+        let pending = temp_dir.path().join("pending-skills");
+        let installed = temp_dir.path().join("skills");
+        let store = SkillStore::with_dirs(pending.clone(), installed.clone());
+
+        assert!(store.list().unwrap().is_empty());
+
+        store
+            .save("2026-01-18", "fix-tests", "# Fix tests")
+            .unwrap();
+        store.save("2026-01-19", "deploy", "# Deploy").unwrap();
+        // Skill saved before the SKILL.md layout
+        fs::write(pending.join("2026-01-18").join("legacy.md"), "# Legacy").unwrap();
+
+        let ids: Vec<String> = store.list().unwrap().iter().map(|s| s.id()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "2026-01-19:deploy",
+                "2026-01-18:fix-tests",
+                "2026-01-18:legacy"
+            ]
+        );
+
+        let path = store.install("2026-01-18", "fix-tests").unwrap();
+        assert_eq!(path, installed.join("fix-tests").join("SKILL.md"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "# Fix tests");
+
+        let path = store.install("2026-01-18", "legacy").unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), "# Legacy");
+        assert!(!pending.join("2026-01-18").exists());
+
+        store.delete("2026-01-19", "deploy").unwrap();
+        assert!(store.list().unwrap().is_empty());
+        assert!(store.delete("2026-01-19", "deploy").is_err());
+    }
+}
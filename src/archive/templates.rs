@@ -1,3 +1,5 @@
+use super::frontmatter::quote;
+use super::session::SessionUsage;
 use chrono::Local;
 
 /// Templates for generating Obsidian-compatible Markdown files
@@ -18,21 +20,74 @@ impl Templates {
         code_changes: &str,
         learnings: &str,
         skill_hints: &str,
+        confidence: Option<&str>,
+        changes: Option<&str>,
+        parent_session_id: Option<&str>,
+        extra_tags: &[String],
+        session_kind: Option<&str>,
+        content_hash: Option<&str>,
+        duration: Option<&str>,
+        usage: Option<&SessionUsage>,
     ) -> String {
         let created = Local::now().to_rfc3339();
         let git_branch_str = git_branch.unwrap_or("N/A");
         let transcript_path_str = transcript_path.unwrap_or("N/A");
+        let confidence_line = confidence
+            .map(|c| format!("confidence: {}\n", c))
+            .unwrap_or_default();
+        let tags = ["claude-code".to_string(), "session-archive".to_string()]
+            .iter()
+            .chain(extra_tags)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ");
+        let kind_line = session_kind
+            .map(|kind| format!("session_kind: {}\n", kind))
+            .unwrap_or_default();
+        let hash_line = content_hash
+            .map(|hash| format!("content_hash: {}\n", hash))
+            .unwrap_or_default();
+        let duration_line = duration
+            .map(|d| format!("duration: {}\n", quote(d)))
+            .unwrap_or_default();
+        let usage_lines = usage
+            .map(|usage| {
+                let cost = usage
+                    .cost_usd
+                    .map(|cost| format!("cost_usd: {:.4}\n", cost))
+                    .unwrap_or_default();
+                format!(
+                    "input_tokens: {}\noutput_tokens: {}\n{}",
+                    usage.input_tokens, usage.output_tokens, cost
+                )
+            })
+            .unwrap_or_default();
+        let parent_line = parent_session_id
+            .map(|id| format!("parent_session_id: {}\n", id))
+            .unwrap_or_default();
+        let title_yaml = quote(title);
+        let cwd_yaml = quote(cwd);
+        let git_branch_yaml = quote(git_branch_str);
+        let transcript_path_yaml = quote(transcript_path_str);
+        let changes_section = changes
+            .map(|diff| {
+                format!(
+                    "## Changes\n\n<details>\n<summary>git diff --stat</summary>\n\n```\n{}\n```\n\n</details>\n\n",
+                    diff
+                )
+            })
+            .unwrap_or_default();
 
         format!(
             r#"---
-title: "{title}"
+title: {title_yaml}
 date: {date}
 session_id: {session_id}
-cwd: "{cwd}"
-git_branch: "{git_branch_str}"
-transcript_path: "{transcript_path_str}"
-tags: [claude-code, session-archive]
-created: {created}
+{parent_line}cwd: {cwd_yaml}
+git_branch: {git_branch_yaml}
+transcript_path: {transcript_path_yaml}
+{duration_line}tags: [{tags}]
+{kind_line}{confidence_line}{hash_line}{usage_lines}created: {created}
 ---
 
 # {title}
@@ -54,7 +109,7 @@ created: {created}
 
 {code_changes}
 
-## Learnings
+{changes_section}## Learnings
 
 {learnings}
 
@@ -163,6 +218,59 @@ _No sessions archived yet._
 
 ## Tomorrow's Focus
 
+---
+*Generated by Daily Context Archive System*
+"#
+        )
+    }
+
+    /// Generate weekly digest frontmatter and content
+    #[allow(clippy::too_many_arguments)]
+    pub fn weekly_summary(
+        week: &str,
+        start_date: &str,
+        end_date: &str,
+        session_count: usize,
+        theme_index: &str,
+        overview: &str,
+        themes: &str,
+        insights: &str,
+        next_week: &str,
+    ) -> String {
+        let updated = Local::now().to_rfc3339();
+
+        format!(
+            r#"---
+week: {week}
+start_date: {start_date}
+end_date: {end_date}
+updated: {updated}
+tags: [weekly-summary, claude-code]
+session_count: {session_count}
+---
+
+# Weekly Summary - {week}
+
+## Overview
+
+{overview}
+
+## Themes at a Glance
+
+{theme_index}
+
+## Themes
+
+{themes}
+
+## Key Insights
+
+{insights}
+
+## Next Week
+
+{next_week}
+
 ---
 *Generated by Daily Context Archive System*
 "#
@@ -242,11 +350,29 @@ mod tests {
             "Test changes",
             "Test learnings",
             "Test hints",
+            Some("high"),
+            None,
+            Some("parent-456"),
+            &["client-x".to_string()],
+            Some("implementation"),
+            Some("ab12"),
+            Some("1h 23m"),
+            Some(&SessionUsage {
+                input_tokens: 1200,
+                output_tokens: 340,
+                cost_usd: Some(0.0087),
+            }),
         );
 
         assert!(content.contains("title: \"Test Session\""));
         assert!(content.contains("session_id: abc123"));
+        assert!(content.contains("session_id: abc123\nparent_session_id: parent-456\n"));
         assert!(content.contains("transcript_path:"));
+        assert!(content.contains("duration: \"1h 23m\"\ntags:"));
+        assert!(content.contains(
+            "session_kind: implementation\nconfidence: high\ncontent_hash: ab12\ninput_tokens: 1200\noutput_tokens: 340\ncost_usd: 0.0087\ncreated:"
+        ));
+        assert!(content.contains("tags: [claude-code, session-archive, client-x]"));
     }
 
     #[test]
@@ -0,0 +1,45 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::manager::ArchiveManager;
+use super::templates::Templates;
+use crate::config::Config;
+
+/// Represents a weekly digest organized by topic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeeklySummary {
+    /// ISO week label (e.g. "2026-W03")
+    pub week: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub session_count: usize,
+    /// Markdown list of themes with session counts
+    pub theme_index: String,
+    pub overview: String,
+    pub themes: String,
+    pub insights: String,
+    pub next_week: String,
+}
+
+impl WeeklySummary {
+    /// Generate Markdown content for this summary
+    pub fn to_markdown(&self) -> String {
+        Templates::weekly_summary(
+            &self.week,
+            &self.start_date,
+            &self.end_date,
+            self.session_count,
+            &self.theme_index,
+            &self.overview,
+            &self.themes,
+            &self.insights,
+            &self.next_week,
+        )
+    }
+
+    /// Save this summary to disk
+    pub fn save(&self, config: &Config) -> Result<std::path::PathBuf> {
+        let manager = ArchiveManager::new(config.clone());
+        manager.write_weekly_summary(&self.week, &self.to_markdown())
+    }
+}
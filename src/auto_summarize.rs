@@ -1,12 +1,19 @@
 use anyhow::{Context, Result};
-use chrono::{Local, NaiveTime};
-use std::collections::HashSet;
+use chrono::{DateTime, Local, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
 use std::fs;
-use std::path::PathBuf;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
 
+use crate::archive::frontmatter::Frontmatter;
 use crate::archive::ArchiveManager;
 use crate::config::Config;
-use crate::transcript::TranscriptParser;
+use crate::fs_util::write_atomic;
+use crate::jobs::JobManager;
+use crate::transcript::source::{configured_sources, parse_transcript};
+use tracing::{info, warn};
 
 /// Represents an unsummarized transcript that needs processing
 #[derive(Debug, Clone)]
@@ -16,47 +23,119 @@ pub struct UnsummarizedTranscript {
     pub cwd: Option<PathBuf>,
 }
 
-/// Find all transcript files in Claude Code's projects directory
-pub fn find_all_transcripts() -> Result<Vec<PathBuf>> {
-    let home = dirs::home_dir().context("Failed to get home directory")?;
-    let projects_dir = home.join(".claude").join("projects");
+/// Find all transcript files from the configured transcript sources
+pub fn find_all_transcripts(config: &Config) -> Result<Vec<PathBuf>> {
+    let mut transcripts = Vec::new();
 
-    if !projects_dir.exists() {
-        return Ok(Vec::new());
+    for source in configured_sources(config) {
+        match source.discover() {
+            Ok(found) => transcripts.extend(found),
+            Err(e) => warn!("Failed to scan {} transcripts: {}", source.name(), e),
+        }
     }
 
-    let mut transcripts = Vec::new();
+    Ok(transcripts)
+}
 
-    // Recursively search for .jsonl files
-    for entry in fs::read_dir(&projects_dir)? {
-        let entry = entry?;
-        let path = entry.path();
+/// Reconstruct the cwd Claude encoded into a project directory name
+/// (`/home/me/my-app` → `-home-me-my-app`).
+///
+/// Every character other than a letter or digit was replaced by '-', so the name is
+/// matched against directories that exist on disk. `None` when no path fits, or when
+/// several do (e.g. both `/home/me/my-app` and `/home/me/my/app` exist).
+pub fn decode_project_dir(name: &str) -> Option<PathBuf> {
+    let encoded = name.strip_prefix('-')?;
+    decode_encoded_path(Path::new("/"), encoded)
+}
 
-        if path.is_dir() {
-            // Search in subdirectories
-            for sub_entry in fs::read_dir(&path)? {
-                let sub_entry = sub_entry?;
-                let sub_path = sub_entry.path();
-
-                if sub_path.extension().is_some_and(|ext| ext == "jsonl") {
-                    // Skip agent transcripts (temporary agent sessions)
-                    if !sub_path
-                        .file_name()
-                        .and_then(|n| n.to_str())
-                        .is_some_and(|name| name.starts_with("agent-"))
-                    {
-                        transcripts.push(sub_path);
-                    }
-                }
-            }
+fn decode_encoded_path(root: &Path, encoded: &str) -> Option<PathBuf> {
+    let mut found = Vec::new();
+    find_encoded_path(root, encoded, &mut found);
+    if found.len() == 1 {
+        found.pop()
+    } else {
+        None
+    }
+}
+
+/// Collect directories below `dir` whose encoded relative path is `rest`, stopping
+/// once the result is known to be ambiguous
+fn find_encoded_path(dir: &Path, rest: &str, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if found.len() > 1 {
+            return;
+        }
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let component = encode_path_component(&entry.file_name().to_string_lossy());
+        if rest == component {
+            found.push(path);
+        } else if let Some(tail) = rest
+            .strip_prefix(component.as_str())
+            .and_then(|tail| tail.strip_prefix('-'))
+        {
+            find_encoded_path(&path, tail, found);
         }
     }
+}
 
-    Ok(transcripts)
+/// Encode a path component the way Claude does for project directory names
+fn encode_path_component(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// Whether a transcript belongs to a project in `summarization.excluded_cwd_patterns`.
+///
+/// Patterns match the session's cwd when it is known. Otherwise they match the project
+/// directory Claude names after the cwd (`/home/me/nda` → `-home-me-nda`), with the
+/// pattern encoded the same way.
+pub fn is_excluded_project(
+    patterns: &[String],
+    transcript_path: &Path,
+    cwd: Option<&Path>,
+) -> bool {
+    let project_dir = transcript_path
+        .parent()
+        .and_then(|dir| dir.file_name())
+        .map(|name| name.to_string_lossy().to_string());
+
+    patterns.iter().any(|pattern| {
+        let pattern = shellexpand::tilde(pattern);
+        match cwd {
+            Some(cwd) => glob::Pattern::new(&pattern).is_ok_and(|p| p.matches_path(cwd)),
+            // Encoding is lossy (`nda-client` and `nda/client` look alike), so the
+            // directory is only a fallback
+            None => project_dir.as_deref().is_some_and(|dir| {
+                glob::Pattern::new(&encode_project_pattern(&pattern)).is_ok_and(|p| p.matches(dir))
+            }),
+        }
+    })
+}
+
+/// Encode a cwd pattern like Claude encodes project directory names: every character
+/// other than a letter, digit or '-' becomes '-', keeping glob syntax intact
+fn encode_project_pattern(pattern: &str) -> String {
+    pattern
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || "-*?[]!".contains(c) {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
 }
 
 /// Get all transcript paths that have been archived by reading frontmatter
-fn get_archived_transcript_paths(config: &Config) -> Result<HashSet<String>> {
+pub fn get_archived_transcript_paths(config: &Config) -> Result<HashSet<String>> {
     let archive_manager = ArchiveManager::new(config.clone());
     let mut archived_paths = HashSet::new();
 
@@ -68,26 +147,9 @@ fn get_archived_transcript_paths(config: &Config) -> Result<HashSet<String>> {
         for session in sessions {
             // Read the session file to extract transcript path from frontmatter
             if let Ok(content) = archive_manager.read_session(&date, &session) {
-                // Extract transcript_path from frontmatter
-                let mut in_frontmatter = false;
-                for line in content.lines() {
-                    if line.trim() == "---" {
-                        if !in_frontmatter {
-                            in_frontmatter = true;
-                            continue;
-                        } else {
-                            // End of frontmatter
-                            break;
-                        }
-                    }
-
-                    if in_frontmatter && line.starts_with("transcript_path:") {
-                        if let Some(path) = line.split(':').nth(1) {
-                            let path_str = path.trim().trim_matches('"');
-                            if path_str != "N/A" {
-                                archived_paths.insert(path_str.to_string());
-                            }
-                        }
+                if let Some(path) = Frontmatter::parse(&content).get("transcript_path") {
+                    if path != "N/A" {
+                        archived_paths.insert(path.to_string());
                     }
                 }
             }
@@ -97,9 +159,49 @@ fn get_archived_transcript_paths(config: &Config) -> Result<HashSet<String>> {
     Ok(archived_paths)
 }
 
-/// Check if a transcript has been recently modified (within the configured inactive threshold)
+/// Path of the marker the SessionEnd hook leaves next to a finished transcript
+pub fn done_marker_path(transcript: &Path) -> PathBuf {
+    let mut name = transcript.as_os_str().to_os_string();
+    name.push(".done");
+    PathBuf::from(name)
+}
+
+/// Record that the session behind a transcript has ended
+pub fn mark_transcript_done(transcript: &Path) -> Result<()> {
+    let marker = done_marker_path(transcript);
+    fs::write(&marker, Local::now().to_rfc3339())
+        .with_context(|| format!("Failed to write {}", marker.display()))
+}
+
+/// Check if a transcript has a done marker that is newer than the transcript itself.
+/// A transcript written after its marker belongs to a resumed session.
+fn is_transcript_done(path: &Path) -> bool {
+    let modified = |p: &Path| fs::metadata(p).and_then(|m| m.modified()).ok();
+    match (modified(&done_marker_path(path)), modified(path)) {
+        (Some(marker), Some(transcript)) => marker >= transcript,
+        (Some(_), None) => true,
+        _ => false,
+    }
+}
+
+/// Bytes read from the end of a transcript when looking for its last event
+const TRANSCRIPT_TAIL_BYTES: u64 = 64 * 1024;
+
+/// Check if a transcript's last activity is within the configured inactive threshold
 /// This helps avoid processing active sessions
-fn is_transcript_active(path: &std::path::Path, inactive_minutes: u64) -> bool {
+///
+/// With `use_content`, activity is the `timestamp` of the last logged event, so a file
+/// touched without new entries doesn't look active; transcripts without timestamps
+/// fall back to the file's mtime.
+fn is_transcript_active(path: &Path, inactive_minutes: u64, use_content: bool) -> bool {
+    if use_content {
+        if let Some(last_event) = last_event_time(path) {
+            // A timestamp ahead of this clock counts as just now
+            let elapsed = (Utc::now() - last_event).num_seconds().max(0) as u64;
+            return elapsed < inactive_minutes * 60;
+        }
+    }
+
     if let Ok(metadata) = fs::metadata(path) {
         if let Ok(modified) = metadata.modified() {
             if let Ok(elapsed) = modified.elapsed() {
@@ -111,19 +213,39 @@ fn is_transcript_active(path: &std::path::Path, inactive_minutes: u64) -> bool {
     false
 }
 
-/// Check if a transcript was modified yesterday
+/// Timestamp of the last event in a transcript that records one, read from its tail
+fn last_event_time(path: &Path) -> Option<DateTime<Utc>> {
+    let mut file = fs::File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    file.seek(SeekFrom::Start(len.saturating_sub(TRANSCRIPT_TAIL_BYTES)))
+        .ok()?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail).ok()?;
+
+    // A cut-off first line fails to parse and is skipped
+    String::from_utf8_lossy(&tail)
+        .lines()
+        .rev()
+        .find_map(|line| {
+            let entry: serde_json::Value = serde_json::from_str(line).ok()?;
+            let timestamp = entry.get("timestamp")?.as_str()?;
+            DateTime::parse_from_rfc3339(timestamp)
+                .ok()
+                .map(|time| time.with_timezone(&Utc))
+        })
+}
+
+/// Check if a transcript was modified yesterday, by the days `output.day_cutoff_hour` sets
 /// This helps limit auto-summarization to only yesterday's sessions
-fn is_transcript_from_yesterday(path: &std::path::Path) -> bool {
+fn is_transcript_from_yesterday(config: &Config, path: &std::path::Path) -> bool {
     use chrono::{Duration, Local};
 
     if let Ok(metadata) = fs::metadata(path) {
         if let Ok(modified) = metadata.modified() {
-            let modified_dt = chrono::DateTime::<Local>::from(modified);
-            let modified_date = modified_dt.date_naive();
+            let modified_date = config.effective_date(chrono::DateTime::<Local>::from(modified));
 
-            let now = Local::now();
-            let yesterday = (now - Duration::days(1)).date_naive();
-            let today = now.date_naive();
+            let today = config.effective_date(Local::now());
+            let yesterday = today - Duration::days(1);
 
             // Accept both yesterday and today (for sessions that started yesterday but ended today)
             return modified_date == yesterday || modified_date == today;
@@ -132,43 +254,166 @@ fn is_transcript_from_yesterday(path: &std::path::Path) -> bool {
     false
 }
 
+/// Auto-summarizations started on one day, stored in `.summarize_count`
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SummarizeCount {
+    date: String,
+    count: usize,
+}
+
+/// Path of the per-day auto-summarize counter
+fn summarize_count_path(config: &Config) -> PathBuf {
+    config.storage_path().join(".summarize_count")
+}
+
+/// Number of auto-summarizations already started today
+pub fn summarized_today(config: &Config) -> usize {
+    fs::read_to_string(summarize_count_path(config))
+        .ok()
+        .and_then(|content| serde_json::from_str::<SummarizeCount>(&content).ok())
+        .filter(|counter| counter.date == config.today())
+        .map(|counter| counter.count)
+        .unwrap_or(0)
+}
+
+/// Add `count` auto-summarizations to today's counter
+pub fn record_auto_summarized(config: &Config, count: usize) -> Result<()> {
+    let counter = SummarizeCount {
+        date: config.today(),
+        count: summarized_today(config) + count,
+    };
+    fs::create_dir_all(config.storage_path()).context("Failed to create storage directory")?;
+    write_atomic(
+        &summarize_count_path(config),
+        serde_json::to_string(&counter)?,
+    )
+    .context("Failed to save auto-summarize count")
+}
+
+/// Held while auto-summarize jobs are being spawned; the lock is released on drop
+#[derive(Debug)]
+pub struct SummarizeLock {
+    _file: fs::File,
+}
+
+/// Take the advisory lock on `storage/.summarize.lock` without blocking.
+///
+/// Returns `None` when another process (e.g. a second `daily show`) holds it.
+pub fn try_lock_summarize(config: &Config) -> Result<Option<SummarizeLock>> {
+    fs::create_dir_all(config.storage_path()).context("Failed to create storage directory")?;
+    let path = config.storage_path().join(".summarize.lock");
+    let file = fs::File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    match file.try_lock() {
+        Ok(()) => Ok(Some(SummarizeLock { _file: file })),
+        Err(fs::TryLockError::WouldBlock) => Ok(None),
+        Err(fs::TryLockError::Error(e)) => {
+            Err(e).with_context(|| format!("Failed to lock {}", path.display()))
+        }
+    }
+}
+
 /// Find transcripts that have not been summarized yet
 ///
 /// This function now uses transcript_path from session.md frontmatter for accurate tracking.
 /// It also applies safety measures:
 /// 1. Only processes transcripts from yesterday or today (to avoid processing too many old files)
-/// 2. Only processes transcripts that haven't been modified in the last 2 hours (likely inactive)
-/// 3. Limits to MAX_AUTO_SUMMARIZE to prevent fork bomb
+/// 2. Only processes transcripts whose session has ended: a `.done` marker from the
+///    SessionEnd hook, or without one, no modification within the inactive threshold
+/// 3. Limits to `max_auto_summarize` per invocation to prevent fork bomb
+/// 4. Stops once today's `daily_summarize_budget` is used up
+/// 5. Skips transcripts a queued or running job is already summarizing
 pub fn find_unsummarized_transcripts(config: &Config) -> Result<Vec<UnsummarizedTranscript>> {
-    let all_transcripts = find_all_transcripts()?;
+    filter_unsummarized(config, find_all_transcripts(config)?)
+}
+
+/// Transcripts one auto-summarize run may pick: `max_auto_summarize`, capped by what is
+/// left of today's `daily_summarize_budget`
+pub fn auto_summarize_limit(config: &Config) -> usize {
+    let limit = config.summarization.max_auto_summarize;
+    match config.summarization.daily_summarize_budget {
+        0 => limit,
+        budget => limit.min(budget.saturating_sub(summarized_today(config))),
+    }
+}
+
+/// Apply the `find_unsummarized_transcripts` guards to the given transcript files.
+///
+/// Stops after `auto_summarize_limit` transcripts, so later ones aren't checked.
+pub fn filter_unsummarized(
+    config: &Config,
+    transcripts: Vec<PathBuf>,
+) -> Result<Vec<UnsummarizedTranscript>> {
+    let limit = auto_summarize_limit(config);
+    let budget = config.summarization.daily_summarize_budget;
+    let budget_bound = budget > 0 && limit < config.summarization.max_auto_summarize;
+    if budget > 0 && limit == 0 {
+        info!(
+            "Daily auto-summarize budget of {} reached; skipping until tomorrow",
+            budget
+        );
+        return Ok(Vec::new());
+    }
+
     let archived_paths = get_archived_transcript_paths(config)?;
+    // Transcripts a queued or running job is already summarizing
+    let in_flight: HashSet<PathBuf> = JobManager::new(config)
+        .and_then(|jobs| jobs.list(false))
+        .map(|jobs| jobs.into_iter().map(|job| job.transcript_path).collect())
+        .unwrap_or_default();
 
     let mut unsummarized = Vec::new();
-    const MAX_AUTO_SUMMARIZE: usize = 3; // Conservative limit to prevent fork bomb
+    let mut decoded_dirs: HashMap<OsString, Option<PathBuf>> = HashMap::new();
 
-    for transcript_path in all_transcripts {
+    for transcript_path in transcripts {
         // IMPORTANT: Only process transcripts from yesterday or today
         // This prevents processing too many old files when switching directories
-        if !is_transcript_from_yesterday(&transcript_path) {
+        if !is_transcript_from_yesterday(config, &transcript_path) {
             continue;
         }
 
-        // Skip if transcript is still active (modified within the configured threshold)
-        if is_transcript_active(
-            &transcript_path,
-            config.summarization.auto_summarize_inactive_minutes,
-        ) {
+        // Skip if the session may still be going: no done marker and
+        // modified within the configured threshold
+        if !is_transcript_done(&transcript_path)
+            && is_transcript_active(
+                &transcript_path,
+                config.summarization.auto_summarize_inactive_minutes,
+                config.summarization.inactivity_check == "content",
+            )
+        {
             continue;
         }
 
         // Check if already archived by exact path matching
         let path_str = transcript_path.to_string_lossy().to_string();
-        if archived_paths.contains(&path_str) {
+        if archived_paths.contains(&path_str) || in_flight.contains(&transcript_path) {
+            continue;
+        }
+
+        // Decoding probes the filesystem, so do it once per project directory
+        let cwd = transcript_path
+            .parent()
+            .and_then(|dir| dir.file_name())
+            .and_then(|name| {
+                decoded_dirs
+                    .entry(name.to_os_string())
+                    .or_insert_with(|| decode_project_dir(&name.to_string_lossy()))
+                    .clone()
+            });
+        if is_excluded_project(
+            &config.summarization.excluded_cwd_patterns,
+            &transcript_path,
+            cwd.as_deref(),
+        ) {
             continue;
         }
 
         // Check if the transcript file is empty or invalid
-        let data = match TranscriptParser::parse(&transcript_path) {
+        let data = match parse_transcript(config, &transcript_path) {
             Ok(data) => data,
             Err(_) => continue,
         };
@@ -188,11 +433,17 @@ pub fn find_unsummarized_transcripts(config: &Config) -> Result<Vec<Unsummarized
         unsummarized.push(UnsummarizedTranscript {
             path: transcript_path.clone(),
             session_id,
-            cwd: None, // CWD is not available from transcript files
+            cwd,
         });
 
         // Conservative limit to prevent fork bomb
-        if unsummarized.len() >= MAX_AUTO_SUMMARIZE {
+        if unsummarized.len() >= limit {
+            if budget_bound {
+                info!(
+                    "Daily auto-summarize budget of {} reached; remaining sessions wait until tomorrow",
+                    budget
+                );
+            }
             break;
         }
     }
@@ -226,7 +477,7 @@ pub fn should_trigger_auto_summarize(config: &Config) -> Result<bool> {
     }
 
     let now = Local::now();
-    let today_date = now.format("%Y-%m-%d").to_string();
+    let today_date = config.effective_date(now);
 
     // Parse trigger time
     let trigger_time =
@@ -242,7 +493,7 @@ pub fn should_trigger_auto_summarize(config: &Config) -> Result<bool> {
     // Check last check time
     if let Some(last_check_str) = &config.summarization.last_auto_summarize_check {
         if let Ok(last_check) = chrono::DateTime::parse_from_rfc3339(last_check_str) {
-            let last_check_date = last_check.format("%Y-%m-%d").to_string();
+            let last_check_date = config.effective_date(last_check.with_timezone(&Local));
 
             // If last check was today and after trigger time, don't trigger again
             if last_check_date == today_date {
@@ -261,6 +512,116 @@ pub fn should_trigger_auto_summarize(config: &Config) -> Result<bool> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_summarize_count_budget() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+        config.summarization.daily_summarize_budget = 2;
+
+        assert_eq!(summarized_today(&config), 0);
+        record_auto_summarized(&config, 1).unwrap();
+        record_auto_summarized(&config, 1).unwrap();
+        assert_eq!(summarized_today(&config), 2);
+        assert!(find_unsummarized_transcripts(&config).unwrap().is_empty());
+
+        // A counter from another day doesn't count against today
+        fs::write(
+            summarize_count_path(&config),
+            r#"{"date":"2000-01-01","count":5}"#,
+        )
+        .unwrap();
+        assert_eq!(summarized_today(&config), 0);
+    }
+
+    #[test]
+    fn test_summarize_lock_is_exclusive() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+
+        let held = try_lock_summarize(&config).unwrap();
+        assert!(held.is_some());
+        assert!(try_lock_summarize(&config).unwrap().is_none());
+
+        drop(held);
+        assert!(try_lock_summarize(&config).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_decode_project_dir() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("work/my-app")).unwrap();
+        fs::create_dir_all(root.join("work/.config")).unwrap();
+        fs::write(root.join("work/notes"), "").unwrap();
+
+        assert_eq!(
+            decode_encoded_path(root, "work-my-app"),
+            Some(root.join("work/my-app"))
+        );
+        assert_eq!(
+            decode_encoded_path(root, "work--config"),
+            Some(root.join("work/.config"))
+        );
+        assert_eq!(decode_encoded_path(root, "work-notes"), None);
+        assert_eq!(decode_encoded_path(root, "work-missing"), None);
+
+        // Ambiguous once a second directory encodes to the same name
+        fs::create_dir_all(root.join("work/my/app")).unwrap();
+        assert_eq!(decode_encoded_path(root, "work-my-app"), None);
+
+        assert_eq!(decode_project_dir("rollout-16"), None);
+    }
+
+    #[test]
+    fn test_is_excluded_project() {
+        let patterns = vec!["/home/me/scratch/*".to_string(), "*nda-client*".to_string()];
+        let transcript = |project: &str| {
+            PathBuf::from(format!("/home/me/.claude/projects/{}/abc.jsonl", project))
+        };
+
+        // Matched through the cwd
+        assert!(is_excluded_project(
+            &patterns,
+            &transcript("-x"),
+            Some(Path::new("/home/me/scratch/try-rust"))
+        ));
+        assert!(!is_excluded_project(
+            &patterns,
+            &transcript("-x"),
+            Some(Path::new("/home/me/work/api"))
+        ));
+        // A known cwd is trusted over the lossy directory name
+        assert!(!is_excluded_project(
+            &patterns,
+            &transcript("-home-me-scratch-a"),
+            Some(Path::new("/home/me/scratch-a"))
+        ));
+
+        // Matched through Claude's encoded project directory
+        assert!(is_excluded_project(
+            &patterns,
+            &transcript("-home-me-scratch-try-rust"),
+            None
+        ));
+        assert!(is_excluded_project(
+            &patterns,
+            &transcript("-home-me-work-nda-client-app"),
+            None
+        ));
+        assert!(!is_excluded_project(
+            &patterns,
+            &transcript("-home-me-work-api"),
+            None
+        ));
+        assert!(!is_excluded_project(
+            &[],
+            &transcript("-home-me-scratch-a"),
+            None
+        ));
+    }
+
     #[test]
     fn test_should_trigger_auto_summarize_disabled() {
         let mut config = Config::default();
@@ -269,6 +630,63 @@ mod tests {
         assert!(!should_trigger_auto_summarize(&config).unwrap());
     }
 
+    #[test]
+    fn test_is_transcript_active_by_content() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let transcript = temp_dir.path().join("abc123.jsonl");
+        let entry = |time: DateTime<Utc>| {
+            format!(
+                r#"{{"type":"user","message":{{"role":"user","content":"hi"}},"timestamp":"{}"}}"#,
+                time.to_rfc3339()
+            )
+        };
+
+        // Touched just now, but the last event is two hours old
+        let old = Utc::now() - chrono::Duration::hours(2);
+        fs::write(
+            &transcript,
+            format!("{}\n{}\n{{\"type\":\"summary\"}}\n", entry(old), entry(old)),
+        )
+        .unwrap();
+        assert!(!is_transcript_active(&transcript, 30, true));
+        assert!(is_transcript_active(&transcript, 30, false));
+
+        let recent = Utc::now() - chrono::Duration::minutes(5);
+        fs::write(&transcript, format!("{}\n{}\n", entry(old), entry(recent))).unwrap();
+        assert!(is_transcript_active(&transcript, 30, true));
+
+        // No timestamps: fall back to mtime
+        fs::write(&transcript, "{\"type\":\"summary\"}\n").unwrap();
+        assert!(is_transcript_active(&transcript, 30, true));
+    }
+
+    #[test]
+    fn test_done_marker() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let transcript = temp_dir.path().join("abc123.jsonl");
+        fs::write(&transcript, "{}\n").unwrap();
+
+        assert_eq!(
+            done_marker_path(&transcript),
+            temp_dir.path().join("abc123.jsonl.done")
+        );
+        assert!(!is_transcript_done(&transcript));
+        assert!(is_transcript_active(&transcript, 30, true));
+
+        mark_transcript_done(&transcript).unwrap();
+        assert!(is_transcript_done(&transcript));
+
+        // Resuming the session writes to the transcript after the marker
+        let marker_time = fs::metadata(done_marker_path(&transcript))
+            .unwrap()
+            .modified()
+            .unwrap();
+        let file = fs::File::options().write(true).open(&transcript).unwrap();
+        file.set_modified(marker_time + std::time::Duration::from_secs(60))
+            .unwrap();
+        assert!(!is_transcript_done(&transcript));
+    }
+
     #[test]
     fn test_should_trigger_auto_summarize_no_last_check() {
         let mut config = Config::default();
@@ -9,13 +9,17 @@ pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
 
-    /// Verbose output
+    /// Verbose output (debug-level logs on stderr)
     #[arg(short, long, global = true)]
     pub verbose: bool,
 
     /// Config file path
     #[arg(short, long, global = true)]
     pub config: Option<PathBuf>,
+
+    /// Print machine-readable JSON instead of text (view, today, yest, jobs list, stats, search, tags)
+    #[arg(long, global = true)]
+    pub json: bool,
 }
 
 #[derive(Subcommand)]
@@ -33,6 +37,24 @@ pub enum Commands {
         /// Do not open browser automatically
         #[arg(long)]
         no_open: bool,
+
+        /// Require this token on API requests for this run (overrides server.auth_token)
+        #[arg(long)]
+        token: Option<String>,
+
+        /// Show the running dashboard's URL and pid
+        #[arg(long, conflicts_with = "stop")]
+        status: bool,
+
+        /// Stop the running dashboard
+        #[arg(long)]
+        stop: bool,
+    },
+
+    /// Open the archive directory in the file manager
+    Open {
+        /// Date directory to open (format: yyyy-mm-dd, default: storage root)
+        date: Option<String>,
     },
 
     /// View archives (interactive date selection if no date specified)
@@ -48,6 +70,30 @@ pub enum Commands {
         /// List all sessions for the day
         #[arg(long)]
         list: bool,
+
+        /// Show the resume chain of a session (session ID, name, or DATE/NAME)
+        #[arg(long, value_name = "SESSION")]
+        chain: Option<String>,
+
+        /// Print one session's full content (date defaults to today)
+        #[arg(long, value_name = "NAME", conflicts_with_all = ["chain", "summary_only", "list"])]
+        session: Option<String>,
+
+        /// List sessions tagged NAME (on --date, or across all dates)
+        #[arg(long, value_name = "NAME", conflicts_with_all = ["chain", "session", "summary_only", "list"])]
+        tag: Option<String>,
+
+        /// Open the date in the web dashboard, offering to start one if none is running
+        #[arg(long, conflicts_with_all = ["chain", "session", "tag", "summary_only", "list"])]
+        open_web: bool,
+
+        /// Show the archives of recent days one after another, newest first
+        #[arg(long, conflicts_with_all = ["date", "chain", "session", "tag", "open_web", "summary_only", "list"])]
+        all: bool,
+
+        /// Days shown by --all, counting today
+        #[arg(long, value_name = "N", default_value_t = 7, requires = "all")]
+        days: u32,
     },
 
     /// View today's archive
@@ -75,8 +121,12 @@ pub enum Commands {
     /// Manually trigger summarization
     Summarize {
         /// Session transcript path
-        #[arg(short, long)]
-        transcript: PathBuf,
+        #[arg(short, long, required_unless_present = "session_id")]
+        transcript: Option<PathBuf>,
+
+        /// Session ID to look up among the configured transcript sources
+        #[arg(long, conflicts_with = "transcript")]
+        session_id: Option<String>,
 
         /// Task name for the archive
         #[arg(short = 'n', long)]
@@ -93,6 +143,40 @@ pub enum Commands {
         /// Job ID for tracking (internal use)
         #[arg(long)]
         job_id: Option<String>,
+
+        /// Archive title (topic) instead of the generated one
+        #[arg(long)]
+        title: Option<String>,
+
+        /// Archive under this date instead of today (format: yyyy-mm-dd)
+        #[arg(long)]
+        date: Option<String>,
+
+        /// Extra tag for the archive (repeatable)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+
+        /// Print the prompt that would be sent to Claude, without calling it or writing anything
+        #[arg(long, conflicts_with_all = ["foreground", "job_id"])]
+        dry_run: bool,
+
+        /// Model to use instead of summarization.model (haiku, sonnet, opus)
+        #[arg(long)]
+        model: Option<String>,
+    },
+
+    /// Summarize every transcript in a directory, skipping ones already archived
+    Import {
+        /// Directory searched recursively for .jsonl transcripts
+        dir: PathBuf,
+
+        /// Archive under this date (format: yyyy-mm-dd, default: each transcript's last-modified day)
+        #[arg(long)]
+        date: Option<String>,
+
+        /// List the transcripts that would be imported without summarizing them
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Generate daily digest from sessions (consolidate sessions into daily.md)
@@ -112,6 +196,109 @@ pub enum Commands {
         /// Force regenerate daily summary even without session files (re-process existing daily.md)
         #[arg(short, long)]
         force: bool,
+
+        /// Generate and print the summary into .daily-preview.md without touching daily.md
+        #[arg(long, conflicts_with = "background")]
+        preview: bool,
+
+        /// Print the prompt that would be sent to Claude, without calling it or writing anything
+        #[arg(long, conflicts_with_all = ["background", "preview"])]
+        dry_run: bool,
+
+        /// Digest every date that has sessions (resumable)
+        #[arg(long, conflicts_with_all = ["relative_date", "date", "background", "preview", "dry_run", "from"])]
+        all: bool,
+
+        /// Start of a date range to digest (format: yyyy-mm-dd, resumable)
+        #[arg(long, conflicts_with_all = ["relative_date", "date", "background", "preview", "dry_run"])]
+        from: Option<String>,
+
+        /// End of the date range (default: today)
+        #[arg(long, requires = "from")]
+        to: Option<String>,
+
+        /// Dates digested in parallel with --all or --from
+        #[arg(long, default_value_t = 2)]
+        concurrency: usize,
+
+        /// Digest today even though it is still in progress (session files are kept)
+        #[arg(long)]
+        allow_today: bool,
+
+        /// Model to use instead of summarization.model (haiku, sonnet, opus)
+        #[arg(long)]
+        model: Option<String>,
+
+        /// Job ID for tracking (internal use)
+        #[arg(long, hide = true)]
+        job_id: Option<String>,
+    },
+
+    /// Run in the foreground and digest yesterday once `digest_time` passes
+    Daemon,
+
+    /// Run in the foreground and summarize each session once its transcript goes quiet
+    Watch,
+
+    /// Undo a digest: set daily.md aside so the next digest starts from the sessions
+    Undigest {
+        /// Date to undigest (format: yyyy-mm-dd)
+        date: String,
+    },
+
+    /// Delete a date's archive (sessions and digest)
+    Delete {
+        /// Date to delete (format: yyyy-mm-dd)
+        date: String,
+
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Merge sessions of one task into a single archive, deleting the originals
+    Merge {
+        /// Sessions to merge: `name` or `yyyy-mm-dd/name`
+        #[arg(required = true, num_args = 2..)]
+        sessions: Vec<String>,
+
+        /// Date of sessions given without one (default: today)
+        #[arg(short, long)]
+        date: Option<String>,
+
+        /// Topic of the merged session (default: the earliest session's)
+        #[arg(short, long)]
+        title: Option<String>,
+
+        /// Allow merging sessions from different dates
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Regenerate a session summary from its transcript with the current prompts
+    Replay {
+        /// Date of the session (format: yyyy-mm-dd)
+        date: String,
+
+        /// Session name
+        session: String,
+    },
+
+    /// Compress old date directories into <date>.tar.gz to save disk space
+    Archive {
+        /// Compress dates older than this many days
+        #[arg(long, default_value_t = 90)]
+        older_than_days: u32,
+    },
+
+    /// Move date directories to another storage layout and switch storage.layout
+    MigrateLayout {
+        /// Target layout: flat (<date>/) or nested (<yyyy>/<mm>/<date>/)
+        layout: String,
+
+        /// Show what would move without moving anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Extract skill from archive
@@ -124,7 +311,7 @@ pub enum Commands {
         #[arg(short, long)]
         session: Option<String>,
 
-        /// Output directory for skill
+        /// Output directory for skill ("-" prints SKILL.md to stdout)
         #[arg(short, long)]
         output: Option<PathBuf>,
     },
@@ -139,7 +326,7 @@ pub enum Commands {
         #[arg(short, long)]
         session: Option<String>,
 
-        /// Output directory for command
+        /// Output file for command ("-" prints it to stdout)
         #[arg(short, long)]
         output: Option<PathBuf>,
     },
@@ -153,6 +340,14 @@ pub enum Commands {
         /// Delete a pending skill (format: YYYY-MM-DD/skill-name)
         #[arg(long)]
         delete: Option<String>,
+
+        /// Print the pending skills instead of reviewing them interactively
+        #[arg(long, conflicts_with_all = ["install", "delete"])]
+        list: bool,
+
+        /// Install and delete the picked skills without asking for confirmation
+        #[arg(long, conflicts_with_all = ["install", "delete", "list"])]
+        force: bool,
     },
 
     /// Manage background jobs
@@ -176,12 +371,22 @@ pub enum Commands {
         haiku: bool,
     },
 
+    /// Show hooks, jobs and archive health at a glance
+    Status,
+
+    /// Check the claude CLI, storage, config, hooks and disk space
+    Doctor,
+
     /// Show or update configuration
     Config {
         /// Set storage path
         #[arg(long)]
         set_storage: Option<PathBuf>,
 
+        /// Set a setting by dotted key, e.g. summarization.model=haiku (repeatable)
+        #[arg(long, value_name = "KEY=VALUE")]
+        set: Vec<String>,
+
         /// Show current config
         #[arg(long)]
         show: bool,
@@ -189,6 +394,18 @@ pub enum Commands {
         /// Interactive configuration mode
         #[arg(short, long)]
         interactive: bool,
+
+        /// Validate custom prompt templates
+        #[arg(long)]
+        validate_templates: bool,
+
+        /// Write the current configuration to a TOML file
+        #[arg(long, value_name = "PATH", conflicts_with = "import")]
+        export: Option<PathBuf>,
+
+        /// Validate and load configuration from a TOML file
+        #[arg(long, value_name = "PATH")]
+        import: Option<PathBuf>,
     },
 
     /// Install plugin to Claude Code
@@ -226,6 +443,90 @@ pub enum Commands {
         days: usize,
     },
 
+    /// Show session activity stats from existing archives
+    Stats {
+        /// Number of days to include (default: 30)
+        #[arg(short, long, default_value = "30")]
+        days: usize,
+
+        /// Show summarization tokens and estimated cost instead of activity
+        #[arg(long)]
+        cost: bool,
+    },
+
+    /// Generate a weekly digest with sessions grouped by topic
+    Weekly {
+        /// Any date within the week (format: yyyy-mm-dd, default: today)
+        #[arg(short, long)]
+        date: Option<String>,
+    },
+
+    /// Export a date's daily summary and sessions to a single file
+    Export {
+        /// Date to export (format: yyyy-mm-dd, default: today)
+        #[arg(short, long)]
+        date: Option<String>,
+
+        /// Output format
+        #[arg(short, long, default_value = "html", value_parser = ["html", "json", "markdown"])]
+        format: String,
+
+        /// Output file (default: daily-DATE.EXT in the current directory)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Search archived sessions and daily summaries
+    Search {
+        /// Text to search for (case-insensitive)
+        query: String,
+
+        /// Only search from this date (format: yyyy-mm-dd)
+        #[arg(long)]
+        date_from: Option<String>,
+
+        /// Only search up to this date (format: yyyy-mm-dd)
+        #[arg(long)]
+        date_to: Option<String>,
+
+        /// Only include archives with this frontmatter tag
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// Only include sessions of this type (e.g. debugging, feature)
+        #[arg(long = "type")]
+        session_type: Option<String>,
+
+        /// Treat the query as a regular expression
+        #[arg(long)]
+        regex: bool,
+
+        /// Maximum number of results
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+
+    /// Rebuild the search index from scratch
+    Reindex,
+
+    /// Rename or add tags in session archives
+    Tag {
+        /// Rename a tag across all session archives
+        #[arg(long, num_args = 2, value_names = ["OLD", "NEW"], conflicts_with = "add")]
+        rename: Option<Vec<String>>,
+
+        /// Add a tag to every session of a date (requires --date)
+        #[arg(long, requires = "date")]
+        add: Option<String>,
+
+        /// Date whose sessions to tag (format: yyyy-mm-dd)
+        #[arg(short, long)]
+        date: Option<String>,
+    },
+
+    /// List all session tags with their counts
+    Tags,
+
     /// Handle Claude Code hooks (internal use)
     Hook {
         #[command(subcommand)]
@@ -240,6 +541,18 @@ pub enum JobsAction {
         /// Show all jobs (including completed)
         #[arg(short, long)]
         all: bool,
+
+        /// Only show jobs started within this long ago (e.g. 30m, 2h, 1d)
+        #[arg(long, value_name = "DURATION")]
+        since: Option<String>,
+
+        /// Output format (json is the same as --json)
+        #[arg(long, default_value = "table", value_parser = ["table", "json"])]
+        format: String,
+
+        /// Order jobs by start time (newest first), elapsed time (longest first) or status
+        #[arg(long, default_value = "started", value_parser = ["started", "elapsed", "status"])]
+        sort: String,
     },
 
     /// Show job log
@@ -256,17 +569,36 @@ pub enum JobsAction {
         follow: bool,
     },
 
-    /// Kill a running job
+    /// Show job details and claude usage
+    Info {
+        /// Job ID
+        job_id: String,
+    },
+
+    /// Kill a running job or cancel a queued one
     Kill {
         /// Job ID
         job_id: String,
     },
 
+    /// Start queued jobs while under summarization.max_concurrent_jobs
+    RunQueue,
+
+    /// Re-run a failed or finished summarization job as a new job
+    Retry {
+        /// Job ID
+        job_id: String,
+    },
+
     /// Cleanup old jobs
     Cleanup {
-        /// Keep jobs from last N days (default: 7)
-        #[arg(short, long, default_value = "7")]
-        days: u32,
+        /// Keep jobs from last N days (default: 7, or jobs.log_retention_days with --logs-only)
+        #[arg(short, long)]
+        days: Option<u32>,
+
+        /// Only delete logs of old finished jobs, keeping their metadata for stats
+        #[arg(long)]
+        logs_only: bool,
 
         /// Show what would be removed without removing
         #[arg(long)]
@@ -281,4 +613,7 @@ pub enum HookType {
 
     /// SessionEnd hook handler
     SessionEnd,
+
+    /// UserPromptSubmit hook handler
+    UserPromptSubmit,
 }
@@ -0,0 +1,49 @@
+use anyhow::Result;
+use chrono::{Duration, Local, NaiveDate};
+use colored::Colorize;
+
+use crate::archive::ArchiveManager;
+use crate::config::load_config;
+
+/// Compress date directories older than `older_than_days` into `<date>.tar.gz`
+pub async fn run(older_than_days: u32) -> Result<()> {
+    let config = load_config()?;
+    // Counted from the effective today, so a day isn't packed before `day_cutoff_hour` ends it
+    let cutoff = config.effective_date(Local::now()) - Duration::days(i64::from(older_than_days));
+    let manager = ArchiveManager::new(config);
+
+    let dates: Vec<String> = manager
+        .list_dates()?
+        .into_iter()
+        .filter(|date| {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok_and(|d| d < cutoff)
+                && !manager.is_compressed(date)
+        })
+        .collect();
+
+    if dates.is_empty() {
+        println!(
+            "{}",
+            format!("No uncompressed dates older than {} days.", older_than_days).dimmed()
+        );
+        return Ok(());
+    }
+
+    let mut compressed = 0;
+    for date in &dates {
+        match manager.compress_date(date) {
+            Ok(path) => {
+                println!("{} {} → {}", "✓".green(), date, path.display());
+                compressed += 1;
+            }
+            Err(e) => eprintln!("{} {}: {}", "✗".red(), date, e),
+        }
+    }
+
+    println!(
+        "\nCompressed {} of {} date(s). They stay readable with `daily view`.",
+        compressed,
+        dates.len()
+    );
+    Ok(())
+}
@@ -1,18 +1,43 @@
 use anyhow::{Context, Result};
+use colored::Colorize;
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-use crate::config::{get_config_path, load_config, save_config};
+use crate::config::{get_config_path, load_config, save_config, Config};
+use crate::fs_util::write_atomic;
+use crate::summarizer::{language_name, Prompts, SUPPORTED_LANGUAGES, TEMPLATE_KINDS};
 
 /// Show or update configuration
-pub async fn run(set_storage: Option<PathBuf>, show: bool, interactive: bool) -> Result<()> {
+pub async fn run(
+    set_storage: Option<PathBuf>,
+    set: Vec<String>,
+    show: bool,
+    interactive: bool,
+    validate_templates: bool,
+    export: Option<PathBuf>,
+    import: Option<PathBuf>,
+) -> Result<()> {
     let mut config = load_config()?;
 
+    if let Some(path) = export {
+        return export_config(&config, &path);
+    }
+
+    if let Some(path) = import {
+        return import_config(&config, &path);
+    }
+
     // Interactive mode
     if interactive {
         return configure_interactive(&mut config).await;
     }
 
+    if validate_templates {
+        return validate_custom_templates(&config);
+    }
+
     // Update storage path if provided
     if let Some(path) = set_storage {
         config.storage.path = path.clone();
@@ -21,6 +46,21 @@ pub async fn run(set_storage: Option<PathBuf>, show: bool, interactive: bool) ->
         return Ok(());
     }
 
+    if !set.is_empty() {
+        let updated = apply_settings(&config, &set)?;
+        save_config(&updated)?;
+        for (key, old, new) in config_changes(&flatten_config(&config)?, &flatten_config(&updated)?)
+        {
+            println!(
+                "[daily] {}: {} -> {}",
+                key,
+                old.as_deref().unwrap_or("(unset)").red(),
+                new.as_deref().unwrap_or("(unset)").green()
+            );
+        }
+        return Ok(());
+    }
+
     // Show current config
     if show || set_storage.is_none() {
         let config_path = get_config_path()?;
@@ -28,15 +68,12 @@ pub async fn run(set_storage: Option<PathBuf>, show: bool, interactive: bool) ->
         println!();
         println!("Current settings:");
         println!("  Storage path: {}", config.storage.path.display());
+        println!("  Storage layout: {}", config.storage.layout);
         println!("  Summarization model: {}", config.summarization.model);
         println!(
             "  Summary language: {} ({})",
             config.summarization.summary_language,
-            if config.summarization.summary_language == "zh" {
-                "Chinese"
-            } else {
-                "English"
-            }
+            language_name(&config.summarization.summary_language).unwrap_or("unsupported")
         );
         println!(
             "  Enable daily summary: {}",
@@ -48,7 +85,13 @@ pub async fn run(set_storage: Option<PathBuf>, show: bool, interactive: bool) ->
         );
         println!("  SessionStart hook: {}", config.hooks.enable_session_start);
         println!("  SessionEnd hook: {}", config.hooks.enable_session_end);
+        println!(
+            "  UserPromptSubmit hook: {}",
+            config.hooks.enable_user_prompt
+        );
         println!("  Background timeout: {}s", config.hooks.background_timeout);
+        println!("  Day cutoff hour: {}", config.output.day_cutoff_hour);
+        println!("  Path template: {}", config.output.path_template);
         println!();
         println!("Archive settings:");
         println!(
@@ -58,6 +101,7 @@ pub async fn run(set_storage: Option<PathBuf>, show: bool, interactive: bool) ->
         println!("  Tags: {}", config.archive.tags.join(", "));
         println!("  Include cwd: {}", config.archive.include_cwd);
         println!("  Include git info: {}", config.archive.include_git_info);
+        println!("  Max diff chars: {}", config.archive.max_diff_chars);
         println!();
         println!("Tip: Use 'daily config -i' for interactive configuration");
     }
@@ -65,8 +109,214 @@ pub async fn run(set_storage: Option<PathBuf>, show: bool, interactive: bool) ->
     Ok(())
 }
 
+/// Write the configuration to a TOML file
+fn export_config(config: &Config, path: &Path) -> Result<()> {
+    let content = toml::to_string_pretty(config).context("Failed to serialize configuration")?;
+    write_atomic(path, content).context(format!("Failed to write {}", path.display()))?;
+    println!("[daily] Configuration exported to: {}", path.display());
+    Ok(())
+}
+
+/// Load a TOML configuration, validate it and save it, printing what changed
+fn import_config(current: &Config, path: &Path) -> Result<()> {
+    let content = fs::read_to_string(path).context(format!("Failed to read {}", path.display()))?;
+    let raw: toml::Value =
+        toml::from_str(&content).context(format!("Invalid TOML in {}", path.display()))?;
+    let imported: Config = raw
+        .clone()
+        .try_into()
+        .context(format!("Invalid configuration in {}", path.display()))?;
+    imported.validate()?;
+
+    let before = flatten_config(current)?;
+    let after = flatten_config(&imported)?;
+
+    // Unknown keys are dropped by deserialization; say so instead of failing
+    let mut provided = BTreeMap::new();
+    flatten_value("", &raw, &mut provided);
+    for key in provided.keys().filter(|key| !after.contains_key(*key)) {
+        println!("  {} ignored unknown setting {}", "!".yellow(), key);
+    }
+
+    save_config(&imported)?;
+
+    let changes = config_changes(&before, &after);
+    if changes.is_empty() {
+        println!("[daily] Imported {}: no settings changed", path.display());
+        return Ok(());
+    }
+    println!("[daily] Imported {}:", path.display());
+    for (key, old, new) in changes {
+        println!(
+            "  {}: {} -> {}",
+            key,
+            old.as_deref().unwrap_or("(unset)").red(),
+            new.as_deref().unwrap_or("(unset)").green()
+        );
+    }
+    Ok(())
+}
+
+/// Apply `KEY=VALUE` assignments with dotted keys (e.g. `summarization.model=haiku`).
+///
+/// Values are read according to the setting's current type; unknown keys, mismatched
+/// types and settings that fail validation are rejected.
+fn apply_settings(config: &Config, assignments: &[String]) -> Result<Config> {
+    let mut value = serde_json::to_value(config).context("Failed to serialize configuration")?;
+
+    for assignment in assignments {
+        let Some((key, raw)) = assignment.split_once('=') else {
+            anyhow::bail!("Expected KEY=VALUE, got: {}", assignment);
+        };
+        let key = key.trim();
+        let setting = key
+            .split('.')
+            .try_fold(&mut value, |node, part| node.get_mut(part))
+            .with_context(|| format!("Unknown setting: {}", key))?;
+        *setting = parse_setting_value(setting, raw.trim())
+            .with_context(|| format!("Invalid value for {}", key))?;
+
+        // Checked per assignment so a type error names its key
+        serde_json::from_value::<Config>(value.clone())
+            .with_context(|| format!("Invalid value for {}", key))?;
+    }
+
+    let updated: Config = serde_json::from_value(value)?;
+    updated.validate()?;
+    Ok(updated)
+}
+
+/// Read a command-line value as the type of the setting it replaces
+fn parse_setting_value(current: &serde_json::Value, raw: &str) -> Result<serde_json::Value> {
+    use serde_json::Value;
+
+    let unquoted = raw
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .unwrap_or(raw);
+    Ok(match current {
+        // Unset optional settings are all strings or paths
+        Value::String(_) | Value::Null => Value::String(unquoted.to_string()),
+        Value::Bool(_) => Value::Bool(
+            raw.parse()
+                .map_err(|_| anyhow::anyhow!("expected true or false, got {}", raw))?,
+        ),
+        Value::Number(_) => Value::Number(
+            raw.parse()
+                .map_err(|_| anyhow::anyhow!("expected a number, got {}", raw))?,
+        ),
+        // A JSON array, or a comma-separated list of strings
+        Value::Array(_) if raw.starts_with('[') => {
+            serde_json::from_str(raw).context("Invalid JSON array")?
+        }
+        Value::Array(_) => Value::Array(
+            raw.split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| Value::String(item.to_string()))
+                .collect(),
+        ),
+        Value::Object(_) => {
+            let parsed: Value = serde_json::from_str(raw)
+                .context("expected a JSON object; or set its keys one at a time")?;
+            if !parsed.is_object() {
+                anyhow::bail!("expected a JSON object; or set its keys one at a time");
+            }
+            parsed
+        }
+    })
+}
+
+/// Flatten a configuration into dotted keys and displayed values
+fn flatten_config(config: &Config) -> Result<BTreeMap<String, String>> {
+    let value = toml::Value::try_from(config).context("Failed to serialize configuration")?;
+    let mut flat = BTreeMap::new();
+    flatten_value("", &value, &mut flat);
+    Ok(flat)
+}
+
+fn flatten_value(prefix: &str, value: &toml::Value, flat: &mut BTreeMap<String, String>) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                let key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten_value(&key, value, flat);
+            }
+        }
+        _ => {
+            flat.insert(prefix.to_string(), value.to_string());
+        }
+    }
+}
+
+/// Keys whose values differ, with the old and new value (`None` when unset)
+fn config_changes(
+    before: &BTreeMap<String, String>,
+    after: &BTreeMap<String, String>,
+) -> Vec<(String, Option<String>, Option<String>)> {
+    let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    keys.into_iter()
+        .filter(|key| before.get(*key) != after.get(*key))
+        .map(|key| {
+            (
+                key.clone(),
+                before.get(key).cloned(),
+                after.get(key).cloned(),
+            )
+        })
+        .collect()
+}
+
+/// Validate all configured custom prompt templates
+fn validate_custom_templates(config: &Config) -> Result<()> {
+    let mut checked = 0;
+    let mut invalid = 0;
+
+    for kind in TEMPLATE_KINDS {
+        let Some(template) = config.prompt_templates.get(kind) else {
+            continue;
+        };
+        checked += 1;
+
+        let result = Prompts::validate_template(kind, template);
+        if result.messages().is_empty() {
+            println!("  {} {}", "✓".green(), kind);
+            continue;
+        }
+
+        if result.is_valid() {
+            println!("  {} {}", "!".yellow(), kind);
+        } else {
+            invalid += 1;
+            println!("  {} {}", "✗".red(), kind);
+        }
+        for message in result.messages() {
+            println!("      {}", message.dimmed());
+        }
+    }
+
+    if checked == 0 {
+        println!("[daily] No custom templates configured (using built-in defaults)");
+        return Ok(());
+    }
+
+    if invalid > 0 {
+        anyhow::bail!("{} custom template(s) failed validation", invalid);
+    }
+
+    println!();
+    println!("[daily] {} custom template(s) validated", checked);
+    Ok(())
+}
+
 /// Interactive configuration
-async fn configure_interactive(config: &mut crate::config::Config) -> Result<()> {
+async fn configure_interactive(config: &mut Config) -> Result<()> {
     let theme = ColorfulTheme::default();
 
     println!("[daily] Interactive Configuration");
@@ -94,12 +344,17 @@ async fn configure_interactive(config: &mut crate::config::Config) -> Result<()>
     };
 
     // Language selection
-    let languages = vec!["en (English, default)", "zh (Chinese / 中文)"];
-    let current_lang_idx = if config.summarization.summary_language == "zh" {
-        1
-    } else {
-        0
-    };
+    let languages = vec![
+        "en (English, default)",
+        "zh (Chinese / 中文)",
+        "ja (Japanese / 日本語)",
+        "es (Spanish / Español)",
+        "fr (French / Français)",
+    ];
+    let current_lang_idx = SUPPORTED_LANGUAGES
+        .iter()
+        .position(|code| *code == config.summarization.summary_language)
+        .unwrap_or(0);
 
     let lang_selection = Select::with_theme(&theme)
         .with_prompt("Select summary language")
@@ -108,11 +363,7 @@ async fn configure_interactive(config: &mut crate::config::Config) -> Result<()>
         .interact()
         .context("Failed to select language")?;
 
-    config.summarization.summary_language = if lang_selection == 1 {
-        "zh".into()
-    } else {
-        "en".into()
-    };
+    config.summarization.summary_language = SUPPORTED_LANGUAGES[lang_selection].into();
 
     // Enable daily summary
     let enable_daily_summary = Confirm::with_theme(&theme)
@@ -181,11 +432,7 @@ async fn configure_interactive(config: &mut crate::config::Config) -> Result<()>
     println!(
         "  Summary language: {} ({})",
         config.summarization.summary_language,
-        if config.summarization.summary_language == "zh" {
-            "Chinese"
-        } else {
-            "English"
-        }
+        language_name(&config.summarization.summary_language).unwrap_or("unsupported")
     );
     println!(
         "  Daily summary: {}",
@@ -209,3 +456,83 @@ async fn configure_interactive(config: &mut crate::config::Config) -> Result<()>
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_changes() {
+        let current = Config::default();
+        let mut imported = Config::default();
+        imported.summarization.model = "sonnet".into();
+        imported.archive.author = Some("Ada".into());
+
+        let changes = config_changes(
+            &flatten_config(&current).unwrap(),
+            &flatten_config(&imported).unwrap(),
+        );
+
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    "archive.author".to_string(),
+                    None,
+                    Some("\"Ada\"".to_string())
+                ),
+                (
+                    "summarization.model".to_string(),
+                    Some("\"haiku\"".to_string()),
+                    Some("\"sonnet\"".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_settings() {
+        let config = Config::default();
+        let set = |assignments: &[&str]| {
+            let assignments: Vec<String> = assignments.iter().map(|s| s.to_string()).collect();
+            apply_settings(&config, &assignments)
+        };
+
+        let updated = set(&[
+            "summarization.model=sonnet",
+            "summarization.auto_summarize_on_show=true",
+            "archive.author=\"Jane\"",
+            "archive.max_diff_chars=100",
+            "summarization.sources=claude, codex",
+        ])
+        .unwrap();
+        assert_eq!(updated.summarization.model, "sonnet");
+        assert!(updated.summarization.auto_summarize_on_show);
+        assert_eq!(updated.archive.author.as_deref(), Some("Jane"));
+        assert_eq!(updated.archive.max_diff_chars, 100);
+        assert_eq!(updated.summarization.sources, vec!["claude", "codex"]);
+
+        let error = |assignment: &str| format!("{:#}", set(&[assignment]).unwrap_err());
+        assert!(error("summarization.modle=haiku").contains("Unknown setting"));
+        assert!(error("summarization.model").contains("Expected KEY=VALUE"));
+        assert!(error("hooks.enable_session_end=yes").contains("expected true or false"));
+        assert!(error("archive.max_diff_chars=-5").contains("archive.max_diff_chars"));
+        assert!(error("storage.layout=deep").contains("storage.layout"));
+    }
+
+    #[test]
+    fn test_exported_config_round_trips() {
+        let mut config = Config::default();
+        config.summarization.digest_time = "07:30".into();
+
+        let content = toml::to_string_pretty(&config).unwrap();
+        let imported: Config = toml::from_str(&content).unwrap();
+
+        assert_eq!(imported.summarization.digest_time, "07:30");
+        assert!(config_changes(
+            &flatten_config(&config).unwrap(),
+            &flatten_config(&imported).unwrap()
+        )
+        .is_empty());
+    }
+}
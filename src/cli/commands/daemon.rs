@@ -0,0 +1,64 @@
+use anyhow::Result;
+use chrono::Local;
+use colored::Colorize;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+use tracing::{info, warn};
+
+use crate::cli::commands::digest::{auto_digest_due, spawn_background_digest};
+use crate::config::load_config;
+
+/// How often the schedule is checked
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Run in the foreground, digesting yesterday's sessions once `digest_time` has passed
+pub async fn run() -> Result<()> {
+    let config = load_config()?;
+    println!(
+        "{} Daemon started; digests run after {} (Ctrl+C to stop)",
+        "✓".green(),
+        config.summarization.digest_time.cyan()
+    );
+
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    // After a sleep, one check is enough to catch up
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    // A failed digest is not retried every minute; it waits for the next date
+    let mut last_triggered: Option<String> = None;
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = tokio::signal::ctrl_c() => {
+                println!("Daemon stopped");
+                return Ok(());
+            }
+        }
+
+        // Reloaded each time so config changes apply without a restart
+        let config = match load_config() {
+            Ok(config) => config,
+            Err(e) => {
+                warn!("Failed to load config: {}", e);
+                continue;
+            }
+        };
+        if !config.summarization.auto_digest_enabled {
+            continue;
+        }
+
+        let Some(date) = auto_digest_due(&config, Local::now()) else {
+            continue;
+        };
+        if last_triggered.as_deref() == Some(date.as_str()) {
+            continue;
+        }
+
+        info!("Digest time passed, digesting {}", date);
+        match spawn_background_digest(&config, &date, false, false, None) {
+            Ok(job) => info!("Digest started for {} (job {})", date, job.id),
+            Err(e) => warn!("Failed to start digest for {}: {}", date, e),
+        }
+        last_triggered = Some(date);
+    }
+}
@@ -0,0 +1,42 @@
+use anyhow::Result;
+use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, Confirm};
+
+use crate::archive::ArchiveManager;
+use crate::config::load_config;
+
+/// Delete a date's archive after confirmation
+pub async fn run(date: String, yes: bool) -> Result<()> {
+    let config = load_config()?;
+    let manager = ArchiveManager::new(config);
+
+    if !yes {
+        let sessions = manager.list_sessions(&date).unwrap_or_default().len();
+        let confirmed = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Delete the archive for {} ({} sessions)? This cannot be undone",
+                date, sessions
+            ))
+            .default(false)
+            .interact()
+            .unwrap_or(false);
+        if !confirmed {
+            println!("{}", "Cancelled.".dimmed());
+            return Ok(());
+        }
+    }
+
+    let deleted = manager.delete_date(&date)?;
+    println!(
+        "{} Deleted archive for {}: {} session(s){}",
+        "✓".green(),
+        date,
+        deleted.sessions,
+        if deleted.had_digest {
+            " and its digest"
+        } else {
+            ""
+        }
+    );
+    Ok(())
+}
@@ -1,35 +1,94 @@
-use anyhow::{Context, Result};
-use chrono::{Duration, Local};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use tokio::task::JoinSet;
+
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
 
 use crate::archive::ArchiveManager;
-use crate::config::load_config;
+use crate::config::{load_config, Config};
+use crate::fs_util::write_atomic;
+use crate::jobs::{JobInfo, JobManager, JobType};
 use crate::summarizer::SummarizerEngine;
 
 /// Parse relative date string to actual date
-fn parse_relative_date(relative: &str) -> Option<String> {
+fn parse_relative_date(config: &Config, relative: &str) -> Option<String> {
     match relative.to_lowercase().as_str() {
-        "yest" | "yesterday" => {
-            let yesterday = Local::now() - Duration::days(1);
-            Some(yesterday.format("%Y-%m-%d").to_string())
-        }
-        "today" => Some(Local::now().format("%Y-%m-%d").to_string()),
+        "yest" | "yesterday" => Some(config.yesterday()),
+        "today" => Some(config.today()),
         _ => None,
     }
 }
 
 /// Run the digest command - generate daily summary from sessions
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     relative_date: Option<String>,
     date: Option<String>,
     background: bool,
     force: bool,
+    preview: bool,
+    dry_run: bool,
+    allow_today: bool,
+    job_id: Option<String>,
+    model: Option<String>,
 ) -> Result<()> {
-    let config = load_config()?;
+    let mut config = load_config()?;
+    config.override_model(model.clone());
+
+    let result = digest(
+        &config,
+        relative_date,
+        date,
+        background,
+        force,
+        preview,
+        dry_run,
+        allow_today,
+        job_id.clone(),
+        model,
+    )
+    .await;
+
+    // Update job status when running as a tracked background job, whichever way it ended
+    if let Some(id) = &job_id {
+        if let Ok(manager) = JobManager::new(&config) {
+            let update = match &result {
+                Ok(_) => manager.mark_completed(id),
+                Err(e) => manager.mark_failed(id, &e.to_string()),
+            };
+            if let Err(e) = update {
+                eprintln!("[daily] Warning: Failed to update job status: {}", e);
+            }
+            let _ = manager.truncate_log_if_needed(id);
+        }
+    }
+
+    result
+}
 
+/// The digest itself; `run` records how it ended on the job
+#[allow(clippy::too_many_arguments)]
+async fn digest(
+    config: &Config,
+    relative_date: Option<String>,
+    date: Option<String>,
+    background: bool,
+    force: bool,
+    preview: bool,
+    dry_run: bool,
+    allow_today: bool,
+    job_id: Option<String>,
+    model: Option<String>,
+) -> Result<()> {
     // Determine target date: relative_date takes precedence, then --date, then today
     let target_date = if let Some(rel) = relative_date {
-        parse_relative_date(&rel).unwrap_or_else(|| {
+        parse_relative_date(config, &rel).unwrap_or_else(|| {
             eprintln!(
                 "[daily] Unknown relative date '{}', using as literal date",
                 rel
@@ -37,9 +96,19 @@ pub async fn run(
             rel
         })
     } else {
-        date.unwrap_or_else(|| Local::now().format("%Y-%m-%d").to_string())
+        date.unwrap_or_else(|| config.today())
     };
 
+    // Preview and dry run write nothing, so they may look at the day so far
+    if target_date == config.today() && !allow_today && !preview && !dry_run {
+        bail!(
+            "{} is today. Digest is meant for finished days: sessions archived later today \
+             would be missing from daily.md. Run `daily digest yest` for yesterday, or pass \
+             --allow-today to digest the day so far (session files are kept)",
+            target_date
+        );
+    }
+
     let manager = ArchiveManager::new(config.clone());
 
     // Check if there are sessions to digest
@@ -66,6 +135,14 @@ pub async fn run(
         }
     }
 
+    if dry_run {
+        return run_dry_run(config, &target_date).await;
+    }
+
+    if preview {
+        return run_preview(config, &target_date).await;
+    }
+
     if background {
         // Background mode: spawn detached process
         eprintln!(
@@ -74,26 +151,351 @@ pub async fn run(
             sessions.len()
         );
 
-        let exe = std::env::current_exe().context("Failed to get current executable")?;
+        let job =
+            spawn_background_digest(config, &target_date, force, allow_today, model.as_deref())?;
 
-        let mut args = vec!["digest", "--date", &target_date];
-        if force {
-            args.push("--force");
+        eprintln!(
+            "[daily] Background digest started: {} (PID: {})",
+            job.id, job.pid
+        );
+        return Ok(());
+    }
+
+    run_digest(config, &target_date, &sessions, job_id).await
+}
+
+/// Set a digested daily.md aside so the date can be digested again from scratch
+pub async fn run_undigest(date: String) -> Result<()> {
+    let config = load_config()?;
+    let manager = ArchiveManager::new(config);
+
+    let backup = manager.undigest(&date)?;
+    let sessions = manager.list_sessions(&date)?;
+    eprintln!(
+        "[daily] Moved daily.md for {} to {}",
+        date,
+        backup.display()
+    );
+    eprintln!(
+        "[daily] {} session files kept; run `daily digest --date {}` to digest again",
+        sessions.len(),
+        date
+    );
+    Ok(())
+}
+
+/// Progress of a range digest, so an interrupted run can resume
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct DigestProgress {
+    /// First date of the range ("" for `--all` from the beginning)
+    from: String,
+    /// Last date of the range
+    to: String,
+    /// `to` was not given and resolved to the day the range started
+    #[serde(default)]
+    open_ended: bool,
+    /// Dates in the range that have been digested
+    completed: Vec<String>,
+}
+
+impl DigestProgress {
+    /// A fresh manifest; without `to` the range ends `today`
+    fn new(from: &str, to: Option<&str>, today: &str) -> Self {
+        Self {
+            from: from.to_string(),
+            to: to.unwrap_or(today).to_string(),
+            open_ended: to.is_none(),
+            completed: Vec::new(),
         }
+    }
 
-        Command::new(&exe)
-            .args(&args)
-            .stdin(Stdio::null())
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .spawn()
-            .context("Failed to spawn background digest process")?;
+    /// Load the manifest for this range; a manifest for another range is discarded.
+    ///
+    /// An open-ended range (e.g. `--all`) resumes with the end date it first resolved to,
+    /// even on a later day.
+    fn load(path: &Path, from: &str, to: Option<&str>, today: &str) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Self>(&content).ok())
+            .filter(|progress| {
+                progress.from == from
+                    && match to {
+                        Some(to) => !progress.open_ended && progress.to == to,
+                        None => progress.open_ended,
+                    }
+            })
+            .unwrap_or_else(|| Self::new(from, to, today))
+    }
 
-        eprintln!("[daily] Background digest started");
-        return Ok(());
+    fn save(&self, path: &Path) -> Result<()> {
+        write_atomic(path, serde_json::to_string_pretty(self)?)
+            .context("Failed to save digest progress")
+    }
+}
+
+/// Path of the range digest manifest
+fn progress_path(config: &Config) -> PathBuf {
+    config.storage_path().join(".digest-progress.json")
+}
+
+/// Digest every date with sessions in a range, skipping dates an earlier run completed
+pub async fn run_range(
+    from: Option<String>,
+    to: Option<String>,
+    force: bool,
+    model: Option<String>,
+    concurrency: usize,
+    allow_today: bool,
+) -> Result<()> {
+    for date in [&from, &to].into_iter().flatten() {
+        if NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+            bail!("Invalid date format: {}. Use yyyy-mm-dd", date);
+        }
+    }
+
+    let mut config = load_config()?;
+    config.override_model(model);
+    let from = from.unwrap_or_default();
+    let today = config.today();
+    let manager = ArchiveManager::new(config.clone());
+
+    let manifest_path = progress_path(&config);
+    let mut progress = if force {
+        DigestProgress::new(&from, to.as_deref(), &today)
+    } else {
+        DigestProgress::load(&manifest_path, &from, to.as_deref(), &today)
+    };
+    let to = progress.to.clone();
+
+    let mut dates = manager.list_dates()?;
+    dates.retain(|d| d.as_str() >= from.as_str() && d.as_str() <= to.as_str());
+    dates.reverse(); // Oldest first
+
+    if !allow_today && dates.contains(&today) {
+        eprintln!(
+            "[daily] Skipping {} (today is still in progress; pass --allow-today to include it)",
+            today
+        );
+        dates.retain(|d| *d != today);
+    }
+
+    let mut pending = Vec::new();
+    for date in dates {
+        if progress.completed.contains(&date) {
+            eprintln!("[daily] Skipping {} (already digested in this range)", date);
+            continue;
+        }
+        let sessions = manager.list_sessions(&date)?;
+        if !sessions.is_empty() {
+            pending.push((date, sessions));
+        }
+    }
+
+    // Each date is an independent claude round-trip; a failure only marks its own row
+    let mut results = Vec::new();
+    let mut tasks = JoinSet::new();
+    let mut pending = pending.into_iter();
+    loop {
+        while tasks.len() < concurrency.max(1) {
+            let Some((date, sessions)) = pending.next() else {
+                break;
+            };
+            let config = config.clone();
+            tasks.spawn(async move {
+                let result = run_digest(&config, &date, &sessions, None).await;
+                (date, sessions.len(), result)
+            });
+        }
+        let Some(joined) = tasks.join_next().await else {
+            break;
+        };
+        let (date, sessions, result) = joined.context("Digest task panicked")?;
+        if result.is_ok() {
+            progress.completed.push(date.clone());
+            progress.save(&manifest_path)?;
+        }
+        results.push(RangeResult {
+            date,
+            sessions,
+            error: result.err().map(|e| e.to_string()),
+        });
+    }
+
+    if !results.is_empty() {
+        results.sort_by(|a, b| a.date.cmp(&b.date));
+        print_range_results(&results);
     }
 
-    // Foreground mode: perform the digest
+    let failed: Vec<&str> = results
+        .iter()
+        .filter(|r| r.error.is_some())
+        .map(|r| r.date.as_str())
+        .collect();
+    if !failed.is_empty() {
+        bail!(
+            "Digest failed for {} date(s): {}. Re-run to resume",
+            failed.len(),
+            failed.join(", ")
+        );
+    }
+
+    // Range fully done: the next run starts from scratch
+    if manifest_path.exists() {
+        fs::remove_file(&manifest_path).context("Failed to remove digest progress")?;
+    }
+    eprintln!(
+        "[daily] Range digest complete ({} dates)",
+        progress.completed.len()
+    );
+    Ok(())
+}
+
+/// Outcome of digesting one date of a range
+struct RangeResult {
+    date: String,
+    sessions: usize,
+    error: Option<String>,
+}
+
+/// Print one row per digested date, then the totals
+fn print_range_results(results: &[RangeResult]) {
+    println!();
+    println!("  {:<12} {:>8}  Result", "Date", "Sessions");
+    for result in results {
+        let status = match &result.error {
+            None => "✓ digested".green(),
+            Some(e) => format!("✗ {}", e).red(),
+        };
+        println!("  {:<12} {:>8}  {}", result.date, result.sessions, status);
+    }
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    println!();
+    println!("  {} succeeded, {} failed", results.len() - failed, failed);
+}
+
+/// Date due for the scheduled digest at `now`, if any.
+///
+/// Yesterday is due once `summarization.digest_time` has passed and it has sessions
+/// but no digest (and no digest job running). Checking "passed and not yet digested"
+/// rather than the exact minute catches up after the machine was asleep.
+pub fn auto_digest_due(config: &Config, now: DateTime<Local>) -> Option<String> {
+    let (hour, minute) = config.summarization.digest_time.split_once(':')?;
+    let digest_time = NaiveTime::from_hms_opt(hour.parse().ok()?, minute.parse().ok()?, 0)?;
+    if now.time() < digest_time {
+        return None;
+    }
+
+    let yesterday = (config.effective_date(now) - Duration::days(1))
+        .format("%Y-%m-%d")
+        .to_string();
+    let manager = ArchiveManager::new(config.clone());
+    if !manager.has_sessions(&yesterday) || manager.has_digest(&yesterday) {
+        return None;
+    }
+
+    let running = JobManager::new(config)
+        .and_then(|jobs| jobs.running_digest(&yesterday))
+        .ok()
+        .flatten();
+    running.is_none().then_some(yesterday)
+}
+
+/// Spawn a detached digest process for a date and register it as a job.
+///
+/// The child refuses to digest today unless `allow_today` is set.
+pub fn spawn_background_digest(
+    config: &Config,
+    date: &str,
+    force: bool,
+    allow_today: bool,
+    model: Option<&str>,
+) -> Result<JobInfo> {
+    let job_manager = JobManager::new(config)?;
+    let task_name = format!("digest-{}", date);
+    let job_id = JobManager::generate_job_id(&task_name);
+
+    // Redirect output to the job log
+    let (stdout_file, stderr_file) = match job_manager.create_log_file(&job_id) {
+        Ok(f) => {
+            let f2 = f.try_clone().unwrap_or_else(|_| {
+                std::fs::File::create("/dev/null").expect("Failed to open /dev/null")
+            });
+            (Stdio::from(f), Stdio::from(f2))
+        }
+        Err(_) => (Stdio::null(), Stdio::null()),
+    };
+
+    let exe = std::env::current_exe().context("Failed to get current executable")?;
+
+    let mut args = vec!["digest", "--date", date, "--job-id", &job_id];
+    if force {
+        args.push("--force");
+    }
+    if allow_today {
+        args.push("--allow-today");
+    }
+    if let Some(model) = model {
+        args.extend(["--model", model]);
+    }
+
+    let mut cmd = Command::new(&exe);
+    cmd.args(&args)
+        .stdin(Stdio::null())
+        .stdout(stdout_file)
+        .stderr(stderr_file);
+
+    // Create a new process group so it outlives the parent
+    #[cfg(unix)]
+    cmd.process_group(0);
+
+    let child = cmd
+        .spawn()
+        .context("Failed to spawn background digest process")?;
+
+    let manager = ArchiveManager::new(config.clone());
+    job_manager.register(
+        &job_id,
+        child.id(),
+        &task_name,
+        &manager.daily_summary_path(date),
+        JobType::Digest,
+    )
+}
+
+/// Print the daily summary prompt without calling Claude or writing anything
+async fn run_dry_run(config: &Config, target_date: &str) -> Result<()> {
+    let engine = SummarizerEngine::new(config.clone());
+    match engine.daily_summary_prompt(target_date).await? {
+        Some(prompt) => println!("{}", prompt),
+        None => eprintln!("[daily] Nothing to digest for {}", target_date),
+    }
+    Ok(())
+}
+
+/// Generate the daily summary into a preview file, leaving daily.md untouched
+async fn run_preview(config: &Config, target_date: &str) -> Result<()> {
+    eprintln!("[daily] Generating digest preview for {}...", target_date);
+
+    let engine = SummarizerEngine::new(config.clone());
+    let summary = engine.update_daily_summary(target_date).await?;
+    let content = summary.to_markdown();
+
+    let manager = ArchiveManager::new(config.clone());
+    let preview_path = manager.write_daily_preview(target_date, &content)?;
+
+    println!("{}", content);
+    eprintln!("[daily] Preview saved: {}", preview_path.display());
+    eprintln!("[daily] daily.md was not modified; run 'daily digest' to apply");
+    Ok(())
+}
+
+/// Perform the digest in the foreground
+async fn run_digest(
+    config: &Config,
+    target_date: &str,
+    sessions: &[String],
+    job_id: Option<String>,
+) -> Result<()> {
     if !sessions.is_empty() {
         eprintln!(
             "[daily] Digesting {} sessions for {}...",
@@ -102,12 +504,12 @@ pub async fn run(
         );
     }
 
-    let engine = SummarizerEngine::new(config.clone());
+    let engine = SummarizerEngine::new(config.clone()).with_job_id(job_id);
 
     // Generate daily summary from all sessions (or regenerate if force mode)
-    match engine.update_daily_summary(&target_date).await {
+    match engine.update_daily_summary(target_date).await {
         Ok(summary) => {
-            let summary_path = summary.save(&config)?;
+            let summary_path = summary.save(config)?;
             eprintln!("[daily] Daily summary created: {}", summary_path.display());
             // Session files are preserved for reference
         }
@@ -123,3 +525,79 @@ pub async fn run(
     eprintln!("[daily] Digest complete!");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_auto_digest_due() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+        config.summarization.digest_time = "06:00".to_string();
+        let manager = ArchiveManager::new(config.clone());
+        let at = |h, m| Local.with_ymd_and_hms(2026, 1, 17, h, m, 0).unwrap();
+
+        assert_eq!(auto_digest_due(&config, at(7, 0)), None);
+
+        manager
+            .write_session("2026-01-16", "proj", "10_00-fix", "## Summary\n")
+            .unwrap();
+        assert_eq!(auto_digest_due(&config, at(5, 59)), None);
+        assert_eq!(
+            auto_digest_due(&config, at(6, 0)).as_deref(),
+            Some("2026-01-16")
+        );
+        // Still due hours later, e.g. after the machine slept through 06:00
+        assert_eq!(
+            auto_digest_due(&config, at(23, 30)).as_deref(),
+            Some("2026-01-16")
+        );
+
+        manager
+            .write_daily_summary("2026-01-16", "## Overview\n\nDone.\n")
+            .unwrap();
+        assert_eq!(auto_digest_due(&config, at(7, 0)), None);
+    }
+
+    #[test]
+    fn test_digest_progress_resumes_same_range_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(".digest-progress.json");
+
+        let today = "2026-03-01";
+        let mut progress = DigestProgress::load(&path, "2026-01-01", Some("2026-01-31"), today);
+        assert!(progress.completed.is_empty());
+        progress.completed.push("2026-01-02".to_string());
+        progress.save(&path).unwrap();
+
+        let resumed = DigestProgress::load(&path, "2026-01-01", Some("2026-01-31"), today);
+        assert_eq!(resumed.completed, vec!["2026-01-02"]);
+
+        let other = DigestProgress::load(&path, "2026-02-01", Some("2026-02-28"), today);
+        assert!(other.completed.is_empty());
+    }
+
+    #[test]
+    fn test_digest_progress_resumes_all_on_a_later_day() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(".digest-progress.json");
+
+        let mut progress = DigestProgress::load(&path, "", None, "2026-03-01");
+        assert_eq!(progress.to, "2026-03-01");
+        progress.completed.push("2026-01-02".to_string());
+        progress.save(&path).unwrap();
+
+        // `--all` again the next day picks up the same range
+        let resumed = DigestProgress::load(&path, "", None, "2026-03-02");
+        assert_eq!(resumed.to, "2026-03-01");
+        assert_eq!(resumed.completed, vec!["2026-01-02"]);
+
+        // An explicit end date is a different range, even the same day
+        let explicit = DigestProgress::load(&path, "", Some("2026-03-01"), "2026-03-02");
+        assert!(explicit.completed.is_empty());
+    }
+}
@@ -0,0 +1,285 @@
+use anyhow::Result;
+use colored::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::status::hooks_installed;
+use crate::config::{get_config_path, Config};
+
+/// Free space below which the storage disk is reported as nearly full
+const LOW_DISK_BYTES: u64 = 500 * 1024 * 1024;
+
+/// Outcome of a single doctor check
+struct Check {
+    label: &'static str,
+    ok: bool,
+    /// Whether a failure makes `daily doctor` exit non-zero
+    critical: bool,
+    detail: String,
+    hint: Option<String>,
+}
+
+impl Check {
+    fn pass(label: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            label,
+            ok: true,
+            critical: false,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn fail(
+        label: &'static str,
+        critical: bool,
+        detail: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        Self {
+            label,
+            ok: false,
+            critical,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// Check the environment daily depends on; fails if any critical check fails
+pub async fn run() -> Result<()> {
+    println!("{}", "Daily Doctor".cyan().bold());
+    println!("{}", "=".repeat(50));
+    println!();
+
+    let (config, config_check) = check_config();
+    let checks = [
+        config_check,
+        check_claude(&config),
+        check_storage(&config.storage_path()),
+        check_hooks(),
+        check_disk_space(&config.storage_path()),
+    ];
+
+    for check in &checks {
+        let marker = match (check.ok, check.critical) {
+            (true, _) => "✓".green(),
+            (false, true) => "✗".red(),
+            (false, false) => "✗".yellow(),
+        };
+        println!("  {} {:<12} {}", marker, check.label, check.detail);
+        if let Some(hint) = &check.hint {
+            println!("  {:<14} {}", "", format!("→ {}", hint).dimmed());
+        }
+    }
+    println!();
+
+    let critical = checks.iter().filter(|c| !c.ok && c.critical).count();
+    if critical > 0 {
+        anyhow::bail!("{} critical check(s) failed", critical);
+    }
+    println!("{}", "All critical checks passed.".green());
+    Ok(())
+}
+
+/// Parse and validate the config file, falling back to defaults so later checks can run
+fn check_config() -> (Config, Check) {
+    const LABEL: &str = "Config";
+
+    let path = match get_config_path() {
+        Ok(path) => path,
+        Err(e) => {
+            let check = Check::fail(LABEL, true, e.to_string(), "check your home directory");
+            return (Config::default(), check);
+        }
+    };
+    if !path.exists() {
+        let check = Check::pass(
+            LABEL,
+            format!("not found, using defaults ({})", path.display()),
+        );
+        return (Config::default(), check);
+    }
+
+    match parse_config(&path) {
+        Ok(config) => {
+            let check = Check::pass(LABEL, format!("valid ({})", path.display()));
+            (config, check)
+        }
+        Err(e) => {
+            let check = Check::fail(
+                LABEL,
+                true,
+                format!("{:#}", e),
+                format!("fix or remove {}", path.display()),
+            );
+            (Config::default(), check)
+        }
+    }
+}
+
+fn parse_config(path: &Path) -> Result<Config> {
+    let content = fs::read_to_string(path)?;
+    let config: Config = toml::from_str(&content)?;
+    config.validate()?;
+    Ok(config)
+}
+
+/// The claude CLI is only required by the "cli" backend
+fn check_claude(config: &Config) -> Check {
+    const LABEL: &str = "Claude CLI";
+
+    let program = config
+        .summarization
+        .claude_binary_path
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("claude"));
+    let critical = config.summarization.backend != "api";
+
+    match Command::new(&program).arg("--version").output() {
+        Ok(output) if output.status.success() => Check::pass(
+            LABEL,
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ),
+        Ok(output) => Check::fail(
+            LABEL,
+            critical,
+            format!(
+                "{} --version exited with {}",
+                program.display(),
+                output.status
+            ),
+            "reinstall Claude Code",
+        ),
+        Err(_) => Check::fail(
+            LABEL,
+            critical,
+            format!("{} not found", program.display()),
+            "install Claude Code or set summarization.claude_binary_path",
+        ),
+    }
+}
+
+/// Storage must exist and accept new files
+fn check_storage(storage: &Path) -> Check {
+    const LABEL: &str = "Storage";
+
+    if !storage.is_dir() {
+        return Check::fail(
+            LABEL,
+            true,
+            format!("{} does not exist", storage.display()),
+            "run 'daily init'",
+        );
+    }
+
+    let probe = storage.join(format!(".doctor-{}.tmp", std::process::id()));
+    match fs::write(&probe, b"") {
+        Ok(()) => {
+            let _ = fs::remove_file(&probe);
+            Check::pass(LABEL, format!("{} (writable)", storage.display()))
+        }
+        Err(e) => Check::fail(
+            LABEL,
+            true,
+            format!("{} is not writable: {}", storage.display(), e),
+            "fix its permissions or point storage.path elsewhere",
+        ),
+    }
+}
+
+/// Without hooks sessions are not archived, but manual commands still work
+fn check_hooks() -> Check {
+    const LABEL: &str = "Hooks";
+
+    let settings = dirs::home_dir().map(|home| home.join(".claude").join("settings.json"));
+    match settings {
+        Some(path) if hooks_installed(&path) => {
+            Check::pass(LABEL, format!("installed ({})", path.display()))
+        }
+        Some(path) => Check::fail(
+            LABEL,
+            false,
+            format!("not installed in {}", path.display()),
+            "run 'daily install'",
+        ),
+        None => Check::fail(
+            LABEL,
+            false,
+            "home directory not found",
+            "set HOME and run 'daily install'",
+        ),
+    }
+}
+
+fn check_disk_space(storage: &Path) -> Check {
+    const LABEL: &str = "Disk space";
+
+    // Measure the nearest existing ancestor so a missing storage dir still reports
+    let existing = storage.ancestors().find(|p| p.exists()).unwrap_or(storage);
+    match free_bytes(existing) {
+        Some(free) if free < LOW_DISK_BYTES => Check::fail(
+            LABEL,
+            false,
+            format!("{} free", format_bytes(free)),
+            "free up space or run 'daily archive' to compress old dates",
+        ),
+        Some(free) => Check::pass(LABEL, format!("{} free", format_bytes(free))),
+        None => Check::pass(LABEL, "unknown on this platform"),
+    }
+}
+
+#[cfg(unix)]
+fn free_bytes(path: &Path) -> Option<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    #[allow(clippy::unnecessary_cast)]
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+fn free_bytes(_path: &Path) -> Option<u64> {
+    None
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const GB: f64 = 1024.0 * 1024.0 * 1024.0;
+    const MB: f64 = 1024.0 * 1024.0;
+    let bytes = bytes as f64;
+    if bytes >= GB {
+        format!("{:.1} GB", bytes / GB)
+    } else {
+        format!("{:.0} MB", bytes / MB)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_check_storage() {
+        let temp_dir = TempDir::new().unwrap();
+        let check = check_storage(temp_dir.path());
+        assert!(check.ok);
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+
+        let missing = check_storage(&temp_dir.path().join("missing"));
+        assert!(!missing.ok);
+        assert!(missing.critical);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(300 * 1024 * 1024), "300 MB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024 / 2), "1.5 GB");
+    }
+}
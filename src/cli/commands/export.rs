@@ -0,0 +1,169 @@
+use anyhow::{bail, Context, Result};
+use pulldown_cmark::{html, Event, Options, Parser};
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::archive::{frontmatter, ArchiveManager};
+use crate::config::load_config;
+use crate::server::dto::{DailySummaryDto, SessionDetailDto};
+use crate::server::handlers::{parse_daily_summary, session_detail};
+
+/// Minimal stylesheet inlined into HTML exports
+const EXPORT_CSS: &str = "body{max-width:820px;margin:2rem auto;padding:0 1rem;\
+font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',sans-serif;line-height:1.6;color:#24292f}\
+h1,h2,h3{line-height:1.25}h1{border-bottom:1px solid #d0d7de;padding-bottom:.3rem}\
+code{background:#f6f8fa;padding:.15rem .3rem;border-radius:4px;font-size:90%}\
+pre{background:#f6f8fa;padding:1rem;overflow-x:auto;border-radius:6px}pre code{padding:0}\
+table{border-collapse:collapse}th,td{border:1px solid #d0d7de;padding:.3rem .6rem}\
+blockquote{margin:0;padding:0 1rem;color:#57606a;border-left:.25rem solid #d0d7de}\
+article{margin-bottom:3rem}hr{border:0;border-top:1px solid #d0d7de;margin:2rem 0}";
+
+/// Structured export of a date
+#[derive(Serialize)]
+struct DateExport {
+    date: String,
+    daily_summary: Option<DailySummaryDto>,
+    sessions: Vec<SessionDetailDto>,
+}
+
+/// Export a date's daily summary and sessions to one file
+pub async fn run(date: Option<String>, format: String, output: Option<PathBuf>) -> Result<()> {
+    let config = load_config()?;
+    let date = date.unwrap_or_else(|| config.today());
+    let manager = ArchiveManager::new(config);
+
+    let summary = manager.read_daily_summary(&date).ok();
+    let mut sessions = Vec::new();
+    for name in manager.list_sessions(&date)? {
+        let content = manager.read_session(&date, &name)?;
+        sessions.push((name, content));
+    }
+
+    if summary.is_none() && sessions.is_empty() {
+        bail!("No archives found for {}", date);
+    }
+
+    let (content, extension) = match format.as_str() {
+        "html" => (render_html(&date, summary.as_deref(), &sessions), "html"),
+        "json" => (render_json(&manager, &date, summary, sessions)?, "json"),
+        _ => (render_markdown(summary.as_deref(), &sessions), "md"),
+    };
+
+    let output = output.unwrap_or_else(|| PathBuf::from(format!("daily-{}.{}", date, extension)));
+    fs::write(&output, content)
+        .with_context(|| format!("Failed to write export: {}", output.display()))?;
+
+    eprintln!("[daily] Exported {} to {}", date, output.display());
+    Ok(())
+}
+
+/// Raw files joined with horizontal rules
+fn render_markdown(summary: Option<&str>, sessions: &[(String, String)]) -> String {
+    summary
+        .into_iter()
+        .chain(sessions.iter().map(|(_, content)| content.as_str()))
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join("\n\n---\n\n")
+        + "\n"
+}
+
+/// A self-contained HTML page with each archive as an article
+fn render_html(date: &str, summary: Option<&str>, sessions: &[(String, String)]) -> String {
+    let articles: String = summary
+        .into_iter()
+        .chain(sessions.iter().map(|(_, content)| content.as_str()))
+        .map(|content| format!("<article>\n{}</article>\n", markdown_to_html(content)))
+        .collect::<Vec<_>>()
+        .join("<hr>\n");
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Daily Archive - {}</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        date, EXPORT_CSS, articles
+    )
+}
+
+/// Render a markdown archive to HTML, leaving out its frontmatter.
+///
+/// Raw HTML is shown as text: summaries quote transcripts, which may hold `<script>`.
+fn markdown_to_html(content: &str) -> String {
+    let body = frontmatter::split(content).map_or(content, |(_, body)| body);
+    let events = Parser::new_ext(body, Options::all()).map(|event| match event {
+        Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+        event => event,
+    });
+    let mut out = String::new();
+    html::push_html(&mut out, events);
+    out
+}
+
+fn render_json(
+    manager: &ArchiveManager,
+    date: &str,
+    summary: Option<String>,
+    sessions: Vec<(String, String)>,
+) -> Result<String> {
+    let daily_summary = summary.map(|content| {
+        let mut dto = parse_daily_summary(date, &content);
+        dto.file_path = manager
+            .daily_summary_path(date)
+            .to_string_lossy()
+            .to_string();
+        dto
+    });
+    let sessions = sessions
+        .into_iter()
+        .map(|(name, content)| session_detail(manager, date, name, content))
+        .collect();
+
+    let export = DateExport {
+        date: date.to_string(),
+        daily_summary,
+        sessions,
+    };
+    Ok(serde_json::to_string_pretty(&export)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_html_skips_frontmatter() {
+        let sessions = vec![(
+            "10_00-fix".to_string(),
+            "---\ntitle: fix\n---\n\n# Fix auth\n\n- **done**\n".to_string(),
+        )];
+        let html = render_html("2026-01-16", None, &sessions);
+
+        assert!(html.contains("<style>"));
+        assert!(html.contains("<h1>Fix auth</h1>"));
+        assert!(html.contains("<strong>done</strong>"));
+        assert!(!html.contains("title: fix"));
+    }
+
+    #[test]
+    fn test_render_html_escapes_raw_html() {
+        let sessions = vec![(
+            "10_00-xss".to_string(),
+            "<script>alert(1)</script>\n\nSee <img src=x onerror=alert(1)> here\n".to_string(),
+        )];
+        let html = render_html("2026-01-16", None, &sessions);
+
+        assert!(!html.contains("<script>alert"));
+        assert!(!html.contains("<img"));
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+    }
+
+    #[test]
+    fn test_render_markdown_separates_files() {
+        let sessions = vec![
+            ("a".to_string(), "# A\n".to_string()),
+            ("b".to_string(), "# B\n".to_string()),
+        ];
+        let markdown = render_markdown(Some("# Daily\n"), &sessions);
+
+        assert_eq!(markdown, "# Daily\n\n---\n\n# A\n\n---\n\n# B\n");
+    }
+}
@@ -6,6 +6,14 @@ use crate::archive::ArchiveManager;
 use crate::config::load_config;
 use crate::summarizer::SummarizerEngine;
 
+/// Exit code when the model judges a session not worth extracting
+pub const NOT_EXTRACTABLE_EXIT_CODE: i32 = 3;
+
+/// `--output -` prints the generated markdown instead of writing files
+fn is_stdout(output: &Option<PathBuf>) -> bool {
+    output.as_deref() == Some(std::path::Path::new("-"))
+}
+
 /// Extract skill from archive
 pub async fn run_skill(
     date: Option<String>,
@@ -17,33 +25,45 @@ pub async fn run_skill(
     let engine = SummarizerEngine::new(config.clone());
 
     // Determine date
-    let view_date = date.unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d").to_string());
+    let view_date = date.unwrap_or_else(|| config.today());
 
     // Get session content
     let session_content = get_session_content(&manager, &view_date, session.as_deref()).await?;
 
-    println!("[daily] Extracting skill from session...");
-    println!("[daily] Applying quality gate (踩过坑? 下次还会遇到? 能说清楚?)...");
+    let zh = config.summarization.summary_language == "zh";
+
+    eprintln!("[daily] Extracting skill from session...");
+    if zh {
+        eprintln!("[daily] Applying quality gate (踩过坑? 下次还会遇到? 能说清楚?)...");
+    } else {
+        eprintln!("[daily] Applying quality gate (pitfall? recurring? explainable?)...");
+    }
 
     // Extract skill using Claude
     let skill_content = engine.extract_skill(&session_content, None).await?;
 
     // Check if skill is extractable
-    if skill_content.trim().starts_with("NOT_EXTRACTABLE:") {
-        let reason = skill_content
-            .trim()
-            .strip_prefix("NOT_EXTRACTABLE:")
-            .unwrap_or("Unknown reason")
-            .trim();
-        println!();
-        println!("[daily] ⚠ Skill not extractable: {}", reason);
-        println!();
-        println!("This session may not contain reusable knowledge that meets the quality bar:");
-        println!("  - 踩过坑吗？ Was there debugging or non-obvious discovery?");
-        println!("  - 下次还会遇到吗？ Is this a recurring problem?");
-        println!("  - 能说清楚吗？ Can it be clearly described?");
-        println!();
-        println!("Try a different session with `daily extract-skill --session <name>`");
+    if let Some(reason) = not_extractable_reason(&skill_content) {
+        eprintln!();
+        eprintln!("[daily] ⚠ Skill not extractable: {}", reason);
+        eprintln!();
+        eprintln!("This session may not contain reusable knowledge that meets the quality bar:");
+        if zh {
+            eprintln!("  - 踩过坑吗？ Was there debugging or non-obvious discovery?");
+            eprintln!("  - 下次还会遇到吗？ Is this a recurring problem?");
+            eprintln!("  - 能说清楚吗？ Can it be clearly described?");
+        } else {
+            eprintln!("  - Was there debugging or non-obvious discovery?");
+            eprintln!("  - Is this a recurring problem?");
+            eprintln!("  - Can it be clearly described?");
+        }
+        eprintln!();
+        eprintln!("Try a different session with `daily extract-skill --session <name>`");
+        std::process::exit(NOT_EXTRACTABLE_EXIT_CODE);
+    }
+
+    if is_stdout(&output) {
+        print!("{}", skill_content);
         return Ok(());
     }
 
@@ -124,16 +144,26 @@ pub async fn run_command(
     let engine = SummarizerEngine::new(config.clone());
 
     // Determine date
-    let view_date = date.unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d").to_string());
+    let view_date = date.unwrap_or_else(|| config.today());
 
     // Get session content
     let session_content = get_session_content(&manager, &view_date, session.as_deref()).await?;
 
-    println!("[daily] Extracting command from session...");
+    eprintln!("[daily] Extracting command from session...");
 
     // Extract command using Claude
     let command_content = engine.extract_command(&session_content, None).await?;
 
+    if let Some(reason) = not_extractable_reason(&command_content) {
+        eprintln!("[daily] ⚠ Command not extractable: {}", reason);
+        std::process::exit(NOT_EXTRACTABLE_EXIT_CODE);
+    }
+
+    if is_stdout(&output) {
+        print!("{}", command_content);
+        return Ok(());
+    }
+
     // Determine output path
     let output_path = if let Some(path) = output {
         path
@@ -207,6 +237,16 @@ async fn get_session_content(
     }
 }
 
+/// Reason given in a `NOT_EXTRACTABLE: <reason>` response, if the model declined
+fn not_extractable_reason(content: &str) -> Option<&str> {
+    let reason = content.trim().strip_prefix("NOT_EXTRACTABLE:")?.trim();
+    Some(if reason.is_empty() {
+        "Unknown reason"
+    } else {
+        reason
+    })
+}
+
 /// Extract name from YAML frontmatter
 fn extract_name_from_yaml(content: &str, default: &str) -> String {
     // Look for name: in frontmatter
@@ -255,6 +295,22 @@ description: test
         assert_eq!(extract_name_from_yaml(content, "default"), "my-skill");
     }
 
+    #[test]
+    fn test_not_extractable_reason() {
+        assert_eq!(
+            not_extractable_reason("NOT_EXTRACTABLE: routine refactor\n"),
+            Some("routine refactor")
+        );
+        assert_eq!(
+            not_extractable_reason("NOT_EXTRACTABLE:"),
+            Some("Unknown reason")
+        );
+        assert_eq!(not_extractable_reason("---\nname: skill\n---\n"), None);
+        assert!(is_stdout(&Some(PathBuf::from("-"))));
+        assert!(!is_stdout(&Some(PathBuf::from("./skill"))));
+        assert!(!is_stdout(&None));
+    }
+
     #[test]
     fn test_extract_name_from_content() {
         let content = r#"---
@@ -0,0 +1,240 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local};
+use colored::Colorize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::auto_summarize::{decode_project_dir, get_archived_transcript_paths};
+use crate::cli::commands::summarize::{spawn_background_summarize, summarize_args};
+use crate::config::{load_config, Config};
+use crate::jobs::{JobManager, JobStatus, JobType};
+use crate::transcript::source::{collect_jsonl, parse_transcript};
+
+/// A transcript to summarize, and where it goes
+#[derive(Debug, PartialEq)]
+struct PlannedImport {
+    transcript: PathBuf,
+    date: String,
+    cwd: Option<PathBuf>,
+}
+
+/// Summarize every transcript under `dir` as a background job.
+///
+/// Unlike auto-summarize there is no recency or activity check, so old transcripts can
+/// be backfilled. Transcripts already archived or queued are skipped, so re-running is safe.
+pub async fn run(dir: PathBuf, date: Option<String>, dry_run: bool) -> Result<()> {
+    if let Some(date) = &date {
+        chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .context(format!("Invalid date format: {}. Use yyyy-mm-dd", date))?;
+    }
+    if !dir.is_dir() {
+        bail!("Not a directory: {}", dir.display());
+    }
+
+    let config = load_config()?;
+    let (planned, skipped) = plan_imports(&config, &dir, date.as_deref())?;
+
+    if planned.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "Nothing to import from {} ({} already archived or empty).",
+                dir.display(),
+                skipped
+            )
+            .dimmed()
+        );
+        return Ok(());
+    }
+
+    if dry_run {
+        for import in &planned {
+            println!("{} → {}", import.transcript.display(), import.date);
+        }
+        println!(
+            "\n{}",
+            format!(
+                "Dry run: {} transcript(s) would be imported, {} skipped.",
+                planned.len(),
+                skipped
+            )
+            .dimmed()
+        );
+        return Ok(());
+    }
+
+    let job_manager = JobManager::new(&config)?;
+    let mut queued = 0;
+    for import in &planned {
+        let session_id = import
+            .transcript
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+        let task_name = format!("import-{}", session_id);
+        let cwd = import
+            .cwd
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|| ".".to_string());
+
+        let mut args = summarize_args(&import.transcript, &task_name, &cwd);
+        args.extend(["--date".to_string(), import.date.clone()]);
+        match spawn_background_summarize(
+            &config,
+            &job_manager,
+            &task_name,
+            &import.transcript,
+            args,
+            JobType::Manual,
+        ) {
+            Ok(job) => {
+                let state = if job.status == JobStatus::Queued {
+                    "queued"
+                } else {
+                    "started"
+                };
+                println!(
+                    "{} {} → {} ({})",
+                    "✓".green(),
+                    import.transcript.display(),
+                    import.date,
+                    state
+                );
+                queued += 1;
+            }
+            Err(e) => eprintln!("{} {}: {}", "✗".red(), import.transcript.display(), e),
+        }
+    }
+
+    println!(
+        "\nImporting {} transcript(s), {} skipped. Follow progress with `daily jobs`.",
+        queued, skipped
+    );
+    Ok(())
+}
+
+/// Transcripts under `dir` that are not archived or being summarized yet and have
+/// content, plus how many were skipped
+fn plan_imports(
+    config: &Config,
+    dir: &Path,
+    date: Option<&str>,
+) -> Result<(Vec<PlannedImport>, usize)> {
+    // Paths are compared canonically, so `./projects` matches the archived absolute path
+    let dir = fs::canonicalize(dir).context(format!("Failed to resolve {}", dir.display()))?;
+    let canonical = |path: &Path| fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+
+    let mut transcripts = Vec::new();
+    collect_jsonl(&dir, &mut transcripts).context(format!("Failed to scan {}", dir.display()))?;
+    // Claude subagent transcripts belong to their parent session
+    transcripts.retain(|path| {
+        !path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with("agent-"))
+    });
+    transcripts.sort();
+
+    let archived: HashSet<PathBuf> = get_archived_transcript_paths(config)?
+        .iter()
+        .map(|path| canonical(Path::new(path)))
+        .collect();
+    let in_flight: HashSet<PathBuf> = JobManager::new(config)?
+        .list(false)?
+        .iter()
+        .map(|job| canonical(&job.transcript_path))
+        .collect();
+    let mut planned = Vec::new();
+    let mut skipped = 0;
+    for transcript in transcripts {
+        let transcript = canonical(&transcript);
+        if archived.contains(&transcript)
+            || in_flight.contains(&transcript)
+            || parse_transcript(config, &transcript).map_or(true, |data| data.is_empty())
+        {
+            skipped += 1;
+            continue;
+        }
+
+        let date = match date {
+            Some(date) => date.to_string(),
+            None => modified_date(&transcript)?,
+        };
+        let cwd = transcript
+            .parent()
+            .and_then(|parent| parent.file_name())
+            .and_then(|name| decode_project_dir(&name.to_string_lossy()));
+        planned.push(PlannedImport {
+            transcript,
+            date,
+            cwd,
+        });
+    }
+    Ok((planned, skipped))
+}
+
+/// Local date (yyyy-mm-dd) a file was last modified
+fn modified_date(path: &Path) -> Result<String> {
+    let modified = fs::metadata(path)
+        .and_then(|m| m.modified())
+        .context(format!("Failed to read {}", path.display()))?;
+    Ok(DateTime::<Local>::from(modified)
+        .format("%Y-%m-%d")
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const TRANSCRIPT: &str = r#"{"type":"user","message":{"role":"user","content":"Fix the login bug"},"timestamp":"2026-01-16T10:00:00Z"}"#;
+
+    #[test]
+    fn test_plan_imports_skips_archived_and_empty() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().join("storage");
+
+        let source = temp_dir.path().join("transcripts");
+        fs::create_dir_all(source.join("-project")).unwrap();
+        let source = fs::canonicalize(source).unwrap();
+        let new = source.join("-project/new.jsonl");
+        let archived = source.join("-project/archived.jsonl");
+        let queued = source.join("-project/queued.jsonl");
+        fs::write(&new, TRANSCRIPT).unwrap();
+        fs::write(&archived, TRANSCRIPT).unwrap();
+        fs::write(&queued, TRANSCRIPT).unwrap();
+        fs::write(source.join("-project/empty.jsonl"), "").unwrap();
+        fs::write(source.join("-project/agent-1.jsonl"), TRANSCRIPT).unwrap();
+
+        let session_dir = config.storage.path.join("2026-01-15");
+        fs::create_dir_all(&session_dir).unwrap();
+        fs::write(
+            session_dir.join("10_00-old.md"),
+            format!(
+                "---\ntranscript_path: {}\n---\n\n# Old\n",
+                archived.display()
+            ),
+        )
+        .unwrap();
+        JobManager::new(&config)
+            .unwrap()
+            .enqueue("queued", "queued", &queued, JobType::Manual, vec![], None)
+            .unwrap();
+
+        // Another spelling of the directory finds the same transcripts
+        let relative = source.join("-project/..");
+        let (planned, skipped) = plan_imports(&config, &relative, Some("2026-01-10")).unwrap();
+        assert_eq!(
+            planned,
+            vec![PlannedImport {
+                transcript: new,
+                date: "2026-01-10".to_string(),
+                cwd: None,
+            }]
+        );
+        assert_eq!(skipped, 3);
+    }
+}
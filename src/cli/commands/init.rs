@@ -6,6 +6,7 @@ use std::path::{Path, PathBuf};
 use super::install;
 use crate::archive::ArchiveManager;
 use crate::config::{get_config_path, load_config, save_config, Config};
+use crate::summarizer::SUPPORTED_LANGUAGES;
 
 /// Initialize the daily archive system
 pub async fn run(storage_path: Option<PathBuf>, interactive: bool, use_haiku: bool) -> Result<()> {
@@ -246,8 +247,14 @@ fn configure_language_interactive(config: &mut Config) -> Result<()> {
     println!();
     println!("[daily] Language Configuration");
 
-    let languages = ["English", "中文 (Chinese)"];
-    let language_codes = ["en", "zh"];
+    let languages = [
+        "English",
+        "中文 (Chinese)",
+        "日本語 (Japanese)",
+        "Español (Spanish)",
+        "Français (French)",
+    ];
+    let language_codes = SUPPORTED_LANGUAGES;
 
     let selection = Select::with_theme(&theme)
         .with_prompt("Select summary language")
@@ -52,6 +52,16 @@ pub async fn run(scope: String) -> Result<()> {
           }
         ]
       }
+    ],
+    "UserPromptSubmit": [
+      {
+        "hooks": [
+          {
+            "type": "command",
+            "command": "daily hook user-prompt-submit"
+          }
+        ]
+      }
     ]
   }
 }
@@ -244,13 +254,21 @@ fn create_daily_hooks() -> Map<String, Value> {
         }]
     }]);
 
+    let user_prompt_hook = json!([{
+        "hooks": [{
+            "type": "command",
+            "command": "daily hook user-prompt-submit"
+        }]
+    }]);
+
     hooks.insert("SessionStart".to_string(), session_start_hook);
     hooks.insert("SessionEnd".to_string(), session_end_hook);
+    hooks.insert("UserPromptSubmit".to_string(), user_prompt_hook);
     hooks
 }
 
 /// Check if a hook array already contains the daily hook command
-fn has_daily_hook(hooks_array: &[Value], command: &str) -> bool {
+pub fn has_daily_hook(hooks_array: &[Value], command: &str) -> bool {
     for hook_entry in hooks_array {
         if let Some(inner_hooks) = hook_entry.get("hooks").and_then(|h| h.as_array()) {
             for inner_hook in inner_hooks {
@@ -280,6 +298,7 @@ fn merge_hooks(settings: &mut Value, daily_hooks: &Map<String, Value>) -> bool {
         let command = match event_name.as_str() {
             "SessionStart" => "daily hook session-start",
             "SessionEnd" => "daily hook session-end",
+            "UserPromptSubmit" => "daily hook user-prompt-submit",
             _ => continue,
         };
 
@@ -1,53 +1,73 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use chrono::{Duration, Local};
 use colored::Colorize;
 
+use crate::cli::commands::summarize;
 use crate::config::load_config;
-use crate::jobs::{JobManager, JobStatus};
+use crate::jobs::{JobInfo, JobManager, JobStatus, JobType, JOB_TIME_FORMAT};
+use crate::server::dto::JobDto;
+
+/// Days of jobs kept by `daily jobs cleanup` unless --days is given
+const DEFAULT_KEEP_DAYS: u32 = 7;
+
+/// List all jobs, ordered by `sort` (started, elapsed or status)
+pub async fn list(all: bool, since: Option<String>, sort: &str, json: bool) -> Result<()> {
+    let since = since.as_deref().map(parse_duration).transpose()?;
 
-/// List all jobs
-pub async fn list(all: bool) -> Result<()> {
     let config = load_config()?;
     let manager = JobManager::new(&config)?;
 
-    let jobs = manager.list(all)?;
+    let mut jobs = manager.list(all)?;
+    if let Some(window) = since {
+        let cutoff = Local::now() - window;
+        jobs.retain(|job| job.started_at >= cutoff);
+    }
+    sort_jobs(&mut jobs, sort);
+
+    if json {
+        let jobs: Vec<JobDto> = jobs.into_iter().map(Into::into).collect();
+        println!("{}", serde_json::to_string_pretty(&jobs)?);
+        return Ok(());
+    }
 
     if jobs.is_empty() {
-        if all {
+        if since.is_some() {
+            println!("No jobs started in that window.");
+        } else if all {
             println!("No jobs found.");
         } else {
-            println!("No running jobs. Use --all to see completed jobs.");
+            println!("No running or queued jobs. Use --all to see completed jobs.");
         }
         return Ok(());
     }
 
     // Print header
     println!(
-        "{:<28} {:<12} {:<20} {:<10}",
+        "{:<28} {:<12} {:<15} {:<20} {:>10}",
         "ID".bold(),
         "STATUS".bold(),
+        "TYPE".bold(),
         "TASK".bold(),
         "ELAPSED".bold()
     );
-    println!("{}", "-".repeat(72));
+    println!("{}", "-".repeat(88));
 
     for job in jobs {
-        let status_str = match &job.status {
-            JobStatus::Running => "Running".green().to_string(),
-            JobStatus::Completed => "Completed".blue().to_string(),
-            JobStatus::Failed { .. } => "Failed".red().to_string(),
-        };
-
-        let task_display = if job.task_name.len() > 18 {
-            format!("{}...", &job.task_name[..15])
-        } else {
-            job.task_name.clone()
+        // Padded before coloring, since escape codes would count toward the width
+        let status = format!("{:<12}", job.status.label());
+        let status = match &job.status {
+            JobStatus::Queued => status.yellow(),
+            JobStatus::Running => status.green(),
+            JobStatus::Completed => status.blue(),
+            JobStatus::Failed { .. } => status.red(),
         };
 
         println!(
-            "{:<28} {:<12} {:<20} {:<10}",
+            "{:<28} {} {:<15} {:<20} {:>10}",
             job.id,
-            status_str,
-            task_display,
+            status,
+            job.job_type.to_string(),
+            job.short_task_name(18),
             job.elapsed_human()
         );
     }
@@ -55,6 +75,82 @@ pub async fn list(all: bool) -> Result<()> {
     Ok(())
 }
 
+/// Order jobs for listing; `list` already returns them newest first
+fn sort_jobs(jobs: &mut [JobInfo], sort: &str) {
+    match sort {
+        "elapsed" => jobs.sort_by_key(|job| std::cmp::Reverse(job.elapsed())),
+        // Active jobs first; stable, so newest first within a status
+        "status" => jobs.sort_by_key(|job| match job.status {
+            JobStatus::Running => 0,
+            JobStatus::Queued => 1,
+            JobStatus::Failed { .. } => 2,
+            JobStatus::Completed => 3,
+        }),
+        _ => jobs.sort_by_key(|job| std::cmp::Reverse(job.started_at)),
+    }
+}
+
+/// Parse a duration like `90m`, `2h` or `1d`
+fn parse_duration(input: &str) -> Result<Duration> {
+    let input = input.trim();
+    let split = input.len() - input.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let (amount, unit) = input.split_at(split);
+    let Ok(amount) = amount.parse::<i64>() else {
+        bail!("Invalid duration '{}': expected e.g. 30m, 2h or 1d", input);
+    };
+    match unit {
+        "s" => Ok(Duration::seconds(amount)),
+        "m" => Ok(Duration::minutes(amount)),
+        "h" => Ok(Duration::hours(amount)),
+        "d" => Ok(Duration::days(amount)),
+        "w" => Ok(Duration::weeks(amount)),
+        _ => bail!("Invalid duration '{}': unit must be s, m, h, d or w", input),
+    }
+}
+
+/// Show details and claude usage for a job
+pub async fn info(job_id: String) -> Result<()> {
+    let config = load_config()?;
+    let manager = JobManager::new(&config)?;
+
+    let job = manager.load_job(&job_id).context("Job not found")?;
+
+    println!("{} {}", "Job:".bold(), job.id);
+    println!("{} {}", "Type:".bold(), job.job_type);
+    println!("{} {}", "Task:".bold(), job.task_name);
+    println!("{} {}", "Status:".bold(), job.status.to_string().cyan());
+    println!(
+        "{} {}",
+        "Started:".bold(),
+        job.started_at.format(JOB_TIME_FORMAT)
+    );
+    if let Some(finished) = job.finished_at {
+        println!(
+            "{} {}",
+            "Finished:".bold(),
+            finished.format(JOB_TIME_FORMAT)
+        );
+    }
+    println!("{} {}", "Elapsed:".bold(), job.elapsed_human());
+    if let Some(original) = &job.retry_of {
+        println!("{} {}", "Retry of:".bold(), original);
+    }
+    println!("{}", "-".repeat(50));
+
+    let metrics = &job.metrics;
+    println!("{} {}", "Claude invocations:".bold(), metrics.invocations);
+    println!("{} {}", "Retries:".bold(), metrics.retries);
+    println!(
+        "{} {} ({} in, {} out)",
+        "Tokens:".bold(),
+        metrics.total_tokens(),
+        metrics.input_tokens,
+        metrics.output_tokens
+    );
+
+    Ok(())
+}
+
 /// Show log for a job
 pub async fn log(job_id: String, tail: Option<usize>, follow: bool) -> Result<()> {
     let config = load_config()?;
@@ -108,7 +204,6 @@ pub async fn log(job_id: String, tail: Option<usize>, follow: bool) -> Result<()
 
 /// Follow log output in real-time
 async fn follow_log(manager: &JobManager, job_id: &str) -> Result<()> {
-    use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
     use std::time::Duration;
 
     let log_path = manager.log_path(job_id);
@@ -124,40 +219,19 @@ async fn follow_log(manager: &JobManager, job_id: &str) -> Result<()> {
         println!("{}", "(waiting for log output...)".dimmed());
     }
 
-    let mut last_pos = 0u64;
+    let mut offset = 0u64;
 
     loop {
-        // Check if job is still running
-        if let Ok(job) = manager.load_job(job_id) {
-            if job.status != JobStatus::Running {
-                // Print remaining content and exit
-                if let Ok(file) = std::fs::File::open(&log_path) {
-                    let mut reader = BufReader::new(file);
-                    reader.seek(SeekFrom::Start(last_pos))?;
-                    for line in reader.lines().map_while(Result::ok) {
-                        println!("{}", line);
-                    }
-                }
-                println!("\n{} {}", "Job finished:".bold(), job.status);
-                break;
-            }
-        }
+        // Check before reading so the output written up to the end is printed
+        let stopped = manager.stopped_status(job_id);
 
-        // Read new content
-        if let Ok(file) = std::fs::File::open(&log_path) {
-            let file_len = file.metadata().map(|m| m.len()).unwrap_or(0);
-
-            if file_len > last_pos {
-                let mut reader = BufReader::new(file);
-                reader.seek(SeekFrom::Start(last_pos))?;
-
-                let mut buf = String::new();
-                if reader.read_to_string(&mut buf).is_ok() && !buf.is_empty() {
-                    print!("{}", buf);
-                }
+        let (output, next) = manager.read_log_since(job_id, offset)?;
+        print!("{}", output);
+        offset = next;
 
-                last_pos = file_len;
-            }
+        if let Some(status) = stopped {
+            println!("\n{} {}", "Job finished:".bold(), status);
+            break;
         }
 
         tokio::time::sleep(Duration::from_millis(500)).await;
@@ -173,6 +247,12 @@ pub async fn kill(job_id: String) -> Result<()> {
 
     let job = manager.load_job(&job_id).context("Job not found")?;
 
+    if job.status == JobStatus::Queued {
+        manager.kill(&job_id)?;
+        println!("{} Cancelled queued job {}", "Success:".green(), job_id);
+        return Ok(());
+    }
+
     if job.status != JobStatus::Running {
         println!(
             "{} Job {} is not running (status: {})",
@@ -202,17 +282,127 @@ pub async fn kill(job_id: String) -> Result<()> {
     Ok(())
 }
 
+/// Re-run a finished summarization job, linking the new job to the original
+pub async fn retry(job_id: String) -> Result<()> {
+    let config = load_config()?;
+    let manager = JobManager::new(&config)?;
+
+    let job = manager.load_job(&job_id).context("Job not found")?;
+
+    if matches!(job.status, JobStatus::Running | JobStatus::Queued) {
+        bail!(
+            "Job {} is still {}; only finished jobs can be retried",
+            job_id,
+            job.status
+        );
+    }
+    if job.job_type == JobType::Digest {
+        bail!("Digest jobs can't be retried; run 'daily digest' for the date instead");
+    }
+
+    // Jobs recorded before their arguments were stored summarize from the current dir
+    let args = if job.args.is_empty() {
+        let cwd = std::env::current_dir()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|_| ".".to_string());
+        summarize::summarize_args(&job.transcript_path, &job.task_name, &cwd)
+    } else {
+        job.args.clone()
+    };
+
+    // The link is saved before the process starts, so its status updates can't race it
+    let new_id = JobManager::generate_job_id(&job.task_name);
+    let queued = manager.enqueue(
+        &new_id,
+        &job.task_name,
+        &job.transcript_path,
+        job.job_type.clone(),
+        args,
+        Some(job.id.clone()),
+    )?;
+    let new_job = summarize::start_if_free(&config, &manager, queued)?;
+
+    println!(
+        "{} Retrying job {} as {} ({})",
+        "Success:".green(),
+        job.id,
+        new_job.id,
+        new_job.status
+    );
+
+    Ok(())
+}
+
+/// Start queued jobs for the free slots under summarization.max_concurrent_jobs
+pub async fn run_queue() -> Result<()> {
+    let config = load_config()?;
+    let started = JobManager::new(&config)?.run_queue(config.summarization.max_concurrent_jobs)?;
+
+    if started.is_empty() {
+        let waiting = JobManager::new(&config)?.queued()?.len();
+        if waiting == 0 {
+            println!("No queued jobs.");
+        } else {
+            println!(
+                "{} {} job(s) still queued; all {} slot(s) are busy",
+                "Waiting:".yellow(),
+                waiting,
+                config.summarization.max_concurrent_jobs
+            );
+        }
+        return Ok(());
+    }
+
+    for job in &started {
+        println!(
+            "{} Started job {} (PID: {})",
+            "Success:".green(),
+            job.id,
+            job.pid
+        );
+    }
+
+    Ok(())
+}
+
 /// Cleanup old jobs
-pub async fn cleanup(days: u32, dry_run: bool) -> Result<()> {
+pub async fn cleanup(days: Option<u32>, logs_only: bool, dry_run: bool) -> Result<()> {
     let config = load_config()?;
     let manager = JobManager::new(&config)?;
 
+    if logs_only {
+        let days = days.unwrap_or(config.jobs.log_retention_days);
+        if dry_run {
+            let expired = manager.expired_logs(days)?;
+            if expired.is_empty() {
+                println!("No job logs to clean up.");
+            } else {
+                println!("Would remove {} job log(s):", expired.len());
+                for job in expired {
+                    println!("  - {} ({})", job.id, job.task_name);
+                }
+            }
+        } else {
+            let removed = manager.cleanup_logs(days)?;
+            println!(
+                "{} Removed {} job log(s) (older than {} days); job metadata kept",
+                "Success:".green(),
+                removed,
+                days
+            );
+        }
+        return Ok(());
+    }
+
+    let days = days.unwrap_or(DEFAULT_KEEP_DAYS);
     if dry_run {
         let jobs = manager.list(true)?;
         let cutoff = chrono::Local::now() - chrono::Duration::days(days as i64);
         let to_remove: Vec<_> = jobs
             .iter()
-            .filter(|j| j.status != JobStatus::Running && j.started_at < cutoff)
+            .filter(|j| {
+                !matches!(j.status, JobStatus::Running | JobStatus::Queued) && j.started_at < cutoff
+            })
             .collect();
 
         if to_remove.is_empty() {
@@ -235,3 +425,54 @@ pub async fn cleanup(days: u32, dry_run: bool) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90m").unwrap(), Duration::minutes(90));
+        assert_eq!(parse_duration("2h").unwrap(), Duration::hours(2));
+        assert_eq!(parse_duration("1d").unwrap(), Duration::days(1));
+    }
+
+    #[test]
+    fn test_parse_duration_invalid() {
+        for input in ["", "h", "2", "2x", "-1h", "1.5h", "two hours"] {
+            assert!(parse_duration(input).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_sort_jobs() {
+        let start = Local::now() - Duration::hours(1);
+        let job = |id: &str, offset: i64, length: Option<i64>, status: JobStatus| JobInfo {
+            id: id.to_string(),
+            pid: 0,
+            task_name: id.to_string(),
+            transcript_path: PathBuf::new(),
+            started_at: start + Duration::minutes(offset),
+            finished_at: length.map(|length| start + Duration::minutes(offset + length)),
+            status,
+            job_type: JobType::Manual,
+            metrics: Default::default(),
+            args: Vec::new(),
+            retry_of: None,
+        };
+        let mut jobs = vec![
+            job("done", 0, Some(30), JobStatus::Completed),
+            job("running", 10, None, JobStatus::Running),
+            job("short", 20, Some(1), JobStatus::Completed),
+        ];
+        let ids = |jobs: &[JobInfo]| jobs.iter().map(|j| j.id.clone()).collect::<Vec<_>>();
+
+        sort_jobs(&mut jobs, "started");
+        assert_eq!(ids(&jobs), ["short", "running", "done"]);
+        sort_jobs(&mut jobs, "elapsed");
+        assert_eq!(ids(&jobs), ["running", "done", "short"]);
+        sort_jobs(&mut jobs, "status");
+        assert_eq!(ids(&jobs), ["running", "done", "short"]);
+    }
+}
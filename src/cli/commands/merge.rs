@@ -0,0 +1,202 @@
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+
+use crate::archive::session::SessionUsage;
+use crate::archive::{ArchiveManager, SessionArchive};
+use crate::config::load_config;
+use crate::summarizer::sanitize_topic;
+
+/// Merge several sessions into one archive and delete the originals.
+///
+/// Sessions are `name` (on `date`, default today) or `yyyy-mm-dd/name`.
+pub async fn run(
+    date: Option<String>,
+    sessions: Vec<String>,
+    title: Option<String>,
+    force: bool,
+) -> Result<()> {
+    let config = load_config()?;
+    let manager = ArchiveManager::new(config.clone());
+    let default_date = date.unwrap_or_else(|| config.today());
+
+    let mut refs: Vec<(String, String)> = sessions
+        .iter()
+        .map(|session| match session.split_once('/') {
+            Some((date, name)) => (date.to_string(), name.to_string()),
+            None => (default_date.clone(), session.clone()),
+        })
+        .collect();
+    refs.sort();
+    refs.dedup();
+    if refs.len() < 2 {
+        bail!("Merge needs at least two different sessions");
+    }
+
+    let first_date = refs[0].0.clone();
+    if !force && refs.iter().any(|(date, _)| *date != first_date) {
+        bail!(
+            "Sessions span several dates; pass --force to merge them into {}",
+            first_date
+        );
+    }
+
+    let mut parts = Vec::new();
+    for (date, name) in &refs {
+        let content = manager
+            .read_session(date, name)
+            .context(format!("Failed to read session: {}/{}", date, name))?;
+        parts.push(SessionArchive::from_markdown(date, name, &content));
+    }
+
+    let merged = merge_archives(&parts, title.as_deref());
+    let replaces_original = refs
+        .iter()
+        .any(|(date, name)| *date == merged.date && *name == merged.title);
+    if !replaces_original && manager.list_sessions(&merged.date)?.contains(&merged.title) {
+        bail!(
+            "A session named '{}' already exists on {}",
+            merged.title,
+            merged.date
+        );
+    }
+
+    // Originals are resolved before saving so a same-named merge isn't deleted afterwards
+    let originals: Vec<_> = refs
+        .iter()
+        .map(|(date, name)| manager.session_archive_path(date, name))
+        .collect();
+    let path = merged.save(&config)?;
+    for original in originals.iter().filter(|p| **p != path) {
+        std::fs::remove_file(original)
+            .context(format!("Failed to delete {}", original.display()))?;
+    }
+
+    println!(
+        "{} Merged {} sessions into {}",
+        "✓".green(),
+        refs.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Combine archives (sorted oldest first) into one, keeping the earliest time prefix
+fn merge_archives(parts: &[SessionArchive], title: Option<&str>) -> SessionArchive {
+    let first = &parts[0];
+    let (prefix, topic) = first
+        .title
+        .split_once('-')
+        .unwrap_or((first.title.as_str(), "merged"));
+    let topic = title
+        .map(sanitize_topic)
+        .unwrap_or_else(|| topic.to_string());
+
+    let join = |section: fn(&SessionArchive) -> &str, skip: &[&str]| {
+        let texts: Vec<&str> = parts
+            .iter()
+            .map(section)
+            .map(str::trim)
+            .filter(|text| !text.is_empty() && !skip.contains(text))
+            .collect();
+        texts.join("\n\n")
+    };
+
+    let mut merged = SessionArchive::new(
+        format!("{}-{}", prefix, topic),
+        first.date.clone(),
+        first.session_id.clone(),
+        first.cwd.clone(),
+    );
+    merged.git_branch = first.git_branch.clone();
+    merged.transcript_path = first.transcript_path.clone();
+    merged.parent_session_id = first.parent_session_id.clone();
+    merged.summary = join(|a| &a.summary, &[]);
+    merged.decisions = join(|a| &a.decisions, &[]);
+    merged.learnings = join(|a| &a.learnings, &[]);
+    merged.skill_hints = join(|a| &a.skill_hints, &["None identified"]);
+    // Line-level dedupe keeps one bullet per file (and one header for numstat tables)
+    let mut code_lines: Vec<&str> = Vec::new();
+    for line in parts.iter().flat_map(|a| a.code_changes.lines()) {
+        let line = line.trim_end();
+        if !line.is_empty() && line != "_No files modified._" && !code_lines.contains(&line) {
+            code_lines.push(line);
+        }
+    }
+    merged.code_changes = code_lines.join("\n");
+    if merged.code_changes.is_empty() {
+        merged.code_changes = "_No files modified._".to_string();
+    }
+    merged.changes = parts.iter().rev().find_map(|a| a.changes.clone());
+    merged.session_kind = parts
+        .iter()
+        .filter_map(|a| a.session_kind.clone())
+        .find(|kind| kind == "implementation")
+        .or_else(|| first.session_kind.clone());
+    let usages: Vec<&SessionUsage> = parts.iter().filter_map(|a| a.usage.as_ref()).collect();
+    if !usages.is_empty() {
+        merged.usage = Some(SessionUsage {
+            input_tokens: usages.iter().map(|u| u.input_tokens).sum(),
+            output_tokens: usages.iter().map(|u| u.output_tokens).sum(),
+            cost_usd: usages
+                .iter()
+                .filter_map(|u| u.cost_usd)
+                .reduce(|a, b| a + b),
+        });
+    }
+    for part in parts {
+        merged = merged.with_tags(&part.tags);
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part(title: &str, summary: &str, code: &str, tags: &[&str]) -> SessionArchive {
+        let mut archive = SessionArchive::new(
+            title.to_string(),
+            "2026-01-16".to_string(),
+            format!("id-{}", title),
+            "/home/user/project".to_string(),
+        );
+        archive.summary = summary.to_string();
+        archive.code_changes = code.to_string();
+        archive.tags = tags.iter().map(|t| t.to_string()).collect();
+        archive
+    }
+
+    #[test]
+    fn test_merge_archives() {
+        let parts = vec![
+            part(
+                "10_05-fix-auth",
+                "Found the bug.",
+                "- `src/auth.rs`",
+                &["auth"],
+            ),
+            part(
+                "12_00-fix-auth-more",
+                "Tested it.",
+                "- `src/auth.rs`\n- `src/db.rs`",
+                &[],
+            ),
+            part(
+                "14_30-fix-auth-again",
+                "Fixed it.",
+                "_No files modified._",
+                &["auth", "db"],
+            ),
+        ];
+
+        let merged = merge_archives(&parts, None);
+        assert_eq!(merged.title, "10_05-fix-auth");
+        assert_eq!(merged.session_id, "id-10_05-fix-auth");
+        assert_eq!(merged.summary, "Found the bug.\n\nTested it.\n\nFixed it.");
+        assert_eq!(merged.code_changes, "- `src/auth.rs`\n- `src/db.rs`");
+        assert_eq!(merged.tags, vec!["auth", "db"]);
+
+        let renamed = merge_archives(&parts, Some("Auth Fix"));
+        assert_eq!(renamed.title, "10_05-auth-fix");
+    }
+}
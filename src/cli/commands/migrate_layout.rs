@@ -0,0 +1,80 @@
+use anyhow::{bail, Result};
+use colored::Colorize;
+use std::path::PathBuf;
+
+use crate::archive::ArchiveManager;
+use crate::config::{load_config, save_config, STORAGE_LAYOUTS};
+
+/// Move every date directory to `layout` and record it as `storage.layout`.
+///
+/// Covers the main storage and every `storage.project_overrides` path. All moves are
+/// planned first, so a conflicting destination stops the run before anything moves.
+pub async fn run(layout: String, dry_run: bool) -> Result<()> {
+    if !STORAGE_LAYOUTS.contains(&layout.as_str()) {
+        bail!(
+            "Unknown layout '{}'. Use one of: {}",
+            layout,
+            STORAGE_LAYOUTS.join(", ")
+        );
+    }
+
+    let mut config = load_config()?;
+    let mut storages: Vec<PathBuf> = vec![config.storage_path()];
+    for path in config.storage.project_overrides.values() {
+        let mut override_config = config.clone();
+        override_config.storage.path = path.clone();
+        let path = override_config.storage_path();
+        if !storages.contains(&path) {
+            storages.push(path);
+        }
+    }
+
+    let managers: Vec<ArchiveManager> = storages
+        .into_iter()
+        .map(|path| {
+            let mut storage_config = config.clone();
+            storage_config.storage.path = path;
+            ArchiveManager::new(storage_config)
+        })
+        .collect();
+
+    // Fails on the first conflict in any storage, before anything has moved
+    let mut planned = Vec::new();
+    for manager in &managers {
+        planned.extend(manager.layout_moves(&layout)?);
+    }
+
+    if dry_run {
+        for (from, to) in &planned {
+            println!("{} → {}", from.display(), to.display());
+        }
+        println!(
+            "\n{}",
+            format!(
+                "Dry run: {} director(ies) would move to the {} layout.",
+                planned.len(),
+                layout
+            )
+            .dimmed()
+        );
+        return Ok(());
+    }
+
+    let mut moved = 0;
+    for manager in &managers {
+        for (from, to) in manager.migrate_layout(&layout)? {
+            println!("{} {} → {}", "✓".green(), from.display(), to.display());
+            moved += 1;
+        }
+    }
+
+    if config.storage.layout != layout {
+        config.storage.layout = layout.clone();
+        save_config(&config)?;
+    }
+    println!(
+        "\nMoved {} director(ies). storage.layout is now {}.",
+        moved, layout
+    );
+    Ok(())
+}
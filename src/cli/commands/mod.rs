@@ -1,14 +1,30 @@
+pub mod archive;
 pub mod config;
+pub mod daemon;
+pub mod delete;
 pub mod digest;
+pub mod doctor;
+pub mod export;
 pub mod extract;
+pub mod import;
 pub mod init;
 pub mod insights;
 pub mod install;
 pub mod jobs;
+pub mod merge;
+pub mod migrate_layout;
+pub mod open;
+pub mod replay;
+pub mod search;
 pub mod show;
 pub mod skills;
+pub mod stats;
+pub mod status;
 pub mod summarize;
+pub mod tag;
 pub mod trash;
 pub mod uninstall;
 pub mod update;
 pub mod view;
+pub mod watch;
+pub mod weekly;
@@ -0,0 +1,35 @@
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+use colored::Colorize;
+use std::fs;
+
+use crate::archive::ArchiveManager;
+use crate::config::load_config;
+
+/// Open a date directory (or the storage root) in the platform file manager
+pub async fn run(date: Option<String>) -> Result<()> {
+    let config = load_config()?;
+
+    let path = match date {
+        Some(date) => {
+            if NaiveDate::parse_from_str(&date, "%Y-%m-%d").is_err() {
+                bail!("Invalid date '{}', expected yyyy-mm-dd", date);
+            }
+            ArchiveManager::new(config).ensure_date_dir(&date)?
+        }
+        None => {
+            let storage = config.storage_path();
+            fs::create_dir_all(&storage).context("Failed to create storage directory")?;
+            storage
+        }
+    };
+
+    // Print first so headless users still get the path
+    println!("{}", path.display());
+
+    if let Err(e) = open::that(&path) {
+        eprintln!("{} Failed to open file manager: {}", "Warning:".yellow(), e);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+use colored::Colorize;
+use std::path::PathBuf;
+
+use crate::archive::{ArchiveManager, SessionArchive};
+use crate::config::load_config;
+use crate::summarizer::SummarizerEngine;
+
+/// Regenerate a session summary from its stored transcript with the current config and prompts
+pub async fn run(date: String, session: String) -> Result<()> {
+    NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .context(format!("Invalid date format: {}. Use yyyy-mm-dd", date))?;
+
+    let config = load_config()?;
+    let manager = ArchiveManager::new(config.clone());
+
+    let content = manager
+        .read_session(&date, &session)
+        .context(format!("Session not found: {}/{}", date, session))?;
+    let original = SessionArchive::from_markdown(&date, &session, &content);
+
+    let Some(transcript) = original.transcript_path.as_deref().map(PathBuf::from) else {
+        bail!("Session {}/{} has no transcript_path", date, session);
+    };
+    if !transcript.exists() {
+        bail!(
+            "Transcript for {}/{} no longer exists: {}",
+            date,
+            session,
+            transcript.display()
+        );
+    }
+
+    println!(
+        "{} {}/{} from {}",
+        "Replaying".cyan(),
+        date,
+        session,
+        transcript.display()
+    );
+    let engine = SummarizerEngine::new(config);
+    let archive = engine.resummarize_session(&original, &transcript).await?;
+    let path = manager.replace_session(&date, &session, &archive.to_markdown())?;
+
+    println!("{} Regenerated {}", "✓".green(), path.display());
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use anyhow::{bail, Result};
+use chrono::NaiveDate;
+use colored::*;
+use std::collections::HashMap;
+
+use crate::archive::search::{self, QueryMatcher, SearchOptions};
+use crate::archive::ArchiveManager;
+use crate::config::load_config;
+use crate::insights::facets::SessionFacet;
+use crate::server::dto::SearchResultDto;
+
+/// Filters narrowing which archives are searched
+pub struct SearchFilters {
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+    pub tag: Option<String>,
+    pub session_type: Option<String>,
+}
+
+/// Search archives and print ranked results
+pub async fn run(
+    query: String,
+    filters: SearchFilters,
+    regex: bool,
+    limit: usize,
+    json: bool,
+) -> Result<()> {
+    let SearchFilters {
+        date_from,
+        date_to,
+        tag,
+        session_type,
+    } = filters;
+
+    for date in [&date_from, &date_to].into_iter().flatten() {
+        if NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+            bail!("Invalid date format: {}. Use yyyy-mm-dd", date);
+        }
+    }
+
+    let config = load_config()?;
+    let manager = ArchiveManager::new(config);
+
+    let options = SearchOptions {
+        query: query.clone(),
+        date_from,
+        date_to,
+        tag,
+        regex,
+    };
+    let matcher = QueryMatcher::new(&query, regex)?;
+    let mut hits = search::search(&manager, &options)?;
+
+    // Filter by session type using Claude Code's facet data
+    if let Some(session_type) = &session_type {
+        let facet_types: HashMap<String, String> = SessionFacet::load_all()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(id, facet)| facet.session_type.map(|t| (id, t)))
+            .collect();
+
+        hits.retain(|hit| {
+            hit.session_id
+                .as_ref()
+                .and_then(|id| facet_types.get(id))
+                .is_some_and(|t| t == session_type)
+        });
+    }
+
+    // Hits are already ordered by date (most recent first), then score
+    hits.truncate(limit);
+
+    if json {
+        let results: Vec<SearchResultDto> = hits.into_iter().map(Into::into).collect();
+        println!("{}", serde_json::to_string_pretty(&results)?);
+        return Ok(());
+    }
+
+    if hits.is_empty() {
+        println!("{}", format!("No results for \"{}\"", query).yellow());
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("Search results for \"{}\":", query).cyan().bold()
+    );
+    println!();
+
+    for hit in &hits {
+        println!(
+            "  {} {}",
+            format!("{}/{}", hit.date, hit.session_name).green(),
+            format!("({} matches)", hit.match_count).dimmed()
+        );
+        if hit.title != hit.session_name {
+            println!("    {}", hit.title.dimmed());
+        }
+        println!(
+            "    {} {}",
+            format!("L{}:", hit.line_number).dimmed(),
+            highlight(&hit.snippet, &matcher)
+        );
+        println!();
+    }
+
+    println!("Total: {} results", hits.len());
+
+    Ok(())
+}
+
+/// Highlight query matches within a snippet
+fn highlight(snippet: &str, matcher: &QueryMatcher) -> String {
+    let mut output = String::new();
+    let mut last = 0;
+
+    for (start, end) in matcher.ranges(snippet) {
+        output.push_str(&snippet[last..start]);
+        output.push_str(&snippet[start..end].yellow().bold().to_string());
+        last = end;
+    }
+    output.push_str(&snippet[last..]);
+
+    output
+}
+
+/// Rebuild the search index (`<storage>/.index.json`) from scratch
+pub async fn reindex() -> Result<()> {
+    let config = load_config()?;
+    let manager = ArchiveManager::new(config);
+    let index = manager.rebuild_full_text_index()?;
+    println!(
+        "{} Indexed {} file(s), {} distinct token(s)",
+        "✓".green(),
+        index.file_count(),
+        index.token_count()
+    );
+    Ok(())
+}
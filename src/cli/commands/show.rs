@@ -1,23 +1,177 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local};
 use colored::Colorize;
-use std::sync::{Arc, RwLock};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
 use tokio::net::TcpListener;
 use tokio::signal;
 
 use crate::auto_summarize::{
     find_unsummarized_transcripts, should_trigger_auto_summarize,
-    should_trigger_auto_summarize_on_show,
+    should_trigger_auto_summarize_on_show, try_lock_summarize,
 };
-use crate::config::{load_config, save_config};
+use crate::config::{load_config, save_config, Config};
+use crate::jobs::{is_process_alive, kill_process, JobManager};
 use crate::server::{create_router, handlers::AppState};
 
 const DEFAULT_PORT: u16 = 31456;
 const MAX_PORT_ATTEMPTS: u16 = 100;
 
-/// Run the web dashboard server
-pub async fn run(port: Option<u16>, host: String, open_browser: bool) -> Result<()> {
+/// How long to wait for a recorded dashboard to confirm it is still running
+const SERVER_PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// How often running digests are checked while the server shuts down
+const DIGEST_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Where a running dashboard listens, kept in `storage/.server.json` while it runs
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ServerInfo {
+    pub pid: u32,
+    pub host: String,
+    pub port: u16,
+    pub started_at: DateTime<Local>,
+}
+
+impl ServerInfo {
+    /// Dashboard URL of `path` (e.g. `/day/2026-01-16`), with the API token if one is needed
+    pub fn url(&self, path: &str, token: Option<&str>) -> String {
+        dashboard_url(&format!("http://{}:{}", self.host, self.port), path, token)
+    }
+}
+
+fn server_info_path(config: &Config) -> PathBuf {
+    config.storage_path().join(".server.json")
+}
+
+fn read_server_info(config: &Config) -> Option<ServerInfo> {
+    fs::read_to_string(server_info_path(config))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+/// The dashboard a `daily show` is serving, if one is running.
+///
+/// A live pid alone may have been reused after the dashboard was killed, so the server
+/// must report the same pid; otherwise `.server.json` is stale and removed.
+pub async fn running_server(config: &Config) -> Option<ServerInfo> {
+    let server = read_server_info(config)?;
+    if is_process_alive(server.pid) && reported_pid(&server).await == Some(server.pid) {
+        return Some(server);
+    }
+    let _ = fs::remove_file(server_info_path(config));
+    None
+}
+
+/// Pid the dashboard at `server`'s address reports, if it answers
+async fn reported_pid(server: &ServerInfo) -> Option<u32> {
+    let response = reqwest::Client::new()
+        .get(format!("http://{}:{}/api/server", server.host, server.port))
+        .timeout(SERVER_PROBE_TIMEOUT)
+        .send()
+        .await
+        .ok()?;
+    let body: serde_json::Value = response.json().await.ok()?;
+    body["data"]["pid"]
+        .as_u64()
+        .and_then(|pid| u32::try_from(pid).ok())
+}
+
+fn write_server_info(config: &Config, info: &ServerInfo) -> Result<()> {
+    fs::create_dir_all(config.storage_path()).context("Failed to create storage directory")?;
+    fs::write(server_info_path(config), serde_json::to_string(info)?)
+        .context("Failed to save server info")
+}
+
+/// Remove `storage/.server.json` unless another dashboard has replaced it since
+fn remove_server_info(config: &Config) {
+    if read_server_info(config).is_some_and(|server| server.pid == std::process::id()) {
+        let _ = fs::remove_file(server_info_path(config));
+    }
+}
+
+/// Print the running dashboard, if any
+pub async fn status() -> Result<()> {
+    let config = load_config()?;
+    let Some(server) = running_server(&config).await else {
+        println!("{}", "No dashboard running.".dimmed());
+        return Ok(());
+    };
+
+    println!("{}", "Dashboard running".green().bold());
+    println!("  {} {}", "URL:".dimmed(), server.url("/", None).cyan());
+    println!("  {} {}", "PID:".dimmed(), server.pid);
+    println!(
+        "  {} {}",
+        "Started:".dimmed(),
+        server.started_at.format("%Y-%m-%d %H:%M:%S")
+    );
+    Ok(())
+}
+
+/// Stop the running dashboard with SIGTERM, which it handles like Ctrl+C
+pub async fn stop() -> Result<()> {
+    let config = load_config()?;
+    let Some(server) = running_server(&config).await else {
+        println!("{}", "No dashboard running.".dimmed());
+        return Ok(());
+    };
+
+    if !kill_process(server.pid) {
+        bail!("Failed to stop dashboard (pid {})", server.pid);
+    }
+    println!(
+        "{} Stopped dashboard at {} (pid {})",
+        "✓".green(),
+        server.url("/", None),
+        server.pid
+    );
+    Ok(())
+}
+
+/// The dashboard keeps a token passed in the URL for its API requests
+fn dashboard_url(base: &str, path: &str, token: Option<&str>) -> String {
+    match token {
+        Some(token) => format!(
+            "{}{}?{}",
+            base,
+            path,
+            form_urlencoded::Serializer::new(String::new())
+                .append_pair("token", token)
+                .finish()
+        ),
+        None => format!("{}{}", base, path),
+    }
+}
+
+/// Run the web dashboard server, opening `open_path` (e.g. `/`) in the browser if given
+pub async fn run(
+    port: Option<u16>,
+    host: String,
+    open_path: Option<String>,
+    token: Option<String>,
+) -> Result<()> {
     let mut config = load_config()?;
 
+    // Reuse a running dashboard instead of starting another on the next free port
+    if let Some(server) = running_server(&config).await {
+        let url = server.url("/", None);
+        println!(
+            "{} {} (pid {}); stop it with `daily show --stop`",
+            "Dashboard already running at".yellow(),
+            url.cyan(),
+            server.pid
+        );
+        if let Some(path) = open_path {
+            let token = token.as_deref().or(config.server.auth_token.as_deref());
+            if let Err(e) = open::that(server.url(&path, token)) {
+                eprintln!("{} Failed to open browser: {}", "Warning:".yellow(), e);
+            }
+        }
+        return Ok(());
+    }
+
     // Check if we should trigger auto-summarization
     // Either: on_show is enabled (triggers every time) OR time-based trigger is due
     let should_trigger =
@@ -52,30 +206,42 @@ pub async fn run(port: Option<u16>, host: String, open_browser: bool) -> Result<
         }
     }
 
-    let state = Arc::new(AppState {
-        config: RwLock::new(config),
-    });
+    let state = Arc::new(AppState::new(config).with_auth_token(token));
+    crate::server::ws::spawn_watcher(state.clone());
 
     // Find available port
     let (listener, actual_port) = find_available_port(&host, port).await?;
     let url = format!("http://{}:{}", host, actual_port);
+    let server_info = ServerInfo {
+        pid: std::process::id(),
+        host: host.clone(),
+        port: actual_port,
+        started_at: Local::now(),
+    };
+    if let Err(e) = write_server_info(&state.config.read().unwrap(), &server_info) {
+        eprintln!("{} {}", "Warning:".yellow(), e);
+    }
 
     println!("{}", "Starting Daily Dashboard...".green().bold());
     println!();
     println!("  {} {}", "URL:".dimmed(), url.cyan());
+    if let Some(token) = &state.auth_token {
+        println!("  {} {}", "Token:".dimmed(), token);
+    }
     println!();
     println!("{}", "Press Ctrl+C to stop the server".dimmed());
     println!();
 
     // Open browser
-    if open_browser {
+    if let Some(path) = open_path {
+        let url = server_info.url(&path, state.auth_token.as_deref());
         if let Err(e) = open::that(&url) {
             eprintln!("{} Failed to open browser: {}", "Warning:".yellow(), e);
         }
     }
 
     // Create router and start server
-    let app = create_router(state);
+    let app = create_router(state.clone());
 
     // Run server with graceful shutdown on Ctrl+C
     axum::serve(listener, app)
@@ -83,6 +249,10 @@ pub async fn run(port: Option<u16>, host: String, open_browser: bool) -> Result<
         .await
         .context("Server error")?;
 
+    let config = state.config.read().unwrap().clone();
+    remove_server_info(&config);
+    wait_for_digests(&config).await;
+
     println!();
     println!("{}", "Server stopped.".dimmed());
 
@@ -122,11 +292,12 @@ async fn find_available_port(host: &str, port: Option<u16>) -> Result<(TcpListen
 
 /// Trigger auto-summarization for unsummarized transcripts
 async fn trigger_auto_summarize(config: &crate::config::Config) -> Result<usize> {
-    use crate::jobs::{JobManager, JobType};
-    use std::process::{Command, Stdio};
+    use crate::cli::commands::summarize::spawn_auto_summarize;
 
-    #[cfg(unix)]
-    use std::os::unix::process::CommandExt;
+    // Another `daily show` is already spawning jobs for the same transcripts
+    let Some(_lock) = try_lock_summarize(config)? else {
+        return Ok(0);
+    };
 
     // Find unsummarized transcripts
     let unsummarized = find_unsummarized_transcripts(config)?;
@@ -135,87 +306,49 @@ async fn trigger_auto_summarize(config: &crate::config::Config) -> Result<usize>
         return Ok(0);
     }
 
-    // Initialize job manager
-    let job_manager = JobManager::new(config)?;
-
-    let mut spawned_count = 0;
-
-    for transcript in unsummarized {
-        // Generate task name from session ID
-        let task_name = format!("auto-{}", transcript.session_id);
-
-        // Generate job ID
-        let job_id = JobManager::generate_job_id(&task_name);
-        let transcript_path_str = transcript.path.to_string_lossy().to_string();
+    spawn_auto_summarize(config, unsummarized)
+}
 
-        // Create log file for the job
-        let (stdout_file, stderr_file) = match job_manager.create_log_file(&job_id) {
-            Ok(f) => {
-                let f2 = f.try_clone().unwrap_or_else(|_| {
-                    std::fs::File::create("/dev/null").expect("Failed to open /dev/null")
-                });
-                (Stdio::from(f), Stdio::from(f2))
-            }
-            Err(_) => (Stdio::null(), Stdio::null()),
-        };
+/// Wait for running digests (e.g. started from the dashboard) to finish writing daily.md.
+///
+/// Digests run as separate processes, so another Ctrl+C stops waiting and leaves them
+/// running; they stay listed in `daily jobs`.
+async fn wait_for_digests(config: &crate::config::Config) {
+    let running = || {
+        JobManager::new(config)
+            .and_then(|jobs| jobs.running_digests())
+            .unwrap_or_default()
+    };
 
-        // Default CWD for auto-summarize jobs
-        let cwd_str = transcript
-            .cwd
-            .as_ref()
-            .map(|p| p.to_string_lossy().to_string())
-            .unwrap_or_else(|| ".".to_string());
-
-        // Spawn background process for summarization
-        let mut cmd = Command::new("daily");
-        cmd.args([
-            "summarize",
-            "--transcript",
-            &transcript_path_str,
-            "--task-name",
-            &task_name,
-            "--cwd",
-            &cwd_str,
-            "--job-id",
-            &job_id,
-            "--foreground",
-        ])
-        .stdin(Stdio::null())
-        .stdout(stdout_file)
-        .stderr(stderr_file);
-
-        // Create a new process group so it doesn't get killed
-        #[cfg(unix)]
-        cmd.process_group(0);
-
-        match cmd.spawn() {
-            Ok(child) => {
-                // Register the job with AutoSummarize type
-                if let Err(e) = job_manager.register(
-                    &job_id,
-                    child.id(),
-                    &task_name,
-                    &transcript.path,
-                    JobType::AutoSummarize,
-                ) {
-                    eprintln!("[daily] Failed to register auto-summarize job: {}", e);
-                } else {
-                    spawned_count += 1;
-                }
-            }
-            Err(e) => {
-                eprintln!(
-                    "[daily] Failed to spawn auto-summarize process for {}: {}",
-                    transcript.session_id, e
-                );
+    let digests = running();
+    if digests.is_empty() {
+        return;
+    }
+    let ids: Vec<String> = digests.into_iter().map(|job| job.id).collect();
+    println!(
+        "{} {} (Ctrl+C to leave running)",
+        "Waiting for digest to finish:".yellow(),
+        ids.join(", ")
+    );
+
+    let finished = async {
+        loop {
+            tokio::time::sleep(DIGEST_POLL_INTERVAL).await;
+            if running().is_empty() {
+                break;
             }
         }
+    };
+    tokio::select! {
+        _ = finished => println!("{} Digest finished", "✓".green()),
+        _ = signal::ctrl_c() => {
+            println!("Digest left running in the background; see `daily jobs`")
+        }
     }
-
-    Ok(spawned_count)
 }
 
-async fn shutdown_signal() {
+/// Resolves on Ctrl+C, or SIGTERM on Unix
+pub async fn shutdown_signal() {
     let ctrl_c = async {
         signal::ctrl_c()
             .await
@@ -238,3 +371,52 @@ async fn shutdown_signal() {
         _ = terminate => {},
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_running_server() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+        assert_eq!(running_server(&config).await, None);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let app = create_router(Arc::new(AppState::new(config.clone())));
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let info = ServerInfo {
+            pid: std::process::id(),
+            host: "127.0.0.1".to_string(),
+            port,
+            started_at: Local::now(),
+        };
+        write_server_info(&config, &info).unwrap();
+        let server = running_server(&config).await.unwrap();
+        assert_eq!(
+            server.url("/day/2026-01-16", None),
+            format!("http://127.0.0.1:{}/day/2026-01-16", port)
+        );
+        assert_eq!(
+            server.url("/", Some("secret")),
+            format!("http://127.0.0.1:{}/?token=secret", port)
+        );
+        assert_eq!(
+            server.url("/", Some("a&b=c d")),
+            format!("http://127.0.0.1:{}/?token=a%26b%3Dc+d", port)
+        );
+
+        remove_server_info(&config);
+        assert_eq!(running_server(&config).await, None);
+
+        // A live pid that isn't the dashboard listening there is stale
+        let stale = ServerInfo { pid: 1, ..info };
+        write_server_info(&config, &stale).unwrap();
+        assert_eq!(running_server(&config).await, None);
+        assert!(!server_info_path(&config).exists());
+    }
+}
@@ -1,56 +1,159 @@
 use anyhow::Result;
+use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, Confirm, MultiSelect};
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::io::{self, IsTerminal};
+use termimad::MadSkin;
 
+use crate::archive::skills::{parse_skill_ref, PendingSkill, SkillStore};
 use crate::config::load_config;
 
 /// Review pending skills
-pub async fn run_review(install: Option<String>, delete: Option<String>) -> Result<()> {
+pub async fn run_review(
+    install: Option<String>,
+    delete: Option<String>,
+    list: bool,
+    force: bool,
+) -> Result<()> {
     let config = load_config()?;
-    let pending_dir = config.storage.path.join("pending-skills");
+    let store = SkillStore::new(&config);
 
-    if !pending_dir.exists() {
+    // Handle install action
+    if let Some(skill_ref) = install {
+        let (date, name) = parse_skill_ref(&skill_ref)?;
+        let target_file = store.install(&date, &name)?;
+        println!("✓ Skill installed: {}", target_file.display());
+        println!();
+        println!("The skill is now active and Claude will automatically use it");
+        println!("when matching conditions are detected.");
+        return Ok(());
+    }
+
+    // Handle delete action
+    if let Some(skill_ref) = delete {
+        let (date, name) = parse_skill_ref(&skill_ref)?;
+        store.delete(&date, &name)?;
+        println!("✓ Skill deleted: {}/{}", date, name);
+        return Ok(());
+    }
+
+    // Interactive review needs a terminal; scripts and Claude get the plain listing
+    if list || !(io::stdin().is_terminal() && io::stdout().is_terminal()) {
+        return list_pending_skills(&store);
+    }
+    review_interactively(&store, force)
+}
+
+/// Pick skills to install and to delete in one pass, preview them, then act
+fn review_interactively(store: &SkillStore, force: bool) -> Result<()> {
+    let skills = store.list()?;
+
+    if skills.is_empty() {
         println!("No pending skills to review.");
         return Ok(());
     }
 
-    // Handle install action
-    if let Some(skill_path) = install {
-        return install_skill(&pending_dir, &skill_path);
+    let theme = ColorfulTheme::default();
+    let labels: Vec<String> = skills.iter().map(skill_label).collect();
+    let Some(to_install) = MultiSelect::with_theme(&theme)
+        .with_prompt("Skills to install (space to toggle, enter to continue)")
+        .items(&labels)
+        .interact_opt()?
+    else {
+        println!("{}", "Cancelled.".dimmed());
+        return Ok(());
+    };
+
+    // Only skills not picked for install can be deleted
+    let remaining: Vec<usize> = (0..skills.len())
+        .filter(|i| !to_install.contains(i))
+        .collect();
+    let to_delete: Vec<usize> = if remaining.is_empty() {
+        Vec::new()
+    } else {
+        let items: Vec<&String> = remaining.iter().map(|&i| &labels[i]).collect();
+        let Some(picked) = MultiSelect::with_theme(&theme)
+            .with_prompt("Skills to delete (space to toggle, enter to continue)")
+            .items(&items)
+            .interact_opt()?
+        else {
+            println!("{}", "Cancelled.".dimmed());
+            return Ok(());
+        };
+        picked.into_iter().map(|k| remaining[k]).collect()
+    };
+
+    if to_install.is_empty() && to_delete.is_empty() {
+        println!("{}", "Nothing selected.".dimmed());
+        return Ok(());
     }
 
-    // Handle delete action
-    if let Some(skill_path) = delete {
-        return delete_skill(&pending_dir, &skill_path);
+    let skin = MadSkin::default();
+    for &i in &to_install {
+        let skill = &skills[i];
+        println!();
+        println!(
+            "{}",
+            format!("── {}/{} ──", skill.date, skill.name).cyan().bold()
+        );
+        match fs::read_to_string(&skill.path) {
+            Ok(content) => skin.print_text(&content),
+            Err(e) => eprintln!("{} {}: {}", "Warning:".yellow(), skill.path.display(), e),
+        }
     }
 
-    // List all pending skills
-    list_pending_skills(&pending_dir)
-}
+    if !force {
+        println!();
+        let confirmed = Confirm::with_theme(&theme)
+            .with_prompt(format!(
+                "Install {} and delete {} skill(s)?",
+                to_install.len(),
+                to_delete.len()
+            ))
+            .default(false)
+            .interact()?;
+        if !confirmed {
+            println!("{}", "Cancelled.".dimmed());
+            return Ok(());
+        }
+    }
 
-/// List all pending skills
-fn list_pending_skills(pending_dir: &Path) -> Result<()> {
-    let mut skills: Vec<(String, String, PathBuf)> = Vec::new();
-
-    if let Ok(entries) = fs::read_dir(pending_dir) {
-        for entry in entries.flatten() {
-            if entry.path().is_dir() {
-                let date = entry.file_name().to_string_lossy().to_string();
-                if let Ok(files) = fs::read_dir(entry.path()) {
-                    for file in files.flatten() {
-                        if file.path().extension().is_some_and(|e| e == "md") {
-                            let name = file
-                                .path()
-                                .file_stem()
-                                .map(|s| s.to_string_lossy().to_string())
-                                .unwrap_or_default();
-                            skills.push((date.clone(), name, file.path()));
-                        }
-                    }
-                }
-            }
+    for &i in &to_install {
+        let skill = &skills[i];
+        match store.install(&skill.date, &skill.name) {
+            Ok(path) => println!("{} Skill installed: {}", "✓".green(), path.display()),
+            Err(e) => eprintln!("{} {}/{}: {}", "✗".red(), skill.date, skill.name, e),
+        }
+    }
+    for &i in &to_delete {
+        let skill = &skills[i];
+        match store.delete(&skill.date, &skill.name) {
+            Ok(()) => println!(
+                "{} Skill deleted: {}/{}",
+                "✓".green(),
+                skill.date,
+                skill.name
+            ),
+            Err(e) => eprintln!("{} {}/{}: {}", "✗".red(), skill.date, skill.name, e),
         }
     }
+    Ok(())
+}
+
+/// `date/name`, followed by the skill's description when it has one
+fn skill_label(skill: &PendingSkill) -> String {
+    let description = fs::read_to_string(&skill.path)
+        .ok()
+        .and_then(|content| extract_description(&content));
+    match description {
+        Some(description) => format!("{}/{} - {}", skill.date, skill.name, description),
+        None => format!("{}/{}", skill.date, skill.name),
+    }
+}
+
+/// List all pending skills
+fn list_pending_skills(store: &SkillStore) -> Result<()> {
+    let skills = store.list()?;
 
     if skills.is_empty() {
         println!("No pending skills to review.");
@@ -60,12 +163,12 @@ fn list_pending_skills(pending_dir: &Path) -> Result<()> {
     println!("Pending Skills ({} total):", skills.len());
     println!("{}", "─".repeat(60));
 
-    for (date, name, path) in &skills {
+    for skill in &skills {
         println!();
-        println!("📦 {}/{}", date, name);
+        println!("📦 {}/{}", skill.date, skill.name);
 
         // Read and show preview
-        if let Ok(content) = fs::read_to_string(path) {
+        if let Ok(content) = fs::read_to_string(&skill.path) {
             // Extract description from frontmatter
             if let Some(desc) = extract_description(&content) {
                 println!("   {}", desc);
@@ -80,91 +183,26 @@ fn list_pending_skills(pending_dir: &Path) -> Result<()> {
 
         println!();
         println!("   Actions:");
-        println!("     daily review-skills --install {}/{}", date, name);
-        println!("     daily review-skills --delete {}/{}", date, name);
+        println!(
+            "     daily review-skills --install {}/{}",
+            skill.date, skill.name
+        );
+        println!(
+            "     daily review-skills --delete {}/{}",
+            skill.date, skill.name
+        );
     }
 
     println!();
     println!("{}", "─".repeat(60));
     println!(
         "Or ask Claude: \"install skill {}/{}\"",
-        skills[0].0, skills[0].1
+        skills[0].date, skills[0].name
     );
 
     Ok(())
 }
 
-/// Install a skill to user's skills directory
-fn install_skill(pending_dir: &Path, skill_ref: &str) -> Result<()> {
-    let (date, name) = parse_skill_ref(skill_ref)?;
-    let skill_path = pending_dir.join(&date).join(format!("{}.md", name));
-
-    if !skill_path.exists() {
-        anyhow::bail!("Skill not found: {}/{}", date, name);
-    }
-
-    // Read skill content
-    let content = fs::read_to_string(&skill_path)?;
-
-    // Install to ~/.claude/skills/{name}/SKILL.md
-    let target_dir = dirs::home_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join(".claude")
-        .join("skills")
-        .join(&name);
-
-    fs::create_dir_all(&target_dir)?;
-    let target_file = target_dir.join("SKILL.md");
-    fs::write(&target_file, &content)?;
-
-    // Remove from pending
-    fs::remove_file(&skill_path)?;
-
-    // Clean up empty date directory
-    let date_dir = pending_dir.join(&date);
-    if fs::read_dir(&date_dir)?.next().is_none() {
-        fs::remove_dir(&date_dir)?;
-    }
-
-    println!("✓ Skill installed: {}", target_file.display());
-    println!();
-    println!("The skill is now active and Claude will automatically use it");
-    println!("when matching conditions are detected.");
-
-    Ok(())
-}
-
-/// Delete a pending skill
-fn delete_skill(pending_dir: &Path, skill_ref: &str) -> Result<()> {
-    let (date, name) = parse_skill_ref(skill_ref)?;
-    let skill_path = pending_dir.join(&date).join(format!("{}.md", name));
-
-    if !skill_path.exists() {
-        anyhow::bail!("Skill not found: {}/{}", date, name);
-    }
-
-    fs::remove_file(&skill_path)?;
-
-    // Clean up empty date directory
-    let date_dir = pending_dir.join(&date);
-    if fs::read_dir(&date_dir)?.next().is_none() {
-        fs::remove_dir(&date_dir)?;
-    }
-
-    println!("✓ Skill deleted: {}/{}", date, name);
-
-    Ok(())
-}
-
-/// Parse skill reference like "2026-01-18/skill-name"
-fn parse_skill_ref(skill_ref: &str) -> Result<(String, String)> {
-    let parts: Vec<&str> = skill_ref.split('/').collect();
-    if parts.len() != 2 {
-        anyhow::bail!("Invalid skill reference. Use format: YYYY-MM-DD/skill-name");
-    }
-    Ok((parts[0].to_string(), parts[1].to_string()))
-}
-
 /// Extract description from YAML frontmatter
 fn extract_description(content: &str) -> Option<String> {
     for line in content.lines() {
@@ -0,0 +1,406 @@
+use anyhow::Result;
+use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::archive::frontmatter::Frontmatter;
+use crate::archive::session::SessionUsage;
+use crate::archive::ArchiveManager;
+use crate::config::load_config;
+use crate::jobs::{JobManager, JobMetrics};
+
+/// Activity aggregated from existing archives
+#[derive(Debug, Default)]
+struct UsageStats {
+    /// (date, session count), oldest first
+    per_day: Vec<(String, usize)>,
+    total_sessions: usize,
+    files_modified: usize,
+    /// Session counts indexed by weekday, Monday first
+    by_weekday: [usize; 7],
+    /// Summarization usage recorded in session frontmatter
+    cost: CostStats,
+}
+
+/// Summarization tokens and estimated cost summed from session frontmatter
+#[derive(Debug, Default, Serialize)]
+struct CostStats {
+    input_tokens: u64,
+    output_tokens: u64,
+    cost_usd: f64,
+    /// Sessions with recorded usage
+    sessions: usize,
+    /// Sessions with tokens but no price for their model
+    unpriced_sessions: usize,
+    /// (date, cost), oldest first; only dates with recorded usage
+    per_day: Vec<(String, f64)>,
+}
+
+impl CostStats {
+    fn add(&mut self, date: &str, usage: &SessionUsage) {
+        self.input_tokens += usage.input_tokens;
+        self.output_tokens += usage.output_tokens;
+        self.sessions += 1;
+        let Some(cost) = usage.cost_usd else {
+            self.unpriced_sessions += 1;
+            return;
+        };
+        self.cost_usd += cost;
+        match self.per_day.last_mut() {
+            Some((day, total)) if day == date => *total += cost,
+            _ => self.per_day.push((date.to_string(), cost)),
+        }
+    }
+}
+
+impl UsageStats {
+    /// Collect stats for the given dates (newest first, as `list_dates` returns them)
+    fn collect(manager: &ArchiveManager, dates: &[String]) -> Self {
+        let mut stats = Self::default();
+
+        for date in dates.iter().rev() {
+            let sessions = manager.list_sessions(date).unwrap_or_default();
+            if sessions.is_empty() {
+                continue;
+            }
+
+            for session in &sessions {
+                if let Ok(content) = manager.read_session(date, session) {
+                    stats.files_modified += count_modified_files(&content);
+                    if let Some(usage) =
+                        SessionUsage::from_frontmatter(&Frontmatter::parse(&content))
+                    {
+                        stats.cost.add(date, &usage);
+                    }
+                }
+            }
+
+            if let Ok(day) = NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+                stats.by_weekday[day.weekday().num_days_from_monday() as usize] += sessions.len();
+            }
+            stats.total_sessions += sessions.len();
+            stats.per_day.push((date.clone(), sessions.len()));
+        }
+
+        stats
+    }
+
+    fn average_per_day(&self) -> f64 {
+        if self.per_day.is_empty() {
+            0.0
+        } else {
+            self.total_sessions as f64 / self.per_day.len() as f64
+        }
+    }
+
+    fn most_active_weekday(&self) -> Option<Weekday> {
+        let (idx, count) = self
+            .by_weekday
+            .iter()
+            .enumerate()
+            .max_by_key(|(i, count)| (**count, std::cmp::Reverse(*i)))?;
+        (*count > 0)
+            .then_some(idx)
+            .and_then(|i| Weekday::try_from(i as u8).ok())
+    }
+}
+
+/// Stats as printed by `daily stats --json`
+#[derive(Serialize)]
+struct StatsJson {
+    days: usize,
+    total_sessions: usize,
+    active_days: usize,
+    average_per_day: f64,
+    most_active_weekday: Option<String>,
+    files_modified: usize,
+    claude_usage: JobMetrics,
+    per_day: Vec<DayCountJson>,
+}
+
+#[derive(Serialize)]
+struct DayCountJson {
+    date: String,
+    sessions: usize,
+}
+
+impl StatsJson {
+    fn new(days: usize, stats: UsageStats, claude_usage: JobMetrics) -> Self {
+        Self {
+            days,
+            total_sessions: stats.total_sessions,
+            active_days: stats.per_day.len(),
+            average_per_day: stats.average_per_day(),
+            most_active_weekday: stats.most_active_weekday().map(|d| d.to_string()),
+            files_modified: stats.files_modified,
+            claude_usage,
+            per_day: stats
+                .per_day
+                .into_iter()
+                .map(|(date, sessions)| DayCountJson { date, sessions })
+                .collect(),
+        }
+    }
+}
+
+/// Count the files in a session's `## Code Changes` section, as bullets or table rows
+fn count_modified_files(content: &str) -> usize {
+    let Some(start) = content.find("## Code Changes") else {
+        return 0;
+    };
+    content[start..]
+        .lines()
+        .skip(1)
+        .take_while(|line| !line.starts_with("## "))
+        .filter(|line| {
+            let line = line.trim_start();
+            line.starts_with("- ") || line.starts_with("| `")
+        })
+        .count()
+}
+
+/// Run the stats command - summarize archive activity over the last N days
+pub async fn run(days: usize, cost: bool, json: bool) -> Result<()> {
+    let config = load_config()?;
+    let manager = ArchiveManager::new(config.clone());
+
+    let since = (config.effective_date(Local::now())
+        - Duration::days(days.saturating_sub(1) as i64))
+    .format("%Y-%m-%d")
+    .to_string();
+    let mut dates = manager.list_dates()?;
+    dates.retain(|d| d.as_str() >= since.as_str());
+
+    let stats = UsageStats::collect(&manager, &dates);
+    if cost {
+        return print_cost(days, &stats.cost, json);
+    }
+
+    // AI usage recorded by background jobs started in the period
+    let mut metrics = JobMetrics::default();
+    if let Ok(job_manager) = JobManager::new(&config) {
+        for job in job_manager.list(true).unwrap_or_default() {
+            if job.started_at.format("%Y-%m-%d").to_string() >= since {
+                metrics.add(&job.metrics);
+            }
+        }
+    }
+
+    if json {
+        let output = StatsJson::new(days, stats, metrics);
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    println!(
+        "\n{}",
+        format!("  Daily Stats (last {} days)", days)
+            .bold()
+            .bright_yellow()
+    );
+    println!("{}", "  ─────────────────────────────".dimmed());
+
+    if stats.total_sessions == 0 {
+        println!("\n  {}", "No sessions archived in this period.".yellow());
+        println!();
+        return Ok(());
+    }
+
+    println!(
+        "\n  {} {}",
+        "Total sessions:".bold(),
+        stats.total_sessions.to_string().bright_yellow()
+    );
+    println!(
+        "  {} {}",
+        "Active days:".bold(),
+        stats.per_day.len().to_string().bright_yellow()
+    );
+    println!(
+        "  {} {:.1}",
+        "Average per active day:".bold(),
+        stats.average_per_day()
+    );
+    if let Some(weekday) = stats.most_active_weekday() {
+        println!("  {} {}", "Most active day:".bold(), weekday);
+    }
+    println!(
+        "  {} {}",
+        "Files modified:".bold(),
+        stats.files_modified.to_string().bright_yellow()
+    );
+
+    if metrics.invocations > 0 {
+        println!(
+            "  {} {} calls, {} retries, {} tokens",
+            "Claude usage (jobs):".bold(),
+            metrics.invocations,
+            metrics.retries,
+            metrics.total_tokens()
+        );
+    }
+
+    // Sessions per day (simple bar chart)
+    println!("\n  {}", "Sessions per Day:".bold());
+    let max_count = stats.per_day.iter().map(|(_, c)| *c).max().unwrap_or(1);
+    for (date, count) in &stats.per_day {
+        let bar = "#".repeat((count * 30).div_ceil(max_count));
+        println!(
+            "  {} {} {}",
+            date.dimmed(),
+            bar.bright_yellow(),
+            count.to_string().dimmed()
+        );
+    }
+
+    println!();
+    Ok(())
+}
+
+/// `daily stats --cost`: summarization spend over the period
+fn print_cost(days: usize, cost: &CostStats, json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(cost)?);
+        return Ok(());
+    }
+
+    println!(
+        "\n{}",
+        format!("  Summarization Cost (last {} days)", days)
+            .bold()
+            .bright_yellow()
+    );
+    println!("{}", "  ─────────────────────────────".dimmed());
+
+    if cost.sessions == 0 {
+        println!(
+            "\n  {}",
+            "No sessions with recorded usage in this period.".yellow()
+        );
+        println!();
+        return Ok(());
+    }
+
+    println!(
+        "\n  {} {}",
+        "Sessions:".bold(),
+        cost.sessions.to_string().bright_yellow()
+    );
+    println!(
+        "  {} {} in, {} out",
+        "Tokens:".bold(),
+        cost.input_tokens,
+        cost.output_tokens
+    );
+    println!(
+        "  {} {}",
+        "Estimated cost:".bold(),
+        format!("${:.2}", cost.cost_usd).bright_yellow()
+    );
+    if cost.unpriced_sessions > 0 {
+        println!(
+            "  {}",
+            format!(
+                "{} session(s) had no price for their model (see summarization.prices)",
+                cost.unpriced_sessions
+            )
+            .dimmed()
+        );
+    }
+
+    println!("\n  {}", "Cost per Day:".bold());
+    for (date, total) in &cost.per_day {
+        println!("  {} ${:.4}", date.dimmed(), total);
+    }
+
+    println!();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_count_modified_files() {
+        let content = "## Summary\n\n- not a file\n\n## Code Changes\n\n- `src/a.rs`\n- `src/b.rs`\n\n## Learnings\n\n- learned\n";
+        assert_eq!(count_modified_files(content), 2);
+        assert_eq!(
+            count_modified_files("## Code Changes\n\n_No files modified._\n"),
+            0
+        );
+        let table = "## Code Changes\n\n| File | Added | Removed |\n| ---- | ----- | ------- |\n| `src/a.rs` | +1 | -0 |\n";
+        assert_eq!(count_modified_files(table), 1);
+    }
+
+    #[test]
+    fn test_collect_stats() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+        let manager = ArchiveManager::new(config);
+
+        let session = "## Code Changes\n\n- `src/main.rs`\n";
+        // 2026-01-12 is a Monday, 2026-01-13 a Tuesday
+        manager
+            .write_session("2026-01-12", "proj", "one", session)
+            .unwrap();
+        manager
+            .write_session("2026-01-13", "proj", "two", session)
+            .unwrap();
+        manager
+            .write_session("2026-01-13", "proj", "three", session)
+            .unwrap();
+
+        let dates = manager.list_dates().unwrap();
+        let stats = UsageStats::collect(&manager, &dates);
+
+        assert_eq!(stats.total_sessions, 3);
+        assert_eq!(stats.files_modified, 3);
+        assert_eq!(stats.per_day[0], ("2026-01-12".to_string(), 1));
+        assert_eq!(stats.most_active_weekday(), Some(Weekday::Tue));
+        assert_eq!(stats.average_per_day(), 1.5);
+        assert_eq!(stats.cost.sessions, 0);
+    }
+
+    #[test]
+    fn test_collect_cost() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+        let manager = ArchiveManager::new(config);
+
+        let session = |cost: &str| {
+            format!(
+                "---\ninput_tokens: 1000\noutput_tokens: 200\n{}---\n\n## Summary\n",
+                cost
+            )
+        };
+        manager
+            .write_session("2026-01-12", "proj", "one", &session("cost_usd: 0.0060\n"))
+            .unwrap();
+        manager
+            .write_session("2026-01-13", "proj", "two", &session("cost_usd: 0.0100\n"))
+            .unwrap();
+        manager
+            .write_session("2026-01-13", "proj", "three", &session(""))
+            .unwrap();
+        manager
+            .write_session("2026-01-13", "proj", "four", "## Summary\n")
+            .unwrap();
+
+        let dates = manager.list_dates().unwrap();
+        let cost = UsageStats::collect(&manager, &dates).cost;
+
+        assert_eq!(cost.sessions, 3);
+        assert_eq!(cost.unpriced_sessions, 1);
+        assert_eq!(cost.input_tokens, 3000);
+        assert_eq!(cost.output_tokens, 600);
+        assert!((cost.cost_usd - 0.016).abs() < 1e-9);
+        assert_eq!(cost.per_day.len(), 2);
+        assert_eq!(cost.per_day[0].0, "2026-01-12");
+        assert!((cost.per_day[1].1 - 0.01).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,191 @@
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use colored::*;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+use super::install::has_daily_hook;
+use crate::archive::ArchiveManager;
+use crate::config::load_config;
+use crate::jobs::{JobManager, JobStatus};
+
+/// Show an at-a-glance health view of hooks, jobs and archives
+pub async fn run() -> Result<()> {
+    let config = load_config()?;
+    let manager = ArchiveManager::new(config.clone());
+
+    println!("{}", "Daily Status".cyan().bold());
+    println!("{}", "=".repeat(50));
+    println!();
+
+    // Hooks
+    let mut scopes = Vec::new();
+    if let Some(home) = dirs::home_dir() {
+        scopes.push(("user", home.join(".claude").join("settings.json")));
+    }
+    if let Ok(cwd) = std::env::current_dir() {
+        scopes.push(("project", cwd.join(".claude").join("settings.json")));
+    }
+    let installed: Vec<&str> = scopes
+        .iter()
+        .filter(|(_, path)| hooks_installed(path))
+        .map(|(scope, _)| *scope)
+        .collect();
+    if installed.is_empty() {
+        print_line(
+            false,
+            "Hooks",
+            "not installed (run 'daily install')".to_string(),
+        );
+    } else {
+        print_line(
+            true,
+            "Hooks",
+            format!("installed ({})", installed.join(", ")),
+        );
+    }
+
+    // Jobs
+    match JobManager::new(&config).and_then(|jobs| jobs.list(true)) {
+        Ok(jobs) => {
+            let running = jobs
+                .iter()
+                .filter(|j| j.status == JobStatus::Running)
+                .count();
+            let queued = jobs
+                .iter()
+                .filter(|j| j.status == JobStatus::Queued)
+                .count();
+            let failed = jobs
+                .iter()
+                .filter(|j| matches!(j.status, JobStatus::Failed { .. }))
+                .count();
+            print_line(
+                failed == 0,
+                "Jobs",
+                format!("{} running, {} queued, {} failed", running, queued, failed),
+            );
+        }
+        Err(e) => print_line(false, "Jobs", format!("unavailable ({})", e)),
+    }
+
+    // Archives
+    let today = config.today();
+    let today_sessions = manager.list_sessions(&today).unwrap_or_default().len();
+    print_line(
+        true,
+        "Today",
+        format!("{} session(s) on {}", today_sessions, today),
+    );
+
+    let yesterday = config.yesterday();
+    let pending = manager.list_sessions(&yesterday).unwrap_or_default().len();
+    if manager.has_digest(&yesterday) {
+        print_line(true, "Yesterday", format!("digested ({})", yesterday));
+    } else if pending > 0 {
+        print_line(
+            false,
+            "Yesterday",
+            format!(
+                "{} session(s) not digested (run 'daily digest yest')",
+                pending
+            ),
+        );
+    } else {
+        print_line(true, "Yesterday", "no sessions".to_string());
+    }
+
+    // Auto-summarize
+    let last_run = config
+        .summarization
+        .last_auto_summarize_check
+        .as_deref()
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|t| t.with_timezone(&Local));
+    if !config.summarization.auto_summarize_enabled {
+        print_line(true, "Auto-summarize", "disabled".to_string());
+    } else if let Some(last_run) = last_run {
+        print_line(
+            true,
+            "Auto-summarize",
+            format!("last run {}", last_run.format("%Y-%m-%d %H:%M")),
+        );
+    } else {
+        print_line(true, "Auto-summarize", "never run".to_string());
+    }
+
+    println!();
+    println!("  Storage: {}", config.storage_path().display());
+
+    Ok(())
+}
+
+/// Print one status line with a check or warning marker
+fn print_line(ok: bool, label: &str, detail: String) {
+    let marker = if ok { "✓".green() } else { "!".yellow() };
+    println!("  {} {:<16} {}", marker, label, detail);
+}
+
+/// Check whether a Claude Code settings file registers both daily hooks
+pub fn hooks_installed(settings_file: &Path) -> bool {
+    let Ok(content) = fs::read_to_string(settings_file) else {
+        return false;
+    };
+    let Ok(settings) = serde_json::from_str::<Value>(&content) else {
+        return false;
+    };
+
+    [
+        ("SessionStart", "daily hook session-start"),
+        ("SessionEnd", "daily hook session-end"),
+    ]
+    .iter()
+    .all(|(event, command)| {
+        settings
+            .get("hooks")
+            .and_then(|h| h.get(event))
+            .and_then(|v| v.as_array())
+            .is_some_and(|hooks| has_daily_hook(hooks, command))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_hooks_installed() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = temp_dir.path().join("settings.json");
+        assert!(!hooks_installed(&settings));
+
+        let hook = |cmd: &str| {
+            format!(
+                r#"[{{"hooks": [{{"type": "command", "command": "{}"}}]}}]"#,
+                cmd
+            )
+        };
+        fs::write(
+            &settings,
+            format!(
+                r#"{{"hooks": {{"SessionStart": {}}}}}"#,
+                hook("daily hook session-start")
+            ),
+        )
+        .unwrap();
+        assert!(!hooks_installed(&settings));
+
+        fs::write(
+            &settings,
+            format!(
+                r#"{{"hooks": {{"SessionStart": {}, "SessionEnd": {}}}}}"#,
+                hook("daily hook session-start"),
+                hook("daily hook session-end")
+            ),
+        )
+        .unwrap();
+        assert!(hooks_installed(&settings));
+    }
+}
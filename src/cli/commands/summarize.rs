@@ -1,12 +1,55 @@
-use anyhow::{Context, Result};
-use std::fs;
-use std::path::PathBuf;
-use std::process::{Command, Stdio};
-
-use crate::config::load_config;
-use crate::jobs::JobManager;
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn, Instrument};
+
+use crate::archive::session::SessionOverrides;
+use crate::archive::skills::SkillStore;
+use crate::auto_summarize::{find_all_transcripts, record_auto_summarized, UnsummarizedTranscript};
+use crate::config::{load_config, Config};
+use crate::jobs::{JobInfo, JobManager, JobStatus, JobType};
 use crate::summarizer::SummarizerEngine;
-use crate::transcript::TranscriptParser;
+use crate::transcript::source::parse_transcript;
+use crate::transcript::SessionKind;
+
+/// Resolve the transcript to summarize from `--transcript` or `--session-id`
+pub fn resolve_transcript(
+    transcript: Option<PathBuf>,
+    session_id: Option<String>,
+) -> Result<PathBuf> {
+    match (transcript, session_id) {
+        (Some(transcript), _) => Ok(transcript),
+        (None, Some(session_id)) => {
+            let config = load_config()?;
+            find_session_transcript(find_all_transcripts(&config)?, &session_id)
+        }
+        (None, None) => bail!("Either --transcript or --session-id is required"),
+    }
+}
+
+/// Pick the transcript named `<session_id>.jsonl`, failing on zero or several matches
+fn find_session_transcript(transcripts: Vec<PathBuf>, session_id: &str) -> Result<PathBuf> {
+    let mut matches: Vec<PathBuf> = transcripts
+        .into_iter()
+        .filter(|path| path.file_stem().and_then(|s| s.to_str()) == Some(session_id))
+        .collect();
+
+    match matches.len() {
+        0 => bail!("No transcript found for session {}", session_id),
+        1 => Ok(matches.remove(0)),
+        _ => {
+            let candidates = matches
+                .iter()
+                .map(|path| format!("  {}", path.display()))
+                .collect::<Vec<_>>()
+                .join("\n");
+            bail!(
+                "Session {} matches several transcripts; pass one with --transcript:\n{}",
+                session_id,
+                candidates
+            )
+        }
+    }
+}
 
 /// Manually trigger summarization of a transcript
 pub async fn run(
@@ -15,8 +58,16 @@ pub async fn run(
     cwd: Option<PathBuf>,
     foreground: bool,
     job_id: Option<String>,
+    overrides: SessionOverrides,
+    model: Option<String>,
 ) -> Result<()> {
-    let config = load_config()?;
+    let mut config = load_config()?;
+    config.override_model(model.clone());
+
+    if let Some(date) = &overrides.date {
+        chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .context(format!("Invalid date format: {}. Use yyyy-mm-dd", date))?;
+    }
 
     // Generate task name if not provided
     let task_name = task_name.unwrap_or_else(|| {
@@ -24,8 +75,13 @@ pub async fn run(
         format!("session-{}", timestamp)
     });
 
+    // The background job and the archived transcript_path must not depend on where
+    // `daily` was run from
+    let transcript = std::path::absolute(&transcript).unwrap_or(transcript);
+
     // Use provided cwd, or fallback to current dir
     let cwd = cwd
+        .map(|p| std::path::absolute(&p).unwrap_or(p))
         .map(|p| p.to_string_lossy().to_string())
         .unwrap_or_else(|| {
             std::env::current_dir()
@@ -34,143 +90,252 @@ pub async fn run(
         });
 
     if !foreground {
-        // Background mode: spawn detached process
-        eprintln!(
-            "[daily] Starting background summarization for: {}",
-            task_name
-        );
-
-        // Re-invoke ourselves in foreground mode as a detached process
-        let exe = std::env::current_exe().context("Failed to get current executable")?;
-
-        let transcript_str = transcript.to_string_lossy().to_string();
-
-        // Build args with cwd
-        let args = vec![
-            "summarize".to_string(),
-            "--transcript".to_string(),
-            transcript_str,
-            "--task-name".to_string(),
-            task_name.clone(),
-            "--cwd".to_string(),
-            cwd.clone(),
-            "--foreground".to_string(),
-        ];
+        // Background mode: re-invoke ourselves in foreground mode as a tracked job, so
+        // it shows up in `daily jobs` and keeps this cwd
+        info!("Starting background summarization for: {}", task_name);
 
-        // Spawn detached background process
-        #[cfg(unix)]
-        {
-            // Use nohup-style spawning on Unix
-            Command::new(&exe)
-                .args(&args)
-                .stdin(Stdio::null())
-                .stdout(Stdio::null())
-                .stderr(Stdio::null())
-                .spawn()
-                .context("Failed to spawn background process")?;
+        let mut args = summarize_args(&transcript, &task_name, &cwd);
+        if let Some(title) = &overrides.title {
+            args.extend(["--title".to_string(), title.clone()]);
         }
-
-        #[cfg(windows)]
-        {
-            Command::new(&exe)
-                .args(&args)
-                .stdin(Stdio::null())
-                .stdout(Stdio::null())
-                .stderr(Stdio::null())
-                .spawn()
-                .context("Failed to spawn background process")?;
+        if let Some(date) = &overrides.date {
+            args.extend(["--date".to_string(), date.clone()]);
+        }
+        for tag in &overrides.tags {
+            args.extend(["--tag".to_string(), tag.clone()]);
+        }
+        if let Some(model) = &model {
+            args.extend(["--model".to_string(), model.clone()]);
         }
 
-        // Suppress unused variable warning
-        let _ = args;
-
-        eprintln!("[daily] Background summarization started");
+        let job_manager = JobManager::new(&config)?;
+        let job = spawn_background_summarize(
+            &config,
+            &job_manager,
+            &task_name,
+            &transcript,
+            args,
+            JobType::Manual,
+        )?;
+        let state = if job.status == JobStatus::Queued {
+            "queued"
+        } else {
+            "started"
+        };
+        println!(
+            "Summarizing in the background as job {} ({}). Follow progress with `daily jobs log {}`.",
+            job.id, state, job.id
+        );
         return Ok(());
     }
 
     // Foreground mode: do the actual summarization
-    eprintln!("[daily] Summarizing session: {}", task_name);
+    info!("Summarizing session: {}", task_name);
 
     // Initialize job manager for status updates
     let job_manager = JobManager::new(&config).ok();
 
     // Run summarization with job status tracking
-    let result = run_summarization(&config, &transcript, &task_name, &cwd).await;
+    let result = run_summarization(
+        &config,
+        &transcript,
+        &task_name,
+        &cwd,
+        &overrides,
+        job_id.clone(),
+    )
+    .instrument(tracing::info_span!("summarize", task = %task_name))
+    .await;
 
     // Update job status based on result
     if let (Some(ref manager), Some(ref id)) = (&job_manager, &job_id) {
         match &result {
             Ok(_) => {
                 if let Err(e) = manager.mark_completed(id) {
-                    eprintln!("[daily] Warning: Failed to update job status: {}", e);
+                    warn!("Failed to update job status: {}", e);
                 }
             }
             Err(e) => {
                 if let Err(update_err) = manager.mark_failed(id, &e.to_string()) {
-                    eprintln!(
-                        "[daily] Warning: Failed to update job status: {}",
-                        update_err
-                    );
+                    warn!("Failed to update job status: {}", update_err);
                 }
             }
         }
 
         // Truncate log if needed
         let _ = manager.truncate_log_if_needed(id);
+
+        // This job's slot is free now; start whatever is waiting for it
+        if let Err(e) = manager.run_queue(config.summarization.max_concurrent_jobs) {
+            warn!("Failed to start queued jobs: {}", e);
+        }
     }
 
     result
 }
 
+/// Arguments for a foreground `daily summarize` of a transcript
+pub fn summarize_args(transcript: &Path, task_name: &str, cwd: &str) -> Vec<String> {
+    vec![
+        "summarize".to_string(),
+        "--transcript".to_string(),
+        transcript.to_string_lossy().to_string(),
+        "--task-name".to_string(),
+        task_name.to_string(),
+        "--cwd".to_string(),
+        cwd.to_string(),
+        "--foreground".to_string(),
+    ]
+}
+
+/// Run `daily <args>` as a tracked background summarization job.
+///
+/// When `summarization.max_concurrent_jobs` jobs are already running, the job is
+/// queued instead and started once a slot frees (or by `daily jobs run-queue`).
+pub fn spawn_background_summarize(
+    config: &Config,
+    job_manager: &JobManager,
+    task_name: &str,
+    transcript: &Path,
+    args: Vec<String>,
+    job_type: JobType,
+) -> Result<JobInfo> {
+    let job_id = JobManager::generate_job_id(task_name);
+    let info = job_manager.enqueue(&job_id, task_name, transcript, job_type, args, None)?;
+    start_if_free(config, job_manager, info)
+}
+
+/// Start a queued job if `summarization.max_concurrent_jobs` leaves a slot, else keep it
+/// queued
+pub fn start_if_free(config: &Config, job_manager: &JobManager, info: JobInfo) -> Result<JobInfo> {
+    // Only running jobs take a slot, so the queued job itself doesn't count
+    if job_manager.free_slots(config.summarization.max_concurrent_jobs)? == 0 {
+        return Ok(info);
+    }
+
+    job_manager.start(&info).inspect_err(|e| {
+        let _ = job_manager.mark_failed(&info.id, &e.to_string());
+    })
+}
+
+/// Spawn (or queue) a summarize job per auto-detected transcript and count them
+/// against today's `daily_summarize_budget`. Returns how many jobs were created.
+pub fn spawn_auto_summarize(
+    config: &Config,
+    transcripts: Vec<UnsummarizedTranscript>,
+) -> Result<usize> {
+    let job_manager = JobManager::new(config)?;
+    let mut spawned_count = 0;
+
+    for transcript in transcripts {
+        let task_name = format!("auto-{}", transcript.session_id);
+
+        // Default CWD for auto-summarize jobs
+        let cwd_str = transcript
+            .cwd
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|| ".".to_string());
+
+        let args = summarize_args(&transcript.path, &task_name, &cwd_str);
+        match spawn_background_summarize(
+            config,
+            &job_manager,
+            &task_name,
+            &transcript.path,
+            args,
+            JobType::AutoSummarize,
+        ) {
+            Ok(_) => spawned_count += 1,
+            Err(e) => warn!(
+                "Failed to spawn auto-summarize process for {}: {}",
+                transcript.session_id, e
+            ),
+        }
+    }
+
+    if spawned_count > 0 {
+        record_auto_summarized(config, spawned_count)?;
+    }
+
+    Ok(spawned_count)
+}
+
+/// Print the session summary prompt without calling Claude or writing anything
+pub async fn run_dry_run(transcript: PathBuf, cwd: Option<PathBuf>) -> Result<()> {
+    let config = load_config()?;
+
+    let cwd = cwd
+        .or_else(|| std::env::current_dir().ok())
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|| ".".to_string());
+
+    let transcript_data =
+        parse_transcript(&config, &transcript).context("Failed to parse transcript")?;
+    let engine = SummarizerEngine::new(config);
+    println!("{}", engine.session_summary_prompt(&transcript_data, &cwd)?);
+    Ok(())
+}
+
 /// Run the actual summarization logic
 async fn run_summarization(
     config: &crate::config::Config,
-    transcript: &PathBuf,
+    transcript: &Path,
     task_name: &str,
     cwd: &str,
+    overrides: &SessionOverrides,
+    job_id: Option<String>,
 ) -> Result<()> {
     // Check if transcript file exists before attempting to parse
     if !transcript.exists() {
-        eprintln!(
-            "[daily] Transcript file not found, skipping: {}",
+        info!(
+            "Transcript file not found, skipping: {}",
             transcript.display()
         );
         return Ok(());
     }
 
+    // Sessions inside a project with storage.project_overrides archive there
+    let config = &config.for_project(Path::new(cwd));
+
     // Check if session is empty before summarizing
     let transcript_data =
-        TranscriptParser::parse(transcript).context("Failed to parse transcript")?;
+        parse_transcript(config, transcript).context("Failed to parse transcript")?;
 
-    if transcript_data.is_empty() {
-        eprintln!("[daily] Session is empty, skipping summarization");
+    if transcript_data.classify_session(&config.summarization.meaningful_tools)
+        == SessionKind::Empty
+    {
+        info!("Session is empty, skipping summarization");
         return Ok(());
     }
 
-    let engine = SummarizerEngine::new(config.clone());
+    let engine = SummarizerEngine::new(config.clone()).with_job_id(job_id);
 
     // Summarize the session
-    let archive = engine
-        .summarize_session(transcript, task_name, cwd)
+    let Some(archive) = engine
+        .summarize_session(transcript, task_name, cwd, overrides)
         .await
-        .context("Failed to summarize session")?;
+        .context("Failed to summarize session")?
+    else {
+        return Ok(());
+    };
 
     // Save the archive
     let archive_path = archive.save(config)?;
-    eprintln!("[daily] Session archived: {}", archive_path.display());
+    info!("Session archived: {}", archive_path.display());
 
-    // Auto-evaluate skill extraction (沉淀三问 quality gate)
+    // Auto-evaluate skill extraction (three-question quality gate)
     if should_extract_skill(&archive.skill_hints) {
-        eprintln!("[daily] Skill candidate detected, attempting extraction...");
+        info!("Skill candidate detected, attempting extraction...");
         match auto_extract_skill(&engine, &archive, config).await {
             Ok(Some(skill_path)) => {
-                eprintln!("[daily] Pending skill saved: {}", skill_path.display());
+                info!("Pending skill saved: {}", skill_path.display());
             }
             Ok(None) => {
-                eprintln!("[daily] Skill did not pass quality gate, skipped");
+                info!("Skill did not pass quality gate, skipped");
             }
             Err(e) => {
-                eprintln!("[daily] Skill extraction failed: {}", e);
+                warn!("Skill extraction failed: {}", e);
             }
         }
     }
@@ -178,7 +343,7 @@ async fn run_summarization(
     // Note: Daily summary is now generated via `daily digest` command
     // either manually or auto-triggered on session start
 
-    eprintln!("[daily] Summarization complete!");
+    info!("Summarization complete!");
 
     Ok(())
 }
@@ -209,7 +374,7 @@ async fn auto_extract_skill(
     // Build context from archive
     let session_content = archive.to_markdown();
 
-    // Extract skill (will apply 沉淀三问 quality gate)
+    // Extract skill (will apply the three-question quality gate)
     let skill_content = engine
         .extract_skill(&session_content, Some(&archive.skill_hints))
         .await?;
@@ -219,19 +384,9 @@ async fn auto_extract_skill(
         return Ok(None);
     }
 
-    // Save to pending-skills directory
-    let pending_dir = config
-        .storage
-        .path
-        .join("pending-skills")
-        .join(&archive.date);
-    fs::create_dir_all(&pending_dir)?;
-
-    // Extract skill name from content
+    // Save to pending-skills/<date>/<name>/SKILL.md for review
     let skill_name = extract_skill_name(&skill_content);
-    let skill_file = pending_dir.join(format!("{}.md", skill_name));
-
-    fs::write(&skill_file, &skill_content)?;
+    let skill_file = SkillStore::new(config).save(&archive.date, &skill_name, &skill_content)?;
 
     Ok(Some(skill_file))
 }
@@ -252,3 +407,31 @@ fn extract_skill_name(content: &str) -> String {
     let timestamp = chrono::Local::now().format("%H%M%S");
     format!("skill-{}", timestamp)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_session_transcript() {
+        let transcripts = vec![
+            PathBuf::from("/p/a/abc-123.jsonl"),
+            PathBuf::from("/p/b/def-456.jsonl"),
+            PathBuf::from("/p/c/def-456.jsonl"),
+        ];
+
+        assert_eq!(
+            find_session_transcript(transcripts.clone(), "abc-123").unwrap(),
+            PathBuf::from("/p/a/abc-123.jsonl")
+        );
+        assert!(find_session_transcript(transcripts.clone(), "missing")
+            .unwrap_err()
+            .to_string()
+            .contains("No transcript found"));
+
+        let err = find_session_transcript(transcripts, "def-456")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("/p/b/def-456.jsonl") && err.contains("/p/c/def-456.jsonl"));
+    }
+}
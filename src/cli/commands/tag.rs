@@ -0,0 +1,98 @@
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+use colored::*;
+use serde::Serialize;
+
+use crate::archive::ArchiveManager;
+use crate::config::load_config;
+
+/// Run the tag command - rename or add tags in session archives
+pub async fn run(
+    rename: Option<Vec<String>>,
+    add: Option<String>,
+    date: Option<String>,
+) -> Result<()> {
+    let config = load_config()?;
+    let manager = ArchiveManager::new(config);
+
+    if let Some(names) = rename {
+        let [old, new] = names.as_slice() else {
+            bail!("--rename takes exactly two tags: OLD NEW");
+        };
+        validate_tag(new)?;
+
+        let changed = manager.retag(old, new)?;
+        println!(
+            "{} Renamed tag {} to {} in {} session(s)",
+            "✓".green(),
+            old.cyan(),
+            new.cyan(),
+            changed
+        );
+        return Ok(());
+    }
+
+    if let Some(tag) = add {
+        // clap enforces --date alongside --add
+        let date = date.context("--add requires --date")?;
+        NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+            .context(format!("Invalid date format: {}. Use yyyy-mm-dd", date))?;
+        validate_tag(&tag)?;
+
+        let changed = manager.add_tag(&tag, &date)?;
+        println!(
+            "{} Added tag {} to {} session(s) on {}",
+            "✓".green(),
+            tag.cyan(),
+            changed,
+            date
+        );
+        return Ok(());
+    }
+
+    bail!("Nothing to do. Use --rename OLD NEW or --add TAG --date DATE")
+}
+
+/// A tag and the number of sessions carrying it, as printed by `daily tags --json`
+#[derive(Serialize)]
+struct TagCountJson {
+    tag: String,
+    count: usize,
+}
+
+/// List all session tags with their counts, most used first
+pub async fn list(json: bool) -> Result<()> {
+    let config = load_config()?;
+    let manager = ArchiveManager::new(config);
+
+    let counts = manager.tag_counts()?;
+
+    if json {
+        let counts: Vec<TagCountJson> = counts
+            .into_iter()
+            .map(|(tag, count)| TagCountJson { tag, count })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&counts)?);
+        return Ok(());
+    }
+
+    if counts.is_empty() {
+        println!("{}", "No tags found.".yellow());
+        return Ok(());
+    }
+
+    let width = counts.iter().map(|(tag, _)| tag.len()).max().unwrap_or(0);
+    for (tag, count) in &counts {
+        println!("  {}  {}", format!("{:<width$}", tag).cyan(), count);
+    }
+
+    Ok(())
+}
+
+/// Reject tags that would break the inline frontmatter list
+fn validate_tag(tag: &str) -> Result<()> {
+    if tag.trim().is_empty() || tag.contains([',', '[', ']', '\n']) {
+        bail!("Invalid tag: {:?}", tag);
+    }
+    Ok(())
+}
@@ -110,6 +110,21 @@ fn remove_daily_hooks(settings: &mut Value) -> bool {
             }
         }
 
+        // Remove daily hooks from UserPromptSubmit
+        if let Some(user_prompt) = hooks.get_mut("UserPromptSubmit") {
+            if let Some(arr) = user_prompt.as_array_mut() {
+                let original_len = arr.len();
+                arr.retain(|entry| !is_daily_hook_entry(entry, "daily hook user-prompt-submit"));
+                if arr.len() != original_len {
+                    changed = true;
+                }
+                // Remove the event entirely if no hooks remain
+                if arr.is_empty() {
+                    hooks.remove("UserPromptSubmit");
+                }
+            }
+        }
+
         // Remove hooks object entirely if empty
         if hooks.is_empty() {
             settings.as_object_mut().unwrap().remove("hooks");
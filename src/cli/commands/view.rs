@@ -1,19 +1,51 @@
-use anyhow::Result;
-use chrono::{Duration, Local};
+use anyhow::{bail, Context, Result};
 use colored::*;
-use dialoguer::{theme::ColorfulTheme, FuzzySelect};
-
-use crate::archive::ArchiveManager;
-use crate::config::load_config;
+use dialoguer::{theme::ColorfulTheme, Confirm, FuzzySelect};
+use serde::Serialize;
+
+use crate::archive::{ArchiveManager, SessionLink};
+use crate::cli::commands::show;
+use crate::config::{load_config, Config};
+use crate::server::dto::{DailySummaryDto, SessionBrief, SessionRefDto};
+use crate::server::handlers::{parse_daily_summary, session_brief, session_detail};
+
+/// A date's archive as printed by `daily view --json`
+#[derive(Serialize)]
+struct DateArchiveJson {
+    date: String,
+    daily_summary: Option<DailySummaryDto>,
+    sessions: Vec<SessionBrief>,
+}
 
 /// View archives with interactive selection
-pub async fn run(date: Option<String>, summary_only: bool, list: bool) -> Result<()> {
+pub async fn run(
+    date: Option<String>,
+    summary_only: bool,
+    list: bool,
+    open_web: bool,
+    json: bool,
+) -> Result<()> {
     let config = load_config()?;
-    let manager = ArchiveManager::new(config);
+    let manager = ArchiveManager::new(config.clone());
+
+    if open_web && json {
+        bail!("--open-web opens the browser and can't print JSON");
+    }
 
     // If date is provided, view that date directly
     if let Some(view_date) = date {
-        return view_date_archive(&manager, &view_date, summary_only, list).await;
+        if open_web {
+            return open_web_view(&config, &view_date).await;
+        }
+        view_date_archive(&manager, &view_date, summary_only, list, json).await?;
+        if !json {
+            print_web_link(&config, &view_date).await;
+        }
+        return Ok(());
+    }
+
+    if json {
+        bail!("--json needs --date; interactive date selection can't print JSON");
     }
 
     // Otherwise, show interactive date selection
@@ -30,7 +62,7 @@ pub async fn run(date: Option<String>, summary_only: bool, list: bool) -> Result
         .map(|d| {
             let sessions = manager.list_sessions(d).unwrap_or_default();
             let count = sessions.len();
-            let label = format_date_label(d);
+            let label = format_date_label(&config, d);
             format!("{} {} ({} sessions)", d, label, count)
         })
         .collect();
@@ -44,8 +76,13 @@ pub async fn run(date: Option<String>, summary_only: bool, list: bool) -> Result
     match selection {
         Some(idx) => {
             let view_date = &dates[idx];
+            if open_web {
+                return open_web_view(&config, view_date).await;
+            }
             println!();
-            view_date_archive(&manager, view_date, summary_only, list).await
+            view_date_archive(&manager, view_date, summary_only, list, false).await?;
+            print_web_link(&config, view_date).await;
+            Ok(())
         }
         None => {
             println!("{}", "Cancelled.".dimmed());
@@ -54,34 +91,263 @@ pub async fn run(date: Option<String>, summary_only: bool, list: bool) -> Result
     }
 }
 
+/// Show each archived day of the last `days` days, newest first
+pub async fn run_all(days: u32, json: bool) -> Result<()> {
+    let config = load_config()?;
+    let manager = ArchiveManager::new(config.clone());
+    let dates = recent_dates(manager.list_dates()?, &config.today(), days)?;
+
+    if json {
+        let archives = dates
+            .iter()
+            .map(|date| date_archive_json(&manager, date))
+            .collect::<Result<Vec<_>>>()?;
+        println!("{}", serde_json::to_string_pretty(&archives)?);
+        return Ok(());
+    }
+
+    if dates.is_empty() {
+        println!(
+            "{}",
+            format!("No archives in the last {} days.", days).yellow()
+        );
+        return Ok(());
+    }
+
+    for (i, date) in dates.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        show_full_archive(&manager, date).await?;
+    }
+    print_session_hint();
+    Ok(())
+}
+
+/// Dates (newest first) falling within the `days` days ending on `today`
+fn recent_dates(dates: Vec<String>, today: &str, days: u32) -> Result<Vec<String>> {
+    let today = chrono::NaiveDate::parse_from_str(today, "%Y-%m-%d")?;
+    let first = (today - chrono::Duration::days(i64::from(days.max(1)) - 1))
+        .format("%Y-%m-%d")
+        .to_string();
+    let today = today.format("%Y-%m-%d").to_string();
+    // yyyy-mm-dd sorts chronologically as a string
+    Ok(dates
+        .into_iter()
+        .filter(|date| *date >= first && *date <= today)
+        .collect())
+}
+
+/// Show the chain of resumed sessions containing a session
+pub async fn run_chain(date: Option<String>, session: String, json: bool) -> Result<()> {
+    let config = load_config()?;
+    let manager = ArchiveManager::new(config);
+
+    let Some(target) = resolve_session(&manager, date.as_deref(), &session)? else {
+        if json {
+            bail!("Session not found: {}", session);
+        }
+        println!("{}", format!("Session not found: {}", session).yellow());
+        return Ok(());
+    };
+
+    let chain = manager.resume_chain(&target.date, &target.name)?;
+
+    if json {
+        let chain: Vec<SessionRefDto> = chain.into_iter().map(Into::into).collect();
+        println!("{}", serde_json::to_string_pretty(&chain)?);
+        return Ok(());
+    }
+
+    println!("{}", "Resume Chain".cyan().bold());
+    println!("{}", "=".repeat(50));
+    println!();
+
+    for (i, link) in chain.iter().enumerate() {
+        let marker = if *link == target {
+            "●".green()
+        } else {
+            "○".dimmed()
+        };
+        println!(
+            "  {} {}. {} {}",
+            marker,
+            (i + 1).to_string().green(),
+            link.title,
+            format!("({}/{})", link.date, link.name).dimmed()
+        );
+    }
+
+    println!();
+    if chain.len() == 1 {
+        println!(
+            "{}",
+            "This session was not resumed from or into another session.".dimmed()
+        );
+    } else {
+        println!("Total: {} sessions", chain.len());
+    }
+
+    Ok(())
+}
+
+/// List sessions carrying a tag, on one date or across all dates
+pub async fn run_tag(date: Option<String>, tag: String, json: bool) -> Result<()> {
+    let config = load_config()?;
+    let manager = ArchiveManager::new(config);
+
+    let sessions = manager.sessions_with_tag(&tag, date.as_deref())?;
+
+    if json {
+        let sessions: Vec<SessionRefDto> = sessions.into_iter().map(Into::into).collect();
+        println!("{}", serde_json::to_string_pretty(&sessions)?);
+        return Ok(());
+    }
+
+    if sessions.is_empty() {
+        println!("{}", format!("No sessions tagged {}", tag).yellow());
+        return Ok(());
+    }
+
+    println!("{}", format!("Sessions tagged {}:", tag).cyan().bold());
+    println!();
+
+    for (i, link) in sessions.iter().enumerate() {
+        println!(
+            "  {}. {} {}",
+            (i + 1).to_string().green(),
+            link.title,
+            format!("({}/{})", link.date, link.name).dimmed()
+        );
+    }
+
+    println!();
+    println!("Total: {} sessions", sessions.len());
+
+    Ok(())
+}
+
+/// Print a single session archive in full
+pub async fn run_session(date: Option<String>, session: String, json: bool) -> Result<()> {
+    let config = load_config()?;
+    let date = date.unwrap_or_else(|| config.today());
+    let manager = ArchiveManager::new(config);
+
+    let sessions = manager.list_sessions(&date)?;
+    if !sessions.contains(&session) {
+        if json {
+            match closest_match(&session, &sessions) {
+                Some(closest) => bail!(
+                    "Session not found on {}: {} (did you mean {}?)",
+                    date,
+                    session,
+                    closest
+                ),
+                None => bail!("Session not found on {}: {}", date, session),
+            }
+        }
+        println!(
+            "{}",
+            format!("Session not found on {}: {}", date, session).yellow()
+        );
+        if let Some(closest) = closest_match(&session, &sessions) {
+            println!("Did you mean {}?", closest.cyan());
+        }
+        return Ok(());
+    }
+
+    let content = manager.read_session(&date, &session)?;
+
+    if json {
+        let detail = session_detail(&manager, &date, session, content);
+        println!("{}", serde_json::to_string_pretty(&detail)?);
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("Session - {} ({})", session, date).cyan().bold()
+    );
+    println!("{}", "=".repeat(50));
+    println!();
+
+    for line in content.lines() {
+        if line.starts_with('#') {
+            println!("{}", line.cyan().bold());
+        } else {
+            println!("{}", line);
+        }
+    }
+
+    Ok(())
+}
+
+/// Closest candidate by edit distance, if it is near enough to be a plausible typo
+fn closest_match<'a>(name: &str, candidates: &'a [String]) -> Option<&'a str> {
+    candidates
+        .iter()
+        .map(|c| (levenshtein(name, c), c))
+        .min_by_key(|(distance, _)| *distance)
+        .filter(|(distance, c)| *distance <= name.chars().count().max(c.chars().count()) / 2)
+        .map(|(_, c)| c.as_str())
+}
+
+/// Levenshtein edit distance between two strings
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+
+    prev[b.len()]
+}
+
+/// Resolve a session given as DATE/NAME, a name (optionally scoped by date), or a session ID
+fn resolve_session(
+    manager: &ArchiveManager,
+    date: Option<&str>,
+    session: &str,
+) -> Result<Option<SessionLink>> {
+    let (date, name) = match session.split_once('/') {
+        Some((d, n)) => (Some(d), n),
+        None => (date, session),
+    };
+
+    // Latest match wins when a name appears on several dates
+    Ok(manager.session_links()?.into_iter().rev().find(|link| {
+        let date_matches = date.is_none_or(|d| link.date == d);
+        date_matches && (link.name == name || link.session_id.as_deref() == Some(name))
+    }))
+}
+
 /// View today's archive
-pub async fn run_today(summary_only: bool, list: bool) -> Result<()> {
+pub async fn run_today(summary_only: bool, list: bool, json: bool) -> Result<()> {
     let config = load_config()?;
+    let today = config.today();
     let manager = ArchiveManager::new(config);
-    let today = Local::now().format("%Y-%m-%d").to_string();
-    view_date_archive(&manager, &today, summary_only, list).await
+    view_date_archive(&manager, &today, summary_only, list, json).await
 }
 
 /// View yesterday's archive
-pub async fn run_yesterday(summary_only: bool, list: bool) -> Result<()> {
+pub async fn run_yesterday(summary_only: bool, list: bool, json: bool) -> Result<()> {
     let config = load_config()?;
+    let yesterday = config.yesterday();
     let manager = ArchiveManager::new(config);
-    let yesterday = (Local::now() - Duration::days(1))
-        .format("%Y-%m-%d")
-        .to_string();
-    view_date_archive(&manager, &yesterday, summary_only, list).await
+    view_date_archive(&manager, &yesterday, summary_only, list, json).await
 }
 
 /// Format date with relative label (today, yesterday, etc.)
-fn format_date_label(date: &str) -> String {
-    let today = Local::now().format("%Y-%m-%d").to_string();
-    let yesterday = (Local::now() - Duration::days(1))
-        .format("%Y-%m-%d")
-        .to_string();
-
-    if date == today {
+fn format_date_label(config: &Config, date: &str) -> String {
+    if date == config.today() {
         "(today)".green().to_string()
-    } else if date == yesterday {
+    } else if date == config.yesterday() {
         "(yesterday)".cyan().to_string()
     } else {
         String::new()
@@ -94,7 +360,12 @@ async fn view_date_archive(
     date: &str,
     summary_only: bool,
     list: bool,
+    json: bool,
 ) -> Result<()> {
+    if json {
+        return print_date_archive_json(manager, date, summary_only, list);
+    }
+
     if list {
         return list_sessions(manager, date).await;
     }
@@ -103,7 +374,94 @@ async fn view_date_archive(
         return show_daily_summary(manager, date).await;
     }
 
-    show_full_archive(manager, date).await
+    if show_full_archive(manager, date).await? > 0 {
+        print_session_hint();
+    }
+    Ok(())
+}
+
+/// Dashboard page of a date
+fn date_web_path(date: &str) -> String {
+    format!("/day/{}", date)
+}
+
+/// Point at the date's dashboard page when a `daily show` server is running
+async fn print_web_link(config: &Config, date: &str) {
+    if let Some(server) = show::running_server(config).await {
+        let url = server.url(&date_web_path(date), config.server.auth_token.as_deref());
+        println!();
+        println!("{} {}", "Web:".dimmed(), url.cyan());
+    }
+}
+
+/// Open the date in the running dashboard, or offer to start one that opens it
+async fn open_web_view(config: &Config, date: &str) -> Result<()> {
+    if let Some(server) = show::running_server(config).await {
+        let url = server.url(&date_web_path(date), config.server.auth_token.as_deref());
+        println!("{} {}", "Opening".green(), url.cyan());
+        return open::that(&url).context("Failed to open browser");
+    }
+
+    let start = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("No dashboard is running. Start one?")
+        .default(true)
+        .interact()?;
+    if !start {
+        println!("{}", "Cancelled.".dimmed());
+        return Ok(());
+    }
+    show::run(
+        None,
+        "127.0.0.1".to_string(),
+        Some(date_web_path(date)),
+        None,
+    )
+    .await
+}
+
+/// Print a date's archive as JSON: the session list, the daily summary, or both
+fn print_date_archive_json(
+    manager: &ArchiveManager,
+    date: &str,
+    summary_only: bool,
+    list: bool,
+) -> Result<()> {
+    let output = if list {
+        serde_json::to_string_pretty(&session_briefs(manager, date)?)?
+    } else if summary_only {
+        serde_json::to_string_pretty(&daily_summary_dto(manager, date))?
+    } else {
+        serde_json::to_string_pretty(&date_archive_json(manager, date)?)?
+    };
+    println!("{}", output);
+    Ok(())
+}
+
+fn date_archive_json(manager: &ArchiveManager, date: &str) -> Result<DateArchiveJson> {
+    Ok(DateArchiveJson {
+        date: date.to_string(),
+        daily_summary: daily_summary_dto(manager, date),
+        sessions: session_briefs(manager, date)?,
+    })
+}
+
+fn daily_summary_dto(manager: &ArchiveManager, date: &str) -> Option<DailySummaryDto> {
+    manager.read_daily_summary(date).ok().map(|content| {
+        let mut dto = parse_daily_summary(date, &content);
+        dto.file_path = manager
+            .daily_summary_path(date)
+            .to_string_lossy()
+            .to_string();
+        dto
+    })
+}
+
+fn session_briefs(manager: &ArchiveManager, date: &str) -> Result<Vec<SessionBrief>> {
+    Ok(manager
+        .list_sessions(date)?
+        .into_iter()
+        .filter_map(|name| session_brief(manager, date, name))
+        .collect())
 }
 
 async fn list_sessions(manager: &ArchiveManager, date: &str) -> Result<()> {
@@ -146,7 +504,8 @@ async fn show_daily_summary(manager: &ArchiveManager, date: &str) -> Result<()>
     }
 }
 
-async fn show_full_archive(manager: &ArchiveManager, date: &str) -> Result<()> {
+/// Print a date's overview and sessions; returns the number of sessions
+async fn show_full_archive(manager: &ArchiveManager, date: &str) -> Result<usize> {
     // Show daily summary first
     println!("{}", format!("Daily Archive - {}", date).cyan().bold());
     println!("{}", "=".repeat(50));
@@ -175,7 +534,7 @@ async fn show_full_archive(manager: &ArchiveManager, date: &str) -> Result<()> {
 
     if sessions.is_empty() {
         println!("{}", "No sessions archived yet.".yellow());
-        return Ok(());
+        return Ok(0);
     }
 
     println!("{}", "Sessions:".green().bold());
@@ -198,11 +557,54 @@ async fn show_full_archive(manager: &ArchiveManager, date: &str) -> Result<()> {
         }
     }
 
+    Ok(sessions.len())
+}
+
+fn print_session_hint() {
     println!();
     println!(
         "Use {} to see a specific session",
-        "daily view --date DATE".cyan()
+        "daily view --date DATE --session NAME".cyan()
     );
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recent_dates() {
+        let dates = ["2026-01-20", "2026-01-16", "2026-01-14", "2026-01-13"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(
+            recent_dates(dates.clone(), "2026-01-20", 7).unwrap(),
+            ["2026-01-20", "2026-01-16", "2026-01-14"]
+        );
+        assert_eq!(
+            recent_dates(dates, "2026-01-19", 1).unwrap(),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("fix-bug", "fix-bug"), 0);
+    }
+
+    #[test]
+    fn test_closest_match() {
+        let sessions = vec![
+            "fix-bug-143052".to_string(),
+            "new-feature-152310".to_string(),
+        ];
+        assert_eq!(
+            closest_match("fix-bug-14305", &sessions),
+            Some("fix-bug-143052")
+        );
+        assert_eq!(closest_match("refactor", &sessions), None);
+        assert_eq!(closest_match("anything", &[]), None);
+    }
 }
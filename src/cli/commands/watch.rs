@@ -0,0 +1,224 @@
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, info, warn};
+
+use crate::auto_summarize::{
+    auto_summarize_limit, filter_unsummarized, find_all_transcripts, try_lock_summarize,
+    UnsummarizedTranscript,
+};
+use crate::cli::commands::show::shutdown_signal;
+use crate::cli::commands::summarize::spawn_auto_summarize;
+use crate::config::{load_config, Config};
+use crate::transcript::source::configured_sources;
+
+/// How often changed transcripts are checked for having gone quiet
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Watch transcript directories and summarize each session once it stops changing
+pub async fn run() -> Result<()> {
+    let config = load_config()?;
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let _ = tx.send(event);
+    })
+    .context("Failed to start file watcher")?;
+
+    let mut watched = 0;
+    for source in configured_sources(&config) {
+        if !source.root().is_dir() {
+            continue;
+        }
+        watcher
+            .watch(source.root(), RecursiveMode::Recursive)
+            .context(format!("Failed to watch {}", source.root().display()))?;
+        println!(
+            "{} Watching {} transcripts in {}",
+            "✓".green(),
+            source.name(),
+            source.root().display()
+        );
+        watched += 1;
+    }
+    if watched == 0 {
+        bail!("No transcript directories to watch; check summarization.sources");
+    }
+    println!(
+        "Sessions are summarized after {} minutes without changes (Ctrl+C to stop)",
+        config.summarization.auto_summarize_inactive_minutes
+    );
+
+    // Transcript → when it last changed
+    let mut changed: HashMap<PathBuf, Instant> = HashMap::new();
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+
+    loop {
+        tokio::select! {
+            Some(event) = rx.recv() => match event {
+                Ok(event) => {
+                    if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                        for path in event.paths {
+                            if path.extension().is_some_and(|ext| ext == "jsonl") {
+                                changed.insert(path, Instant::now());
+                            }
+                        }
+                    }
+                }
+                Err(e) => warn!("File watcher error: {}", e),
+            },
+            _ = interval.tick() => {
+                // Reloaded each time so config changes apply without a restart
+                let config = match load_config() {
+                    Ok(config) => config,
+                    Err(e) => {
+                        warn!("Failed to load config: {}", e);
+                        continue;
+                    }
+                };
+                if let Err(e) = summarize_quiet(&config, &mut changed) {
+                    warn!("Auto-summarize failed: {}", e);
+                }
+            }
+            _ = &mut shutdown => {
+                println!("Watch stopped");
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Remove and return the transcripts unchanged for at least `quiet`, with when they last
+/// changed
+fn take_quiet(
+    changed: &mut HashMap<PathBuf, Instant>,
+    quiet: Duration,
+    now: Instant,
+) -> Vec<(PathBuf, Instant)> {
+    let ready: Vec<(PathBuf, Instant)> = changed
+        .iter()
+        .filter(|(_, last)| now.saturating_duration_since(**last) >= quiet)
+        .map(|(path, last)| (path.clone(), *last))
+        .collect();
+    for (path, _) in &ready {
+        changed.remove(path);
+    }
+    ready
+}
+
+/// Put back the quiet transcripts that weren't picked, so the next check sees them again
+fn requeue_unpicked(
+    changed: &mut HashMap<PathBuf, Instant>,
+    ready: Vec<(PathBuf, Instant)>,
+    picked: &[UnsummarizedTranscript],
+) {
+    for (path, last) in ready {
+        if !picked.iter().any(|transcript| transcript.path == path) {
+            changed.entry(path).or_insert(last);
+        }
+    }
+}
+
+/// Spawn summarize jobs for quiet transcripts that pass the auto-summarize guards
+fn summarize_quiet(config: &Config, changed: &mut HashMap<PathBuf, Instant>) -> Result<()> {
+    // A `daily show` is spawning jobs right now; try again on the next check
+    let Some(_lock) = try_lock_summarize(config)? else {
+        return Ok(());
+    };
+
+    let quiet = Duration::from_secs(config.summarization.auto_summarize_inactive_minutes * 60);
+    let ready = take_quiet(changed, quiet, Instant::now());
+    if ready.is_empty() {
+        return Ok(());
+    }
+
+    // Only files a source would discover, so agent transcripts are left alone
+    let transcripts: Vec<PathBuf> = find_all_transcripts(config)?
+        .into_iter()
+        .filter(|path| ready.iter().any(|(quiet, _)| quiet == path))
+        .collect();
+    debug!(
+        "{} quiet transcript(s), {} from a source",
+        ready.len(),
+        transcripts.len()
+    );
+
+    let limit = auto_summarize_limit(config);
+    let unsummarized = filter_unsummarized(config, transcripts)?;
+    // The limit or today's budget may have stopped the scan early; retry the rest later
+    if unsummarized.len() >= limit {
+        requeue_unpicked(changed, ready, &unsummarized);
+    }
+    if unsummarized.is_empty() {
+        return Ok(());
+    }
+    let spawned = spawn_auto_summarize(config, unsummarized)?;
+    info!("Started {} summarize job(s)", spawned);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_quiet() {
+        let start = Instant::now();
+        let mut changed = HashMap::new();
+        changed.insert(PathBuf::from("a.jsonl"), start);
+        changed.insert(PathBuf::from("b.jsonl"), start + Duration::from_secs(50));
+
+        let quiet = Duration::from_secs(60);
+        assert!(take_quiet(&mut changed, quiet, start + Duration::from_secs(30)).is_empty());
+        assert_eq!(
+            take_quiet(&mut changed, quiet, start + Duration::from_secs(90)),
+            vec![(PathBuf::from("a.jsonl"), start)]
+        );
+        assert_eq!(changed.len(), 1);
+        assert_eq!(
+            take_quiet(&mut changed, quiet, start + Duration::from_secs(110)),
+            vec![(PathBuf::from("b.jsonl"), start + Duration::from_secs(50))]
+        );
+        assert!(changed.is_empty());
+    }
+
+    #[test]
+    fn test_requeue_unpicked_over_limit() {
+        let start = Instant::now();
+        let mut changed: HashMap<PathBuf, Instant> = (0..5)
+            .map(|i| (PathBuf::from(format!("{}.jsonl", i)), start))
+            .collect();
+        let quiet = Duration::from_secs(60);
+        let ready = take_quiet(&mut changed, quiet, start + quiet);
+        assert_eq!(ready.len(), 5);
+
+        // Only three fit under max_auto_summarize
+        let picked: Vec<UnsummarizedTranscript> = ready[..3]
+            .iter()
+            .map(|(path, _)| UnsummarizedTranscript {
+                path: path.clone(),
+                session_id: String::new(),
+                cwd: None,
+            })
+            .collect();
+        let mut left: Vec<PathBuf> = ready[3..].iter().map(|(path, _)| path.clone()).collect();
+        left.sort();
+        requeue_unpicked(&mut changed, ready, &picked);
+
+        // The other two are quiet already and come back on the next check
+        let mut retried: Vec<PathBuf> = take_quiet(&mut changed, quiet, start + quiet)
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        retried.sort();
+        assert_eq!(retried, left);
+    }
+}
@@ -0,0 +1,64 @@
+use anyhow::{Context, Result};
+use chrono::{Datelike, Duration, Local, NaiveDate};
+
+use crate::config::load_config;
+use crate::summarizer::SummarizerEngine;
+
+/// Run the weekly command - generate a topic-grouped digest for a week
+pub async fn run(date: Option<String>) -> Result<()> {
+    let config = load_config()?;
+
+    // Any date within the target week (default: today)
+    let anchor = match date {
+        Some(d) => NaiveDate::parse_from_str(&d, "%Y-%m-%d")
+            .context(format!("Invalid date format: {}. Use yyyy-mm-dd", d))?,
+        None => config.effective_date(Local::now()),
+    };
+
+    let (week, start_date, end_date) = week_bounds(anchor);
+
+    eprintln!(
+        "[daily] Generating weekly digest for {} ({} to {})...",
+        week, start_date, end_date
+    );
+
+    let engine = SummarizerEngine::new(config.clone());
+    let summary = engine.summarize_week(&week, &start_date, &end_date).await?;
+    let path = summary.save(&config)?;
+
+    eprintln!(
+        "[daily] Weekly digest saved ({} sessions): {}",
+        summary.session_count,
+        path.display()
+    );
+
+    Ok(())
+}
+
+/// ISO week label and Monday-Sunday date range containing a date
+fn week_bounds(date: NaiveDate) -> (String, String, String) {
+    let iso = date.iso_week();
+    let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+    let sunday = monday + Duration::days(6);
+
+    (
+        format!("{}-W{:02}", iso.year(), iso.week()),
+        monday.format("%Y-%m-%d").to_string(),
+        sunday.format("%Y-%m-%d").to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_week_bounds() {
+        let date = NaiveDate::from_ymd_opt(2026, 1, 16).unwrap();
+        let (week, start, end) = week_bounds(date);
+
+        assert_eq!(week, "2026-W03");
+        assert_eq!(start, "2026-01-12");
+        assert_eq!(end, "2026-01-18");
+    }
+}
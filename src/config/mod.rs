@@ -1,6 +1,9 @@
 mod settings;
 
+pub use settings::date_relative_path;
 pub use settings::get_config_path;
+pub use settings::is_valid_time;
 pub use settings::load_config;
 pub use settings::save_config;
 pub use settings::Config;
+pub use settings::STORAGE_LAYOUTS;
@@ -1,9 +1,26 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::fs_util::write_atomic;
+use crate::summarizer::TemplateEngine;
 
 const APP_NAME: &str = "daily";
 
+/// Model aliases understood by the claude CLI
+pub const KNOWN_MODELS: [&str; 3] = ["haiku", "sonnet", "opus"];
+
+/// Placeholders understood by `archive.title_format`
+pub const TITLE_FORMAT_VARS: [&str; 4] = ["time", "topic", "branch", "project"];
+
+/// Values accepted by `storage.layout`
+pub const STORAGE_LAYOUTS: [&str; 2] = ["flat", "nested"];
+
+/// Values accepted by `summarization.inactivity_check`
+pub const INACTIVITY_CHECKS: [&str; 2] = ["content", "mtime"];
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
     pub storage: StorageConfig,
@@ -14,11 +31,50 @@ pub struct Config {
     /// Custom prompt templates (None = use built-in defaults)
     #[serde(default)]
     pub prompt_templates: PromptTemplatesConfig,
+    #[serde(default)]
+    pub server: ServerConfig,
+    #[serde(default)]
+    pub jobs: JobsConfig,
+}
+
+/// Background job bookkeeping
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JobsConfig {
+    /// Days a finished job's log is kept by `daily jobs cleanup --logs-only`; its
+    /// metadata stays for stats
+    #[serde(default = "default_log_retention_days")]
+    pub log_retention_days: u32,
+}
+
+impl Default for JobsConfig {
+    fn default() -> Self {
+        Self {
+            log_retention_days: default_log_retention_days(),
+        }
+    }
+}
+
+fn default_log_retention_days() -> u32 {
+    3
+}
+
+/// Dashboard server settings
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ServerConfig {
+    /// Token required as `Authorization: Bearer <token>` on API requests (None = open)
+    #[serde(default)]
+    pub auth_token: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct StorageConfig {
     pub path: PathBuf,
+    /// Date directory layout: "flat" (`<date>/`) or "nested" (`<yyyy>/<mm>/<date>/`)
+    #[serde(default = "default_layout")]
+    pub layout: String,
+    /// Project root → storage path for sessions whose cwd is inside that project
+    #[serde(default)]
+    pub project_overrides: HashMap<PathBuf, PathBuf>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -27,12 +83,35 @@ pub struct ArchiveConfig {
     pub tags: Vec<String>,
     pub include_cwd: bool,
     pub include_git_info: bool,
+    /// Maximum characters of `git diff --stat` output stored in a session archive
+    #[serde(default = "default_max_diff_chars")]
+    pub max_diff_chars: usize,
+    /// Session title (and file name) format, e.g. "{{branch}}-{{time}}-{{topic}}"
+    #[serde(default = "default_title_format")]
+    pub title_format: String,
+}
+
+fn default_layout() -> String {
+    "flat".into()
+}
+
+fn default_max_diff_chars() -> usize {
+    4000
+}
+
+fn default_title_format() -> String {
+    "{{time}}-{{topic}}".to_string()
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SummarizationConfig {
     pub model: String,
+    /// Maximum tokens of a Claude API response
     pub max_tokens: u32,
+    /// Token budget for a condensed transcript sent to Claude; older requests, reasoning
+    /// and modified files are dropped past it
+    #[serde(default = "default_max_transcript_tokens")]
+    pub max_transcript_tokens: usize,
     pub enable_daily_summary: bool,
     pub enable_extraction_hints: bool,
     /// Time to auto-digest previous day's sessions (format: "HH:MM", default: "06:00")
@@ -41,7 +120,7 @@ pub struct SummarizationConfig {
     /// Enable auto-digest of previous day's sessions on session start
     #[serde(default = "default_auto_digest")]
     pub auto_digest_enabled: bool,
-    /// Language for summary output ("en" for English, "zh" for Chinese)
+    /// Language for summary output: "en", "zh", "ja", "es" or "fr"
     #[serde(default = "default_summary_language")]
     pub summary_language: String,
     /// Enable auto-summarization of unsummarized sessions on daily show
@@ -59,12 +138,77 @@ pub struct SummarizationConfig {
     /// Minutes of inactivity before a transcript is considered "inactive" and eligible for auto-summarization
     #[serde(default = "default_auto_summarize_inactive_minutes")]
     pub auto_summarize_inactive_minutes: u64,
+    /// How inactivity is measured: "content" (last event timestamp, falling back to
+    /// mtime) or "mtime" (file modification time only)
+    #[serde(default = "default_inactivity_check")]
+    pub inactivity_check: String,
+    /// Transcripts auto-summarized per `daily show` invocation at most
+    #[serde(default = "default_max_auto_summarize")]
+    pub max_auto_summarize: usize,
+    /// Transcripts auto-summarized per day at most, to cap API spend (0 = unlimited)
+    #[serde(default = "default_daily_summarize_budget")]
+    pub daily_summarize_budget: usize,
+    /// Glob patterns of project directories never auto-summarized, e.g. "~/scratch/*"
+    #[serde(default)]
+    pub excluded_cwd_patterns: Vec<String>,
+    /// Transcript sources to scan for auto-summarization ("claude", "codex")
+    #[serde(default = "default_sources")]
+    pub sources: Vec<String>,
+    /// Number of session files read in parallel during digest
+    #[serde(default = "default_digest_read_concurrency")]
+    pub digest_read_concurrency: usize,
+    /// Tools whose use marks a session as real work rather than chat
+    #[serde(default = "default_meaningful_tools")]
+    pub meaningful_tools: Vec<String>,
+    /// How summaries are generated: "cli" (claude CLI) or "api" (Anthropic API)
+    #[serde(default = "default_backend")]
+    pub backend: String,
+    /// Anthropic API key for the "api" backend (ANTHROPIC_API_KEY takes precedence)
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Base URL for the "api" backend
+    #[serde(default = "default_api_base_url")]
+    pub api_base_url: String,
+    /// Retries for transient Claude failures, with backoff of 1s, 2s, 4s, ...
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Include the assistant's thinking blocks in session summarization input
+    #[serde(default)]
+    pub include_thinking: bool,
+    /// Path to the `claude` CLI when it isn't on PATH (e.g. installed via nvm or volta)
+    #[serde(default)]
+    pub claude_binary_path: Option<PathBuf>,
+    /// Background summarization jobs allowed to run at once; extra jobs are queued (0 = unlimited)
+    #[serde(default = "default_max_concurrent_jobs")]
+    pub max_concurrent_jobs: usize,
+    /// USD per million tokens, keyed by `model`, used to estimate session cost
+    #[serde(default = "default_prices")]
+    pub prices: HashMap<String, ModelPrice>,
+}
+
+/// Token prices for one model, in USD per million tokens
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+}
+
+impl SummarizationConfig {
+    /// Estimated cost in USD of a call to `model`; `None` when it has no price entry
+    pub fn estimate_cost(&self, input_tokens: u64, output_tokens: u64) -> Option<f64> {
+        let price = self.prices.get(&self.model)?;
+        Some((input_tokens as f64 * price.input + output_tokens as f64 * price.output) / 1e6)
+    }
 }
 
 fn default_summary_language() -> String {
     "en".into()
 }
 
+fn default_max_transcript_tokens() -> usize {
+    100_000 // Leaves room for the prompt and response in a 200k context window
+}
+
 fn default_digest_time() -> String {
     "06:00".into()
 }
@@ -89,18 +233,90 @@ fn default_auto_summarize_inactive_minutes() -> u64 {
     30 // 30 minutes of inactivity before considering a session ended
 }
 
+fn default_inactivity_check() -> String {
+    "content".to_string()
+}
+
+fn default_max_auto_summarize() -> usize {
+    3 // Conservative limit to prevent fork bomb
+}
+
+fn default_daily_summarize_budget() -> usize {
+    20
+}
+
+fn default_sources() -> Vec<String> {
+    vec!["claude".into()]
+}
+
+fn default_backend() -> String {
+    "cli".into()
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_api_base_url() -> String {
+    "https://api.anthropic.com".into()
+}
+
+fn default_meaningful_tools() -> Vec<String> {
+    vec!["Edit".into(), "Write".into(), "Bash".into()]
+}
+
+fn default_max_concurrent_jobs() -> usize {
+    2
+}
+
+fn default_prices() -> HashMap<String, ModelPrice> {
+    [
+        ("haiku", 1.0, 5.0),
+        ("sonnet", 3.0, 15.0),
+        ("opus", 15.0, 75.0),
+    ]
+    .into_iter()
+    .map(|(model, input, output)| (model.to_string(), ModelPrice { input, output }))
+    .collect()
+}
+
+fn default_digest_read_concurrency() -> usize {
+    8
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct HooksConfig {
     pub enable_session_start: bool,
     pub enable_session_end: bool,
+    /// Record submitted prompts to `prompts/<session_id>.jsonl` (UserPromptSubmit hook).
+    /// Off by default: prompts are stored verbatim, including any secrets pasted into them
+    #[serde(default = "default_enable_user_prompt")]
+    pub enable_user_prompt: bool,
     pub background_timeout: u64,
 }
 
+fn default_enable_user_prompt() -> bool {
+    false
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OutputConfig {
     pub terminal_format: String,
     pub date_format: String,
     pub time_format: String,
+    /// Hour (0-23) at which a new day starts; sessions before it count as the previous day
+    #[serde(default)]
+    pub day_cutoff_hour: u8,
+    /// Prepend a UTF-8 BOM to written archive files (for Windows editors)
+    #[serde(default)]
+    pub utf8_bom: bool,
+    /// Session archive location relative to storage, e.g. "{{project}}/{{date}}"
+    #[serde(default = "default_path_template")]
+    pub path_template: String,
+}
+
+fn default_path_template() -> String {
+    "{{date}}".to_string()
 }
 
 /// Custom prompt templates configuration
@@ -123,6 +339,19 @@ pub struct PromptTemplatesConfig {
     pub command_extract: Option<String>,
 }
 
+impl PromptTemplatesConfig {
+    /// Get a custom template by kind (e.g. "session_summary")
+    pub fn get(&self, kind: &str) -> Option<&str> {
+        match kind {
+            "session_summary" => self.session_summary.as_deref(),
+            "daily_summary" => self.daily_summary.as_deref(),
+            "skill_extract" => self.skill_extract.as_deref(),
+            "command_extract" => self.command_extract.as_deref(),
+            _ => None,
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         let default_path = dirs::home_dir()
@@ -131,16 +360,23 @@ impl Default for Config {
             .join("daily");
 
         Self {
-            storage: StorageConfig { path: default_path },
+            storage: StorageConfig {
+                path: default_path,
+                layout: default_layout(),
+                project_overrides: HashMap::new(),
+            },
             archive: ArchiveConfig {
                 author: None,
                 tags: vec!["claude-code".into(), "daily-archive".into()],
                 include_cwd: true,
                 include_git_info: true,
+                max_diff_chars: default_max_diff_chars(),
+                title_format: default_title_format(),
             },
             summarization: SummarizationConfig {
                 model: "haiku".into(),
                 max_tokens: 4096,
+                max_transcript_tokens: default_max_transcript_tokens(),
                 enable_daily_summary: true,
                 enable_extraction_hints: true,
                 digest_time: "06:00".into(),
@@ -151,46 +387,215 @@ impl Default for Config {
                 last_auto_summarize_check: None,
                 auto_summarize_on_show: false,
                 auto_summarize_inactive_minutes: 30,
+                inactivity_check: default_inactivity_check(),
+                max_auto_summarize: default_max_auto_summarize(),
+                daily_summarize_budget: default_daily_summarize_budget(),
+                excluded_cwd_patterns: Vec::new(),
+                sources: default_sources(),
+                digest_read_concurrency: default_digest_read_concurrency(),
+                meaningful_tools: default_meaningful_tools(),
+                backend: default_backend(),
+                api_key: None,
+                api_base_url: default_api_base_url(),
+                max_retries: default_max_retries(),
+                include_thinking: false,
+                max_concurrent_jobs: default_max_concurrent_jobs(),
+                prices: default_prices(),
+                claude_binary_path: None,
             },
             hooks: HooksConfig {
                 enable_session_start: true,
                 enable_session_end: true,
+                enable_user_prompt: default_enable_user_prompt(),
                 background_timeout: 300,
             },
             output: OutputConfig {
                 terminal_format: "colored".into(),
                 date_format: "%Y-%m-%d".into(),
                 time_format: "%H:%M:%S".into(),
+                day_cutoff_hour: 0,
+                utf8_bom: false,
+                path_template: default_path_template(),
             },
             prompt_templates: PromptTemplatesConfig::default(),
+            server: ServerConfig::default(),
+            jobs: JobsConfig::default(),
         }
     }
 }
 
 impl Config {
-    /// Get the storage path, expanding ~ if present
-    pub fn storage_path(&self) -> PathBuf {
-        let path_str = self.storage.path.to_string_lossy();
-        if path_str.starts_with("~") {
-            if let Some(home) = dirs::home_dir() {
-                return home.join(path_str.trim_start_matches("~/"));
+    /// Use `model` for this invocation only, warning when it isn't a known alias
+    pub fn override_model(&mut self, model: Option<String>) {
+        let Some(model) = model else {
+            return;
+        };
+        if !KNOWN_MODELS.contains(&model.as_str()) {
+            eprintln!(
+                "[daily] Warning: unknown model '{}' (expected {}), passing it through as-is",
+                model,
+                KNOWN_MODELS.join(", ")
+            );
+        }
+        self.summarization.model = model;
+    }
+
+    /// Check settings that serde defaults can't catch, such as malformed times
+    pub fn validate(&self) -> Result<()> {
+        let times = [
+            ("summarization.digest_time", &self.summarization.digest_time),
+            (
+                "summarization.auto_summarize_time",
+                &self.summarization.auto_summarize_time,
+            ),
+        ];
+        for (key, time) in times {
+            if !is_valid_time(time) {
+                anyhow::bail!("Invalid {} \"{}\": must be HH:MM", key, time);
             }
         }
-        self.storage.path.clone()
+
+        if !STORAGE_LAYOUTS.contains(&self.storage.layout.as_str()) {
+            anyhow::bail!(
+                "Invalid storage.layout \"{}\": must be one of {}",
+                self.storage.layout,
+                STORAGE_LAYOUTS.join(", ")
+            );
+        }
+
+        if !INACTIVITY_CHECKS.contains(&self.summarization.inactivity_check.as_str()) {
+            anyhow::bail!(
+                "Invalid summarization.inactivity_check \"{}\": must be one of {}",
+                self.summarization.inactivity_check,
+                INACTIVITY_CHECKS.join(", ")
+            );
+        }
+
+        if let Some((pattern, e)) = self
+            .summarization
+            .excluded_cwd_patterns
+            .iter()
+            .find_map(|p| glob::Pattern::new(p).err().map(|e| (p, e)))
+        {
+            anyhow::bail!(
+                "Invalid summarization.excluded_cwd_patterns entry \"{}\": {}",
+                pattern,
+                e
+            );
+        }
+
+        let format = &self.archive.title_format;
+        if !format.contains("{{topic}}") {
+            anyhow::bail!(
+                "Invalid archive.title_format \"{}\": must contain {{{{topic}}}}",
+                format
+            );
+        }
+        if let Some(unknown) = TemplateEngine::extract_variables(format)
+            .into_iter()
+            .find(|var| !TITLE_FORMAT_VARS.contains(&var.as_str()))
+        {
+            anyhow::bail!(
+                "Invalid archive.title_format \"{}\": unknown placeholder {{{{{}}}}}",
+                format,
+                unknown
+            );
+        }
+        // Text around the placeholders ends up in file names verbatim
+        let literal = TITLE_FORMAT_VARS.iter().fold(format.clone(), |text, var| {
+            text.replace(&format!("{{{{{}}}}}", var), "")
+        });
+        if !literal
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            anyhow::bail!(
+                "Invalid archive.title_format \"{}\": only letters, digits, '-', '_' and '.' are allowed around placeholders",
+                format
+            );
+        }
+        Ok(())
+    }
+
+    /// Get the storage path, expanding ~ if present
+    pub fn storage_path(&self) -> PathBuf {
+        expand_home(&self.storage.path)
+    }
+
+    /// Get the storage path for a session run in `cwd`.
+    ///
+    /// Uses the `storage.project_overrides` entry of the innermost project root
+    /// containing `cwd`, falling back to `storage.path`.
+    pub fn storage_path_for(&self, cwd: &Path) -> PathBuf {
+        self.storage
+            .project_overrides
+            .iter()
+            .map(|(root, path)| (expand_home(root), path))
+            .filter(|(root, _)| cwd.starts_with(root))
+            .max_by_key(|(root, _)| root.components().count())
+            .map(|(_, path)| expand_home(path))
+            .unwrap_or_else(|| self.storage_path())
+    }
+
+    /// A copy of this config that archives into the storage for `cwd`
+    pub fn for_project(&self, cwd: &Path) -> Config {
+        let mut config = self.clone();
+        config.storage.path = self.storage_path_for(cwd);
+        config
     }
 
     /// Get today's archive directory
     pub fn today_dir(&self) -> PathBuf {
-        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
-        self.storage_path().join(today)
+        self.date_dir(&self.today())
+    }
+
+    /// Effective "today" (yyyy-mm-dd), honoring `output.day_cutoff_hour`
+    pub fn today(&self) -> String {
+        self.effective_date(chrono::Local::now())
+            .format("%Y-%m-%d")
+            .to_string()
+    }
+
+    /// Effective "yesterday" (yyyy-mm-dd), honoring `output.day_cutoff_hour`
+    pub fn yesterday(&self) -> String {
+        (self.effective_date(chrono::Local::now()) - chrono::Duration::days(1))
+            .format("%Y-%m-%d")
+            .to_string()
+    }
+
+    /// Calendar day a moment belongs to once the day cutoff is applied
+    pub fn effective_date(&self, now: chrono::DateTime<chrono::Local>) -> chrono::NaiveDate {
+        let cutoff = self.output.day_cutoff_hour.min(23) as i64;
+        (now - chrono::Duration::hours(cutoff)).date_naive()
     }
 
-    /// Get archive directory for a specific date
+    /// Get archive directory for a specific date, following `storage.layout`
     pub fn date_dir(&self, date: &str) -> PathBuf {
-        self.storage_path().join(date)
+        self.storage_path()
+            .join(date_relative_path(&self.storage.layout, date))
     }
 }
 
+/// Where a date's directory sits below its parent in a layout:
+/// `2026-01-16` (flat) or `2026/01/2026-01-16` (nested)
+pub fn date_relative_path(layout: &str, date: &str) -> PathBuf {
+    match (layout, date.get(0..4), date.get(5..7)) {
+        ("nested", Some(year), Some(month)) => [year, month, date].iter().collect(),
+        _ => PathBuf::from(date),
+    }
+}
+
+/// Expand a leading `~` to the home directory
+fn expand_home(path: &Path) -> PathBuf {
+    let path_str = path.to_string_lossy();
+    if path_str.starts_with("~") {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(path_str.trim_start_matches("~/"));
+        }
+    }
+    path.to_path_buf()
+}
+
 /// Load configuration from file or create default
 pub fn load_config() -> Result<Config> {
     let config: Config =
@@ -200,10 +605,23 @@ pub fn load_config() -> Result<Config> {
 
 /// Save configuration to file
 pub fn save_config(config: &Config) -> Result<()> {
-    confy::store(APP_NAME, Some("config"), config).context("Failed to save configuration")?;
+    let path = get_config_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("Failed to create configuration directory")?;
+    }
+    let content = toml::to_string_pretty(config).context("Failed to serialize configuration")?;
+    write_atomic(&path, content).context("Failed to save configuration")?;
     Ok(())
 }
 
+/// Check that a time is in HH:MM format
+pub fn is_valid_time(time: &str) -> bool {
+    let Some((h, m)) = time.split_once(':') else {
+        return false;
+    };
+    matches!((h.parse::<u32>(), m.parse::<u32>()), (Ok(h), Ok(m)) if h < 24 && m < 60)
+}
+
 /// Get the configuration file path
 pub fn get_config_path() -> Result<PathBuf> {
     let path = confy::get_configuration_file_path(APP_NAME, Some("config"))
@@ -215,6 +633,54 @@ pub fn get_config_path() -> Result<PathBuf> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_storage_path_for_project() {
+        let mut config = Config::default();
+        config.storage.path = PathBuf::from("/archive");
+        config.storage.project_overrides.insert(
+            PathBuf::from("/work/repo"),
+            PathBuf::from("/work/repo/.daily"),
+        );
+        config.storage.project_overrides.insert(
+            PathBuf::from("/work/repo/vendor/lib"),
+            PathBuf::from("/lib-archive"),
+        );
+
+        assert_eq!(
+            config.storage_path_for(Path::new("/work/repo/src")),
+            PathBuf::from("/work/repo/.daily")
+        );
+        assert_eq!(
+            config.storage_path_for(Path::new("/work/repo/vendor/lib")),
+            PathBuf::from("/lib-archive")
+        );
+        assert_eq!(
+            config.storage_path_for(Path::new("/work/repository")),
+            PathBuf::from("/archive")
+        );
+        assert_eq!(
+            config.for_project(Path::new("/work/repo")).storage_path(),
+            PathBuf::from("/work/repo/.daily")
+        );
+
+        let saved: Config = toml::from_str(&toml::to_string_pretty(&config).unwrap()).unwrap();
+        assert_eq!(saved.storage.project_overrides.len(), 2);
+    }
+
+    #[test]
+    fn test_override_model() {
+        let mut config = Config::default();
+        config.override_model(None);
+        assert_eq!(config.summarization.model, "haiku");
+
+        config.override_model(Some("sonnet".into()));
+        assert_eq!(config.summarization.model, "sonnet");
+
+        // Unknown names pass through for new or full model IDs
+        config.override_model(Some("claude-opus-4-1".into()));
+        assert_eq!(config.summarization.model, "claude-opus-4-1");
+    }
+
     #[test]
     fn test_default_config() {
         let config = Config::default();
@@ -226,10 +692,92 @@ mod tests {
         assert_eq!(config.summarization.model, "haiku");
     }
 
+    #[test]
+    fn test_effective_date_with_cutoff() {
+        use chrono::TimeZone;
+
+        let mut config = Config::default();
+        let late_night = chrono::Local
+            .with_ymd_and_hms(2026, 1, 17, 1, 30, 0)
+            .unwrap();
+        let morning = chrono::Local
+            .with_ymd_and_hms(2026, 1, 17, 9, 0, 0)
+            .unwrap();
+
+        assert_eq!(config.effective_date(late_night).to_string(), "2026-01-17");
+
+        config.output.day_cutoff_hour = 4;
+        assert_eq!(config.effective_date(late_night).to_string(), "2026-01-16");
+        assert_eq!(config.effective_date(morning).to_string(), "2026-01-17");
+    }
+
+    #[test]
+    fn test_validate_times() {
+        let mut config = Config::default();
+        assert!(config.validate().is_ok());
+
+        config.summarization.auto_summarize_time = "25:00".into();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("summarization.auto_summarize_time"));
+
+        assert!(is_valid_time("23:59"));
+        assert!(!is_valid_time("6am"));
+        assert!(!is_valid_time("12:60"));
+    }
+
+    #[test]
+    fn test_validate_title_format() {
+        let mut config = Config::default();
+        config.archive.title_format = "{{branch}}_{{time}}-{{topic}}".into();
+        assert!(config.validate().is_ok());
+
+        for bad in [
+            "{{time}}",
+            "{{time}}-{{topic}}-{{host}}",
+            "{{time}}/{{topic}}",
+        ] {
+            config.archive.title_format = bad.into();
+            let err = config.validate().unwrap_err().to_string();
+            assert!(err.contains("archive.title_format"), "{}", bad);
+        }
+    }
+
     #[test]
     fn test_today_dir() {
         let config = Config::default();
         let today = chrono::Local::now().format("%Y-%m-%d").to_string();
         assert!(config.today_dir().to_string_lossy().contains(&today));
     }
+
+    #[test]
+    fn test_date_dir_layout() {
+        let mut config = Config::default();
+        config.storage.path = PathBuf::from("/archive");
+        assert_eq!(
+            config.date_dir("2026-01-16"),
+            PathBuf::from("/archive/2026-01-16")
+        );
+
+        config.storage.layout = "nested".into();
+        assert_eq!(
+            config.date_dir("2026-01-16"),
+            PathBuf::from("/archive/2026/01/2026-01-16")
+        );
+        assert!(config.validate().is_ok());
+
+        config.storage.layout = "yearly".into();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("storage.layout"));
+    }
+
+    #[test]
+    fn test_validate_excluded_cwd_patterns() {
+        let mut config = Config::default();
+        config.summarization.excluded_cwd_patterns = vec!["~/scratch/*".into()];
+        assert!(config.validate().is_ok());
+
+        config.summarization.excluded_cwd_patterns = vec!["/tmp/[".into()];
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("excluded_cwd_patterns"));
+    }
 }
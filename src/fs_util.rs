@@ -0,0 +1,114 @@
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::jobs::is_process_alive;
+
+/// Numbers temporary files, so concurrent writes from one process don't share one
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Write a file via a temporary sibling and rename, so a crash or `kill`
+/// mid-write never leaves a truncated file behind
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let tmp = write_temp(path, contents.as_ref())?;
+    fs::rename(&tmp, path).inspect_err(|_| {
+        let _ = fs::remove_file(&tmp);
+    })?;
+    remove_stale_temps(path);
+    Ok(())
+}
+
+/// Write the contents to a hidden temporary file next to `path`, named
+/// `.<name>.<pid>.<n>.tmp`
+fn write_temp(path: &Path, contents: &[u8]) -> io::Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+    let tmp = path.with_file_name(format!(
+        ".{}.{}.{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let mut file = File::create(&tmp)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    Ok(tmp)
+}
+
+/// Remove temporary files of `path` left by writers that were killed mid-write
+fn remove_stale_temps(path: &Path) {
+    let (Some(dir), Some(file_name)) = (path.parent(), path.file_name()) else {
+        return;
+    };
+    let prefix = format!(".{}.", file_name.to_string_lossy());
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let writer = name
+            .to_string_lossy()
+            .strip_prefix(&prefix)
+            .and_then(|rest| rest.strip_suffix(".tmp"))
+            .and_then(|rest| rest.split('.').next())
+            .and_then(|pid| pid.parse::<u32>().ok());
+        // Another live process may be about to rename its file into place
+        if writer.is_some_and(|pid| !is_process_alive(pid)) {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_atomic_replaces_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("job.json");
+
+        write_atomic(&path, "first").unwrap();
+        write_atomic(&path, "second").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_temp_names_are_unique() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("job.json");
+
+        let first = write_temp(&path, b"first").unwrap();
+        let second = write_temp(&path, b"second").unwrap();
+        assert_ne!(first, second);
+        assert_eq!(fs::read_to_string(&first).unwrap(), "first");
+    }
+
+    #[test]
+    fn test_interrupted_write_keeps_old_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("daily.md");
+        write_atomic(&path, "complete old content").unwrap();
+
+        // A writer (no longer running) was killed after writing its temp file but
+        // before the rename
+        let tmp = write_temp(&path, b"new cont").unwrap();
+        let stale = temp_dir
+            .path()
+            .join(format!(".daily.md.{}.0.tmp", i32::MAX));
+        fs::rename(&tmp, &stale).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "complete old content");
+
+        // The next write still lands in full and clears the leftover
+        write_atomic(&path, "complete new content").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "complete new content");
+        assert!(!stale.exists());
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+}
@@ -15,6 +15,8 @@ pub struct HookInput {
     pub reason: Option<String>, // Only for SessionEnd: prompt_input_exit, logout, clear, other
     #[serde(default)]
     pub permission_mode: Option<String>,
+    #[serde(default)]
+    pub prompt: Option<String>, // Only for UserPromptSubmit: the submitted prompt text
 }
 
 /// Read hook input JSON from stdin
@@ -67,4 +69,19 @@ mod tests {
         let input: HookInput = serde_json::from_str(json).unwrap();
         assert_eq!(input.reason, Some("user_exit".to_string()));
     }
+
+    #[test]
+    fn test_parse_user_prompt_submit_input() {
+        let json = r#"{
+            "session_id": "abc123",
+            "transcript_path": "/home/user/.claude/projects/xyz/session.jsonl",
+            "cwd": "/home/user/project",
+            "hook_event_name": "UserPromptSubmit",
+            "prompt": "Fix the failing test"
+        }"#;
+
+        let input: HookInput = serde_json::from_str(json).unwrap();
+        assert_eq!(input.prompt, Some("Fix the failing test".to_string()));
+        assert!(input.reason.is_none());
+    }
 }
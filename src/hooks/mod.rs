@@ -1,5 +1,7 @@
 mod input;
 pub mod session_end;
+mod session_hint;
 pub mod session_start;
+pub mod user_prompt;
 
 pub use input::read_hook_input;
@@ -1,13 +1,13 @@
 use anyhow::Result;
-use std::process::{Command, Stdio};
-
-#[cfg(unix)]
-use std::os::unix::process::CommandExt;
 
+use crate::auto_summarize::mark_transcript_done;
+use crate::cli::commands::summarize::{spawn_background_summarize, summarize_args};
 use crate::config::load_config;
 use crate::hooks::read_hook_input;
-use crate::jobs::{JobManager, JobType};
-use crate::transcript::TranscriptParser;
+use crate::hooks::session_hint::{SessionHint, HINT_FILE};
+use crate::jobs::{JobManager, JobStatus, JobType};
+use crate::transcript::{SessionKind, TranscriptParser};
+use tracing::{info, warn};
 
 /// Handle SessionEnd hook from Claude Code
 /// Spawns background process for summarization
@@ -23,26 +23,33 @@ pub async fn handle() -> Result<()> {
     let input = match read_hook_input() {
         Ok(input) => input,
         Err(e) => {
-            eprintln!("[daily] Failed to read hook input: {}", e);
+            warn!("Failed to read hook input: {}", e);
             return Ok(()); // Don't block session exit
         }
     };
 
+    // Mark the transcript finished so auto-summarize doesn't wait out the inactivity window
+    if input.transcript_path.exists() {
+        if let Err(e) = mark_transcript_done(&input.transcript_path) {
+            warn!("Failed to mark transcript done: {}", e);
+        }
+    }
+
     // Archive on all session end reasons to collect complete history
     // Reasons: "prompt_input_exit" (Ctrl+D), "logout", "clear", "other"
-    eprintln!(
-        "[daily] Session ended with {:?}, checking transcript",
-        input.reason
-    );
+    info!("Session ended with {:?}, checking transcript", input.reason);
 
     // Check if transcript is empty before spawning summarization job
     // This avoids creating useless 0s jobs for sessions with no user input
-    if is_transcript_empty(&input.transcript_path) {
-        eprintln!("[daily] Empty session (no user input), skipping archive");
+    if is_transcript_empty(
+        &input.transcript_path,
+        &config.summarization.meaningful_tools,
+    ) {
+        info!("Empty session (no user input), skipping archive");
         return Ok(());
     }
 
-    eprintln!("[daily] Starting archive");
+    info!("Starting archive");
 
     // Generate task name from working directory
     let task_name = generate_task_name(&input.cwd);
@@ -51,72 +58,46 @@ pub async fn handle() -> Result<()> {
     let job_manager = match JobManager::new(&config) {
         Ok(m) => m,
         Err(e) => {
-            eprintln!("[daily] Failed to initialize job manager: {}", e);
+            warn!("Failed to initialize job manager: {}", e);
             return Ok(()); // Don't block session exit
         }
     };
 
-    // Generate job ID
-    let job_id = JobManager::generate_job_id(&task_name);
-    let transcript_path = input.transcript_path.to_string_lossy().to_string();
-
-    // Create log file for the job
-    let (stdout_file, stderr_file) = match job_manager.create_log_file(&job_id) {
-        Ok(f) => {
-            let f2 = f.try_clone().unwrap_or_else(|_| {
-                std::fs::File::create("/dev/null").expect("Failed to open /dev/null")
-            });
-            (Stdio::from(f), Stdio::from(f2))
-        }
-        Err(_) => (Stdio::null(), Stdio::null()),
-    };
-
     // Get cwd as string for passing to summarize command
     let cwd_str = input.cwd.to_string_lossy().to_string();
 
     // Spawn background process for summarization
     // This ensures Claude Code can exit immediately
-    let mut cmd = Command::new("daily");
-    cmd.args([
-        "summarize",
-        "--transcript",
-        &transcript_path,
-        "--task-name",
+    let mut args = summarize_args(&input.transcript_path, &task_name, &cwd_str);
+
+    // Project-local overrides for title, date and tags
+    if let Some(hint) = SessionHint::load(&input.cwd) {
+        info!("Applying archive hints from {}", HINT_FILE);
+        args.extend(hint.to_args());
+    }
+
+    match spawn_background_summarize(
+        &config,
+        &job_manager,
         &task_name,
-        "--cwd",
-        &cwd_str,
-        "--job-id",
-        &job_id,
-        "--foreground",
-    ])
-    .stdin(Stdio::null())
-    .stdout(stdout_file)
-    .stderr(stderr_file);
-
-    // Create a new process group so Ctrl+C/Cmd+C doesn't kill the background process
-    #[cfg(unix)]
-    cmd.process_group(0);
-
-    match cmd.spawn() {
-        Ok(child) => {
-            // Register the job
-            if let Err(e) = job_manager.register(
-                &job_id,
-                child.id(),
-                &task_name,
-                &input.transcript_path,
-                JobType::SessionEnd,
-            ) {
-                eprintln!("[daily] Failed to register job: {}", e);
-            }
-            eprintln!(
-                "[daily] Background summarization started: {} (PID: {})",
-                job_id,
-                child.id()
+        &input.transcript_path,
+        args,
+        JobType::SessionEnd,
+    ) {
+        Ok(job) if job.status == JobStatus::Queued => {
+            info!(
+                "Summarization queued: {} (max_concurrent_jobs reached)",
+                job.id
+            );
+        }
+        Ok(job) => {
+            info!(
+                "Background summarization started: {} (PID: {})",
+                job.id, job.pid
             );
         }
         Err(e) => {
-            eprintln!("[daily] Failed to spawn summarization process: {}", e);
+            warn!("Failed to spawn summarization process: {}", e);
         }
     }
 
@@ -124,7 +105,7 @@ pub async fn handle() -> Result<()> {
 }
 
 /// Check if the transcript file is empty or contains no user messages
-fn is_transcript_empty(transcript_path: &std::path::Path) -> bool {
+fn is_transcript_empty(transcript_path: &std::path::Path, meaningful_tools: &[String]) -> bool {
     // If file doesn't exist, consider it empty
     if !transcript_path.exists() {
         return true;
@@ -132,7 +113,7 @@ fn is_transcript_empty(transcript_path: &std::path::Path) -> bool {
 
     // Parse transcript and check if it has meaningful content
     match TranscriptParser::parse(transcript_path) {
-        Ok(data) => data.is_empty(),
+        Ok(data) => data.classify_session(meaningful_tools) == SessionKind::Empty,
         Err(_) => {
             // If parsing fails, don't skip - let summarization handle the error
             false
@@ -0,0 +1,80 @@
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use tracing::info;
+
+/// Name of the optional per-project hint file read at session end
+pub const HINT_FILE: &str = ".daily-session.toml";
+
+/// Archiving hints from a project's `.daily-session.toml`
+#[derive(Debug, Default, Deserialize, PartialEq)]
+pub struct SessionHint {
+    /// Title (topic) to archive the session under
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Archive date (yyyy-mm-dd)
+    #[serde(default)]
+    pub date: Option<String>,
+    /// Extra tags, e.g. a project category
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl SessionHint {
+    /// Load the hint file from a session's working directory, if present
+    pub fn load(cwd: &Path) -> Option<Self> {
+        let path = cwd.join(HINT_FILE);
+        let content = fs::read_to_string(&path).ok()?;
+
+        match toml::from_str(&content) {
+            Ok(hint) => Some(hint),
+            Err(e) => {
+                info!("Ignoring invalid {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Extra `daily summarize` arguments carrying these overrides
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(title) = &self.title {
+            args.extend(["--title".to_string(), title.clone()]);
+        }
+        if let Some(date) = &self.date {
+            args.extend(["--date".to_string(), date.clone()]);
+        }
+        for tag in &self.tags {
+            args.extend(["--tag".to_string(), tag.clone()]);
+        }
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_hint_file() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(SessionHint::load(temp_dir.path()).is_none());
+
+        fs::write(
+            temp_dir.path().join(HINT_FILE),
+            "title = \"client onboarding\"\ntags = [\"client-x\"]\n",
+        )
+        .unwrap();
+        let hint = SessionHint::load(temp_dir.path()).unwrap();
+        assert_eq!(hint.title.as_deref(), Some("client onboarding"));
+        assert_eq!(hint.date, None);
+        assert_eq!(
+            hint.to_args(),
+            vec!["--title", "client onboarding", "--tag", "client-x"]
+        );
+
+        fs::write(temp_dir.path().join(HINT_FILE), "title = [").unwrap();
+        assert!(SessionHint::load(temp_dir.path()).is_none());
+    }
+}
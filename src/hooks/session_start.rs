@@ -1,11 +1,13 @@
 use anyhow::Result;
-use chrono::{Local, Timelike};
+use chrono::Local;
 use std::fs;
-use std::process::{Command, Stdio};
 
-use crate::archive::ArchiveManager;
+use crate::archive::skills::SkillStore;
+use crate::cli::commands::digest::{auto_digest_due, spawn_background_digest};
 use crate::config::load_config;
+use crate::fs_util::write_atomic;
 use crate::hooks::read_hook_input;
+use tracing::{info, warn};
 
 /// Handle SessionStart hook from Claude Code
 /// Creates today's directory if it doesn't exist and initializes daily.md
@@ -21,7 +23,7 @@ pub async fn handle() -> Result<()> {
     // (allows manual testing without stdin)
     let _input = read_hook_input().ok();
 
-    let today = Local::now().format("%Y-%m-%d").to_string();
+    let today = config.today();
     let daily_dir = config.today_dir();
 
     // Create today's directory if first session of the day
@@ -63,9 +65,9 @@ _No sessions archived yet._
             now.to_rfc3339(),
             today
         );
-        fs::write(&daily_md, content)?;
+        write_atomic(&daily_md, content)?;
 
-        eprintln!("[daily] Created daily directory: {}", daily_dir.display());
+        info!("Created daily directory: {}", daily_dir.display());
     }
 
     // Check for auto-digest of previous day's sessions
@@ -82,84 +84,21 @@ _No sessions archived yet._
 
 /// Check if we should auto-digest yesterday's sessions
 fn check_auto_digest(config: &crate::config::Config) {
-    // Parse digest_time (format: "HH:MM")
-    let digest_time = &config.summarization.digest_time;
-    let parts: Vec<&str> = digest_time.split(':').collect();
-    if parts.len() != 2 {
+    let Some(yesterday) = auto_digest_due(config, Local::now()) else {
         return;
-    }
-
-    let (digest_hour, digest_minute) = match (parts[0].parse::<u32>(), parts[1].parse::<u32>()) {
-        (Ok(h), Ok(m)) if h < 24 && m < 60 => (h, m),
-        _ => return,
     };
 
-    let now = Local::now();
-    let current_minutes = now.hour() * 60 + now.minute();
-    let digest_minutes = digest_hour * 60 + digest_minute;
+    info!("Auto-digesting yesterday's sessions ({})...", yesterday);
 
-    // Only trigger if we're past digest time
-    if current_minutes < digest_minutes {
-        return;
-    }
-
-    // Get yesterday's date
-    let yesterday = (now - chrono::Duration::days(1))
-        .format("%Y-%m-%d")
-        .to_string();
-
-    // Check if yesterday has un-digested sessions
-    let manager = ArchiveManager::new(config.clone());
-    if !manager.has_sessions(&yesterday) {
-        return;
-    }
-
-    eprintln!(
-        "[daily] Auto-digesting yesterday's sessions ({})...",
-        yesterday
-    );
-
-    // Spawn background digest process
-    if let Ok(exe) = std::env::current_exe() {
-        let _ = Command::new(&exe)
-            .args(["digest", "--date", &yesterday, "--foreground"])
-            .stdin(Stdio::null())
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .spawn();
+    // Spawn background digest process, tracked as a job
+    if let Err(e) = spawn_background_digest(config, &yesterday, false, false, None) {
+        warn!("Failed to start auto-digest: {}", e);
     }
 }
 
 /// Check for pending skills that need user review
 fn check_pending_skills(config: &crate::config::Config) {
-    let pending_dir = config.storage.path.join("pending-skills");
-
-    if !pending_dir.exists() {
-        return;
-    }
-
-    // Count pending skills across all dates
-    let mut pending_skills: Vec<(String, String)> = Vec::new();
-
-    if let Ok(entries) = fs::read_dir(&pending_dir) {
-        for entry in entries.flatten() {
-            if entry.path().is_dir() {
-                if let Ok(files) = fs::read_dir(entry.path()) {
-                    for file in files.flatten() {
-                        if file.path().extension().is_some_and(|e| e == "md") {
-                            let date = entry.file_name().to_string_lossy().to_string();
-                            let name = file
-                                .path()
-                                .file_stem()
-                                .map(|s| s.to_string_lossy().to_string())
-                                .unwrap_or_default();
-                            pending_skills.push((date, name));
-                        }
-                    }
-                }
-            }
-        }
-    }
+    let pending_skills = SkillStore::new(config).list().unwrap_or_default();
 
     if pending_skills.is_empty() {
         return;
@@ -171,8 +110,8 @@ fn check_pending_skills(config: &crate::config::Config) {
         "[daily] {} pending skill(s) waiting for review:",
         pending_skills.len()
     );
-    for (date, name) in pending_skills.iter().take(5) {
-        eprintln!("[daily]   • {}/{}", date, name);
+    for skill in pending_skills.iter().take(5) {
+        eprintln!("[daily]   • {}/{}", skill.date, skill.name);
     }
     if pending_skills.len() > 5 {
         eprintln!("[daily]   ... and {} more", pending_skills.len() - 5);
@@ -0,0 +1,122 @@
+use anyhow::Result;
+use chrono::Local;
+use serde::Serialize;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::config::load_config;
+use crate::hooks::read_hook_input;
+use tracing::warn;
+
+/// One submitted prompt, stored as a line of `prompts/<session_id>.jsonl`
+#[derive(Debug, Serialize)]
+struct PromptRecord<'a> {
+    timestamp: String,
+    session_id: &'a str,
+    cwd: &'a Path,
+    prompt: &'a str,
+}
+
+/// Handle UserPromptSubmit hook from Claude Code
+/// Appends the submitted prompt to today's per-session prompt log
+pub async fn handle() -> Result<()> {
+    let config = load_config()?;
+
+    // Check if hooks are enabled
+    if !config.hooks.enable_user_prompt {
+        return Ok(());
+    }
+
+    let input = match read_hook_input() {
+        Ok(input) => input,
+        Err(e) => {
+            warn!("Failed to read hook input: {}", e);
+            return Ok(()); // Never block the user's prompt
+        }
+    };
+
+    let Some(prompt) = input.prompt.as_deref().filter(|p| !p.trim().is_empty()) else {
+        return Ok(());
+    };
+
+    let record = PromptRecord {
+        timestamp: Local::now().to_rfc3339(),
+        session_id: &input.session_id,
+        cwd: &input.cwd,
+        prompt,
+    };
+
+    let path = prompts_path(&config.today_dir(), &input.session_id);
+    if let Err(e) = append_record(&path, &record) {
+        warn!("Failed to record prompt: {}", e);
+    }
+
+    // Exit with 0 and no stdout so the prompt goes through unchanged
+    Ok(())
+}
+
+/// Path of the prompt log for a session inside a date directory
+fn prompts_path(date_dir: &Path, session_id: &str) -> PathBuf {
+    let file_name: String = session_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    date_dir
+        .join("prompts")
+        .join(format!("{}.jsonl", file_name))
+}
+
+/// Append a record as a single JSON line, creating the file if needed
+fn append_record(path: &Path, record: &PromptRecord) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut line = serde_json::to_string(record)?;
+    line.push('\n');
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_append_record_writes_one_line_per_prompt() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = prompts_path(temp_dir.path(), "abc/123");
+        assert!(path.ends_with("prompts/abc_123.jsonl"));
+
+        let cwd = PathBuf::from("/home/user/project");
+        for prompt in ["first", "second\nline"] {
+            let record = PromptRecord {
+                timestamp: "2024-01-15T10:00:00+00:00".into(),
+                session_id: "abc/123",
+                cwd: &cwd,
+                prompt,
+            };
+            append_record(&path, &record).unwrap();
+        }
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["prompt"], "first");
+        assert_eq!(lines[1]["prompt"], "second\nline");
+        assert_eq!(lines[1]["cwd"], "/home/user/project");
+    }
+}
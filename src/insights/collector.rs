@@ -1,6 +1,7 @@
 use serde::Serialize;
 use std::collections::HashMap;
 
+use crate::archive::frontmatter::Frontmatter;
 use crate::archive::ArchiveManager;
 use crate::config::Config;
 
@@ -71,12 +72,7 @@ impl InsightsData {
             let session_count = sessions.len();
             total_sessions += session_count;
 
-            let has_digest = manager
-                .read_daily_summary(date)
-                .map(|content| {
-                    content.contains("## Overview") && !content.contains("No sessions recorded yet")
-                })
-                .unwrap_or(false);
+            let has_digest = manager.has_digest(date);
 
             daily_stats.push(DailyStat {
                 date: date.clone(),
@@ -196,7 +192,7 @@ where
         .into_iter()
         .map(|(name, count)| CategoryCount { name, count })
         .collect();
-    result.sort_by(|a, b| b.count.cmp(&a.count));
+    result.sort_by_key(|r| std::cmp::Reverse(r.count));
     result
 }
 
@@ -215,28 +211,15 @@ where
         .into_iter()
         .map(|(name, count)| CategoryCount { name, count })
         .collect();
-    result.sort_by(|a, b| b.count.cmp(&a.count));
+    result.sort_by_key(|r| std::cmp::Reverse(r.count));
     result
 }
 
 /// Extract session_id from YAML frontmatter in a session archive markdown file.
 /// Looks for `session_id: <value>` between `---` markers.
 fn extract_session_id_from_frontmatter(content: &str) -> Option<String> {
-    if let Some(stripped) = content.strip_prefix("---\n") {
-        if let Some(end) = stripped.find("\n---") {
-            let frontmatter = &stripped[..end];
-            for line in frontmatter.lines() {
-                if let Some((key, value)) = line.split_once(':') {
-                    let key = key.trim();
-                    if key == "session_id" {
-                        let value = value.trim().trim_matches('"');
-                        if !value.is_empty() {
-                            return Some(value.to_string());
-                        }
-                    }
-                }
-            }
-        }
-    }
-    None
+    Frontmatter::parse(content)
+        .get("session_id")
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
 }
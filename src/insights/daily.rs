@@ -1,6 +1,7 @@
 use serde::Serialize;
 use std::collections::HashMap;
 
+use crate::archive::frontmatter::Frontmatter;
 use crate::archive::ArchiveManager;
 use crate::config::Config;
 
@@ -184,23 +185,10 @@ impl DateInsights {
 
 /// Extract session_id from YAML frontmatter in session markdown content
 fn extract_session_id(content: &str) -> Option<String> {
-    if let Some(stripped) = content.strip_prefix("---\n") {
-        if let Some(end) = stripped.find("\n---") {
-            let frontmatter = &stripped[..end];
-            for line in frontmatter.lines() {
-                if let Some((key, value)) = line.split_once(':') {
-                    let key = key.trim();
-                    if key == "session_id" {
-                        let value = value.trim().trim_matches('"');
-                        if !value.is_empty() && value != "N/A" {
-                            return Some(value.to_string());
-                        }
-                    }
-                }
-            }
-        }
-    }
-    None
+    Frontmatter::parse(content)
+        .get("session_id")
+        .filter(|value| !value.is_empty() && *value != "N/A")
+        .map(str::to_string)
 }
 
 /// Find the most common key in a HashMap<String, usize>
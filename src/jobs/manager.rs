@@ -3,22 +3,36 @@ use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use tracing::warn;
+
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
 
 use crate::config::Config;
+use crate::fs_util::write_atomic;
 
 /// Maximum log file size in bytes (1MB)
 const MAX_LOG_SIZE: u64 = 1024 * 1024;
 
+/// How job start and finish times are shown
+pub const JOB_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum JobStatus {
+    /// Waiting for a free slot under `summarization.max_concurrent_jobs`
+    Queued,
     Running,
     Completed,
-    Failed { error: String },
+    Failed {
+        error: String,
+    },
 }
 
 impl std::fmt::Display for JobStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            JobStatus::Queued => write!(f, "Queued"),
             JobStatus::Running => write!(f, "Running"),
             JobStatus::Completed => write!(f, "Completed"),
             JobStatus::Failed { error } => write!(f, "Failed: {}", error),
@@ -26,12 +40,35 @@ impl std::fmt::Display for JobStatus {
     }
 }
 
+impl JobStatus {
+    /// Status name without the failure reason
+    pub fn label(&self) -> &'static str {
+        match self {
+            JobStatus::Queued => "Queued",
+            JobStatus::Running => "Running",
+            JobStatus::Completed => "Completed",
+            JobStatus::Failed { .. } => "Failed",
+        }
+    }
+
+    /// Lowercase identifier used by the API and `jobs list --json`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Completed => "completed",
+            JobStatus::Failed { .. } => "failed",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub enum JobType {
     SessionEnd,
     AutoSummarize,
     #[default]
     Manual,
+    Digest,
 }
 
 impl std::fmt::Display for JobType {
@@ -40,10 +77,49 @@ impl std::fmt::Display for JobType {
             JobType::SessionEnd => write!(f, "Session End"),
             JobType::AutoSummarize => write!(f, "Auto Summarize"),
             JobType::Manual => write!(f, "Manual"),
+            JobType::Digest => write!(f, "Digest"),
         }
     }
 }
 
+impl JobType {
+    /// Identifier used by the API and `jobs list --json`
+    pub fn key(&self) -> &'static str {
+        match self {
+            JobType::SessionEnd => "session_end",
+            JobType::AutoSummarize => "auto_summarize",
+            JobType::Manual => "manual",
+            JobType::Digest => "digest",
+        }
+    }
+}
+
+/// Claude usage recorded while a job runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct JobMetrics {
+    /// Number of claude CLI invocations
+    pub invocations: u32,
+    /// Invocations that were retries of a failed attempt
+    pub retries: u32,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+impl JobMetrics {
+    /// Input and output tokens combined
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+
+    /// Add another set of metrics to this one
+    pub fn add(&mut self, other: &JobMetrics) {
+        self.invocations += other.invocations;
+        self.retries += other.retries;
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JobInfo {
     pub id: String,
@@ -55,6 +131,14 @@ pub struct JobInfo {
     pub status: JobStatus,
     #[serde(default)]
     pub job_type: JobType,
+    #[serde(default)]
+    pub metrics: JobMetrics,
+    /// `daily` arguments that start the job, kept so a queued job can be spawned later
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// ID of the failed job this one retries
+    #[serde(default)]
+    pub retry_of: Option<String>,
 }
 
 impl JobInfo {
@@ -69,6 +153,15 @@ impl JobInfo {
         end - self.started_at
     }
 
+    /// Task name cut to at most `max` characters, ending in "..." when cut
+    pub fn short_task_name(&self, max: usize) -> String {
+        if self.task_name.chars().count() <= max {
+            return self.task_name.clone();
+        }
+        let kept: String = self.task_name.chars().take(max.saturating_sub(3)).collect();
+        format!("{}...", kept)
+    }
+
     /// Format elapsed time as human-readable string
     pub fn elapsed_human(&self) -> String {
         let elapsed = self.elapsed();
@@ -86,6 +179,8 @@ impl JobInfo {
 
 pub struct JobManager {
     jobs_dir: PathBuf,
+    /// `summarization.max_concurrent_jobs`, for starting queued jobs when a running one dies
+    max_concurrent_jobs: usize,
 }
 
 impl JobManager {
@@ -94,7 +189,10 @@ impl JobManager {
         let jobs_dir = config.storage_path().join("jobs");
         fs::create_dir_all(&jobs_dir).context("Failed to create jobs directory")?;
 
-        Ok(Self { jobs_dir })
+        Ok(Self {
+            jobs_dir,
+            max_concurrent_jobs: config.summarization.max_concurrent_jobs,
+        })
     }
 
     /// Generate a unique job ID
@@ -132,17 +230,139 @@ impl JobManager {
             finished_at: None,
             status: JobStatus::Running,
             job_type,
+            metrics: JobMetrics::default(),
+            args: Vec::new(),
+            retry_of: None,
+        };
+
+        self.save_job(&info)?;
+        Ok(info)
+    }
+
+    /// Register a job that waits for a free slot before it is spawned
+    pub fn enqueue(
+        &self,
+        job_id: &str,
+        task_name: &str,
+        transcript_path: &Path,
+        job_type: JobType,
+        args: Vec<String>,
+        retry_of: Option<String>,
+    ) -> Result<JobInfo> {
+        let info = JobInfo {
+            id: job_id.to_string(),
+            pid: 0,
+            task_name: task_name.to_string(),
+            transcript_path: transcript_path.to_path_buf(),
+            started_at: Local::now(),
+            finished_at: None,
+            status: JobStatus::Queued,
+            job_type,
+            metrics: JobMetrics::default(),
+            args,
+            retry_of,
         };
 
         self.save_job(&info)?;
         Ok(info)
     }
 
+    /// Mark a queued job as running under the given process.
+    ///
+    /// A fast job may already have recorded how it ended; that status is kept.
+    pub fn mark_started(&self, job_id: &str, pid: u32) -> Result<JobInfo> {
+        let mut info = self.load_job(job_id)?;
+        if info.status != JobStatus::Queued {
+            return Ok(info);
+        }
+        info.pid = pid;
+        info.status = JobStatus::Running;
+        info.started_at = Local::now();
+        self.save_job(&info)?;
+        Ok(info)
+    }
+
+    /// Start queued jobs while slots are free, oldest first
+    pub fn run_queue(&self, max_concurrent: usize) -> Result<Vec<JobInfo>> {
+        let free = self.free_slots(max_concurrent)?;
+
+        let mut started = Vec::new();
+        for job in self.queued()?.into_iter().take(free) {
+            match self.start(&job) {
+                Ok(info) => started.push(info),
+                Err(e) => {
+                    warn!("Failed to start queued job {}: {}", job.id, e);
+                    let _ = self.mark_failed(&job.id, &e.to_string());
+                }
+            }
+        }
+
+        Ok(started)
+    }
+
+    /// Spawn the detached `daily <args> --job-id <id>` process for a queued job and mark
+    /// it running
+    pub fn start(&self, job: &JobInfo) -> Result<JobInfo> {
+        // Redirect output to the job log
+        let (stdout_file, stderr_file) = match self.create_log_file(&job.id) {
+            Ok(f) => {
+                let f2 = f.try_clone().unwrap_or_else(|_| {
+                    std::fs::File::create("/dev/null").expect("Failed to open /dev/null")
+                });
+                (Stdio::from(f), Stdio::from(f2))
+            }
+            Err(_) => (Stdio::null(), Stdio::null()),
+        };
+
+        let exe = std::env::current_exe().context("Failed to get current executable")?;
+
+        let mut cmd = Command::new(&exe);
+        cmd.args(&job.args)
+            .args(["--job-id", &job.id])
+            .stdin(Stdio::null())
+            .stdout(stdout_file)
+            .stderr(stderr_file);
+
+        // Create a new process group so Ctrl+C/Cmd+C doesn't kill the background process
+        #[cfg(unix)]
+        cmd.process_group(0);
+
+        let child = cmd
+            .spawn()
+            .context("Failed to spawn background summarization process")?;
+
+        self.mark_started(&job.id, child.id())
+    }
+
+    /// Number of job slots free under a concurrency limit (0 = unlimited)
+    pub fn free_slots(&self, max_concurrent: usize) -> Result<usize> {
+        if max_concurrent == 0 {
+            return Ok(usize::MAX);
+        }
+        let running = self
+            .list(false)?
+            .iter()
+            .filter(|job| job.status == JobStatus::Running)
+            .count();
+        Ok(max_concurrent.saturating_sub(running))
+    }
+
+    /// Queued jobs, oldest first
+    pub fn queued(&self) -> Result<Vec<JobInfo>> {
+        let mut jobs: Vec<JobInfo> = self
+            .list(false)?
+            .into_iter()
+            .filter(|job| job.status == JobStatus::Queued)
+            .collect();
+        jobs.reverse();
+        Ok(jobs)
+    }
+
     /// Save job info to disk
     fn save_job(&self, info: &JobInfo) -> Result<()> {
         let path = self.job_path(&info.id);
         let content = serde_json::to_string_pretty(info)?;
-        fs::write(&path, content).context("Failed to save job info")?;
+        write_atomic(&path, content).context("Failed to save job info")?;
         Ok(())
     }
 
@@ -172,9 +392,17 @@ impl JobManager {
         self.save_job(&info)
     }
 
-    /// List all jobs, optionally filtering by status
+    /// Add claude usage to a job's recorded metrics
+    pub fn record_metrics(&self, job_id: &str, delta: &JobMetrics) -> Result<()> {
+        let mut info = self.load_job(job_id)?;
+        info.metrics.add(delta);
+        self.save_job(&info)
+    }
+
+    /// List all jobs; without `include_completed` only running and queued jobs
     pub fn list(&self, include_completed: bool) -> Result<Vec<JobInfo>> {
         let mut jobs = vec![];
+        let mut reaped = false;
 
         for entry in fs::read_dir(&self.jobs_dir)? {
             let entry = entry?;
@@ -190,9 +418,12 @@ impl JobManager {
                             };
                             info.finished_at = Some(Local::now());
                             let _ = self.save_job(&info);
+                            reaped = true;
                         }
 
-                        if include_completed || info.status == JobStatus::Running {
+                        if include_completed
+                            || matches!(info.status, JobStatus::Running | JobStatus::Queued)
+                        {
                             jobs.push(info);
                         }
                     }
@@ -200,11 +431,38 @@ impl JobManager {
             }
         }
 
+        // A crashed job never starts the queued ones waiting for its slot, so do it here
+        if reaped && jobs.iter().any(|job| job.status == JobStatus::Queued) {
+            match self.run_queue(self.max_concurrent_jobs) {
+                Ok(started) if !started.is_empty() => return self.list(include_completed),
+                Ok(_) => {}
+                Err(e) => warn!("Failed to start queued jobs: {}", e),
+            }
+        }
+
         // Sort by start time, newest first
-        jobs.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        jobs.sort_by_key(|j| std::cmp::Reverse(j.started_at));
         Ok(jobs)
     }
 
+    /// Running digest jobs, newest first
+    pub fn running_digests(&self) -> Result<Vec<JobInfo>> {
+        Ok(self
+            .list(false)?
+            .into_iter()
+            .filter(|job| job.job_type == JobType::Digest && job.status == JobStatus::Running)
+            .collect())
+    }
+
+    /// Find a running digest job for a date
+    pub fn running_digest(&self, date: &str) -> Result<Option<JobInfo>> {
+        let task_name = format!("digest-{}", date);
+        Ok(self
+            .running_digests()?
+            .into_iter()
+            .find(|job| job.task_name == task_name))
+    }
+
     /// Get log content for a job
     pub fn read_log(&self, job_id: &str, tail_lines: Option<usize>) -> Result<String> {
         let path = self.log_path(job_id);
@@ -220,10 +478,53 @@ impl JobManager {
         }
     }
 
-    /// Kill a running job
+    /// Read log output written after byte `offset`, returning it with the new offset.
+    ///
+    /// Shared by `jobs log --follow` and the dashboard log stream. A missing log reads
+    /// as empty, and a log that shrank (see `truncate_log_if_needed`) is re-read from the start.
+    pub fn read_log_since(&self, job_id: &str, offset: u64) -> Result<(String, u64)> {
+        use std::io::{Read, Seek, SeekFrom};
+
+        let Ok(mut file) = fs::File::open(self.log_path(job_id)) else {
+            return Ok((String::new(), offset));
+        };
+        let len = file.metadata()?.len();
+        let start = if len < offset { 0 } else { offset };
+        if len == start {
+            return Ok((String::new(), len));
+        }
+
+        file.seek(SeekFrom::Start(start))?;
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf)?;
+        let end = start + buf.len() as u64;
+        Ok((String::from_utf8_lossy(&buf).into_owned(), end))
+    }
+
+    /// Current size of a job's log in bytes, for following it from the end
+    pub fn log_len(&self, job_id: &str) -> u64 {
+        fs::metadata(self.log_path(job_id))
+            .map(|m| m.len())
+            .unwrap_or(0)
+    }
+
+    /// The job's status once it has left `Running`, or None while it still runs
+    pub fn stopped_status(&self, job_id: &str) -> Option<JobStatus> {
+        self.load_job(job_id)
+            .ok()
+            .map(|job| job.status)
+            .filter(|status| *status != JobStatus::Running)
+    }
+
+    /// Kill a running job, or cancel a queued one
     pub fn kill(&self, job_id: &str) -> Result<bool> {
         let info = self.load_job(job_id)?;
 
+        if info.status == JobStatus::Queued {
+            self.mark_failed(job_id, "Cancelled by user")?;
+            return Ok(true);
+        }
+
         if info.status != JobStatus::Running {
             return Ok(false);
         }
@@ -250,7 +551,9 @@ impl JobManager {
                 if let Ok(content) = fs::read_to_string(&path) {
                     if let Ok(info) = serde_json::from_str::<JobInfo>(&content) {
                         // Only remove completed/failed jobs older than cutoff
-                        if info.status != JobStatus::Running && info.started_at < cutoff {
+                        let finished =
+                            !matches!(info.status, JobStatus::Running | JobStatus::Queued);
+                        if finished && info.started_at < cutoff {
                             // Remove both json and log files
                             let _ = fs::remove_file(&path);
                             let _ = fs::remove_file(self.log_path(&info.id));
@@ -264,6 +567,31 @@ impl JobManager {
         Ok(removed)
     }
 
+    /// Finished jobs started more than `keep_days` ago that still have a log file
+    pub fn expired_logs(&self, keep_days: u32) -> Result<Vec<JobInfo>> {
+        let cutoff = Local::now() - chrono::Duration::days(keep_days as i64);
+        Ok(self
+            .list(true)?
+            .into_iter()
+            .filter(|job| {
+                !matches!(job.status, JobStatus::Running | JobStatus::Queued)
+                    && job.started_at < cutoff
+                    && self.log_path(&job.id).exists()
+            })
+            .collect())
+    }
+
+    /// Remove the logs of finished jobs older than `keep_days`, keeping their metadata
+    pub fn cleanup_logs(&self, keep_days: u32) -> Result<usize> {
+        let mut removed = 0;
+        for job in self.expired_logs(keep_days)? {
+            let path = self.log_path(&job.id);
+            fs::remove_file(&path).context(format!("Failed to remove {}", path.display()))?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
     /// Create a bounded log file for a job
     pub fn create_log_file(&self, job_id: &str) -> Result<std::fs::File> {
         let path = self.log_path(job_id);
@@ -321,25 +649,59 @@ fn rand_id() -> u32 {
 
 /// Check if a process is alive
 #[cfg(unix)]
-fn is_process_alive(pid: u32) -> bool {
+pub fn is_process_alive(pid: u32) -> bool {
     // kill with signal 0 checks if process exists without sending a signal
     unsafe { libc::kill(pid as i32, 0) == 0 }
 }
 
-#[cfg(not(unix))]
-fn is_process_alive(_pid: u32) -> bool {
-    // On non-Unix, assume alive (can be improved with platform-specific APIs)
+#[cfg(windows)]
+pub fn is_process_alive(pid: u32) -> bool {
+    use windows::Win32::Foundation::{CloseHandle, STILL_ACTIVE};
+    use windows::Win32::System::Threading::{
+        GetExitCodeProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    // A process that can't be opened is gone (or was never ours to track)
+    unsafe {
+        let Ok(handle) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) else {
+            return false;
+        };
+        let mut exit_code = 0u32;
+        let queried = GetExitCodeProcess(handle, &mut exit_code).is_ok();
+        let _ = CloseHandle(handle);
+        queried && exit_code == STILL_ACTIVE.0 as u32
+    }
+}
+
+/// Without a way to check, assume the job is still running rather than mark it failed
+#[cfg(not(any(unix, windows)))]
+pub fn is_process_alive(_pid: u32) -> bool {
     true
 }
 
 /// Kill a process
 #[cfg(unix)]
-fn kill_process(pid: u32) -> bool {
+pub fn kill_process(pid: u32) -> bool {
     unsafe { libc::kill(pid as i32, libc::SIGTERM) == 0 }
 }
 
-#[cfg(not(unix))]
-fn kill_process(_pid: u32) -> bool {
+#[cfg(windows)]
+pub fn kill_process(pid: u32) -> bool {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{OpenProcess, TerminateProcess, PROCESS_TERMINATE};
+
+    unsafe {
+        let Ok(handle) = OpenProcess(PROCESS_TERMINATE, false, pid) else {
+            return false;
+        };
+        let terminated = TerminateProcess(handle, 1).is_ok();
+        let _ = CloseHandle(handle);
+        terminated
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn kill_process(_pid: u32) -> bool {
     false
 }
 
@@ -347,6 +709,22 @@ fn kill_process(_pid: u32) -> bool {
 mod tests {
     use super::*;
 
+    #[cfg(windows)]
+    #[test]
+    fn test_windows_process_alive_and_kill() {
+        let mut child = std::process::Command::new("ping")
+            .args(["-n", "30", "127.0.0.1"])
+            .stdout(std::process::Stdio::null())
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+
+        assert!(is_process_alive(pid));
+        assert!(kill_process(pid));
+        child.wait().unwrap();
+        assert!(!is_process_alive(pid));
+    }
+
     #[test]
     fn test_sanitize_name() {
         assert_eq!(sanitize_name("my-project"), "my-project");
@@ -359,6 +737,7 @@ mod tests {
 
     #[test]
     fn test_job_status_display() {
+        assert_eq!(format!("{}", JobStatus::Queued), "Queued");
         assert_eq!(format!("{}", JobStatus::Running), "Running");
         assert_eq!(format!("{}", JobStatus::Completed), "Completed");
         assert_eq!(
@@ -371,4 +750,227 @@ mod tests {
             "Failed: test"
         );
     }
+
+    #[test]
+    fn test_digest_job_listed() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+        let manager = JobManager::new(&config).unwrap();
+
+        let job_id = JobManager::generate_job_id("digest-2026-01-16");
+        manager
+            .register(
+                &job_id,
+                std::process::id(),
+                "digest-2026-01-16",
+                Path::new("/tmp/daily.md"),
+                JobType::Digest,
+            )
+            .unwrap();
+
+        let jobs = manager.list(false).unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].job_type, JobType::Digest);
+        assert_eq!(format!("{}", jobs[0].job_type), "Digest");
+
+        let running = manager.running_digest("2026-01-16").unwrap().unwrap();
+        assert_eq!(running.id, job_id);
+        assert!(manager.running_digest("2026-01-15").unwrap().is_none());
+
+        manager.mark_completed(&job_id).unwrap();
+        assert!(manager.running_digests().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_follow_log() {
+        use std::io::Write;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+        let manager = JobManager::new(&config).unwrap();
+
+        let job_id = JobManager::generate_job_id("follow");
+        manager
+            .register(
+                &job_id,
+                std::process::id(),
+                "follow",
+                Path::new("/tmp/t.jsonl"),
+                JobType::Manual,
+            )
+            .unwrap();
+        assert_eq!(
+            manager.read_log_since(&job_id, 0).unwrap(),
+            (String::new(), 0)
+        );
+
+        let mut log = manager.create_log_file(&job_id).unwrap();
+        writeln!(log, "one").unwrap();
+        let (output, offset) = manager.read_log_since(&job_id, 0).unwrap();
+        assert_eq!((output.as_str(), offset), ("one\n", 4));
+        assert_eq!(manager.log_len(&job_id), 4);
+
+        writeln!(log, "two").unwrap();
+        assert_eq!(manager.read_log_since(&job_id, offset).unwrap().0, "two\n");
+        assert!(manager.stopped_status(&job_id).is_none());
+
+        // A truncated log is read again from the start
+        fs::write(manager.log_path(&job_id), "x\n").unwrap();
+        assert_eq!(
+            manager.read_log_since(&job_id, 8).unwrap(),
+            ("x\n".into(), 2)
+        );
+
+        manager.mark_completed(&job_id).unwrap();
+        assert_eq!(manager.stopped_status(&job_id), Some(JobStatus::Completed));
+    }
+
+    #[test]
+    fn test_cleanup_logs_keeps_metadata() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+        let manager = JobManager::new(&config).unwrap();
+
+        for (job_id, days_ago) in [("old", 10), ("recent", 1)] {
+            manager
+                .register(
+                    job_id,
+                    std::process::id(),
+                    job_id,
+                    Path::new("/tmp/t.jsonl"),
+                    JobType::Manual,
+                )
+                .unwrap();
+            manager.create_log_file(job_id).unwrap();
+            manager.mark_completed(job_id).unwrap();
+            let mut info = manager.load_job(job_id).unwrap();
+            info.started_at = Local::now() - chrono::Duration::days(days_ago);
+            manager.save_job(&info).unwrap();
+        }
+
+        let expired: Vec<String> = manager
+            .expired_logs(3)
+            .unwrap()
+            .into_iter()
+            .map(|job| job.id)
+            .collect();
+        assert_eq!(expired, vec!["old"]);
+
+        assert_eq!(manager.cleanup_logs(3).unwrap(), 1);
+        assert!(!manager.log_path("old").exists());
+        assert!(manager.log_path("recent").exists());
+        assert!(manager.load_job("old").is_ok());
+        assert_eq!(manager.cleanup_logs(3).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_record_metrics() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+        let manager = JobManager::new(&config).unwrap();
+
+        let job_id = JobManager::generate_job_id("metrics");
+        manager
+            .register(
+                &job_id,
+                std::process::id(),
+                "metrics",
+                Path::new("/tmp/t.jsonl"),
+                JobType::Manual,
+            )
+            .unwrap();
+
+        let call = JobMetrics {
+            invocations: 1,
+            retries: 0,
+            input_tokens: 1200,
+            output_tokens: 300,
+        };
+        manager.record_metrics(&job_id, &call).unwrap();
+        manager.record_metrics(&job_id, &call).unwrap();
+
+        let metrics = manager.load_job(&job_id).unwrap().metrics;
+        assert_eq!(metrics.invocations, 2);
+        assert_eq!(metrics.total_tokens(), 3000);
+    }
+
+    #[test]
+    fn test_queue_respects_free_slots() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+        let manager = JobManager::new(&config).unwrap();
+
+        manager
+            .register(
+                "running",
+                std::process::id(),
+                "running",
+                Path::new("/tmp/a.jsonl"),
+                JobType::SessionEnd,
+            )
+            .unwrap();
+        assert_eq!(manager.free_slots(2).unwrap(), 1);
+        assert_eq!(manager.free_slots(1).unwrap(), 0);
+        assert_eq!(manager.free_slots(0).unwrap(), usize::MAX);
+
+        let args = vec!["summarize".to_string(), "--foreground".to_string()];
+        manager
+            .enqueue(
+                "queued",
+                "queued",
+                Path::new("/tmp/b.jsonl"),
+                JobType::SessionEnd,
+                args.clone(),
+                None,
+            )
+            .unwrap();
+        assert_eq!(manager.list(false).unwrap().len(), 2);
+        assert_eq!(manager.free_slots(2).unwrap(), 1);
+
+        let queued = manager.queued().unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].args, args);
+
+        let started = manager.mark_started("queued", std::process::id()).unwrap();
+        assert_eq!(started.status, JobStatus::Running);
+        assert!(manager.queued().unwrap().is_empty());
+        assert_eq!(manager.free_slots(2).unwrap(), 0);
+
+        // A job that finished before its spawner marked it started stays finished
+        manager.mark_completed("queued").unwrap();
+        let started = manager.mark_started("queued", std::process::id()).unwrap();
+        assert_eq!(started.status, JobStatus::Completed);
+    }
+
+    #[test]
+    fn test_enqueue_retry_of() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+        let manager = JobManager::new(&config).unwrap();
+
+        for (id, retry_of) in [("original", None), ("retry", Some("original".to_string()))] {
+            manager
+                .enqueue(
+                    id,
+                    "task",
+                    Path::new("/tmp/t.jsonl"),
+                    JobType::Manual,
+                    vec![],
+                    retry_of,
+                )
+                .unwrap();
+        }
+        manager.mark_failed("original", "claude exited").unwrap();
+
+        // Saved with the job, before its process could update the file
+        let retry = manager.load_job("retry").unwrap();
+        assert_eq!(retry.retry_of.as_deref(), Some("original"));
+        assert!(manager.load_job("original").unwrap().retry_of.is_none());
+    }
 }
@@ -1,3 +1,6 @@
 mod manager;
 
-pub use manager::{JobInfo, JobManager, JobStatus, JobType};
+pub use manager::{
+    is_process_alive, kill_process, JobInfo, JobManager, JobMetrics, JobStatus, JobType,
+    JOB_TIME_FORMAT,
+};
@@ -0,0 +1,142 @@
+//! Log output on stderr, in the `[daily] ...` style the hooks have always printed.
+//!
+//! Only events from this crate are shown: `INFO` and above normally, `DEBUG` with
+//! `--verbose`, which also prefixes each line with the level and its spans.
+
+use std::fmt;
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::fmt::format::{self, FormatEvent, FormatFields};
+use tracing_subscriber::fmt::{FmtContext, FormattedFields};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::registry::LookupSpan;
+
+/// Install the global subscriber; call once at startup
+pub fn init(verbose: bool) {
+    let level = if verbose {
+        LevelFilter::DEBUG
+    } else {
+        LevelFilter::INFO
+    };
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .event_format(DailyFormat { verbose }),
+        )
+        .with(Targets::new().with_target(env!("CARGO_CRATE_NAME"), level))
+        .init();
+}
+
+/// `[daily] message`, with `Warning:`/`Error:` markers for those levels
+struct DailyFormat {
+    verbose: bool,
+}
+
+impl<S, N> FormatEvent<S, N> for DailyFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: format::Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        write!(writer, "[daily] ")?;
+
+        let level = *event.metadata().level();
+        if self.verbose && level > Level::INFO {
+            write!(writer, "{} ", level)?;
+        }
+        if self.verbose {
+            if let Some(scope) = ctx.event_scope() {
+                for span in scope.from_root() {
+                    write!(writer, "{}", span.name())?;
+                    let extensions = span.extensions();
+                    if let Some(fields) = extensions.get::<FormattedFields<N>>() {
+                        if !fields.is_empty() {
+                            write!(writer, "{{{}}}", fields)?;
+                        }
+                    }
+                    write!(writer, ": ")?;
+                }
+            }
+        }
+        match level {
+            Level::ERROR => write!(writer, "Error: ")?,
+            Level::WARN => write!(writer, "Warning: ")?,
+            _ => {}
+        }
+
+        ctx.field_format().format_fields(writer.by_ref(), event)?;
+        writeln!(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn capture(verbose: bool, log: impl FnOnce()) -> String {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let level = if verbose {
+            LevelFilter::DEBUG
+        } else {
+            LevelFilter::INFO
+        };
+        let subscriber = tracing_subscriber::registry()
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .with_writer(move || writer.clone())
+                    .event_format(DailyFormat { verbose }),
+            )
+            .with(level);
+        tracing::subscriber::with_default(subscriber, log);
+        let output = buffer.0.lock().unwrap().clone();
+        String::from_utf8(output).unwrap()
+    }
+
+    fn log_sample() {
+        let span = tracing::info_span!("summarize", task = "fix-auth");
+        let _entered = span.enter();
+        tracing::info!("Session archived: {}", "a.md");
+        tracing::warn!("Failed to update job status");
+        tracing::debug!("Claude call took {}ms", 120);
+    }
+
+    #[test]
+    fn test_default_format_matches_plain_lines() {
+        assert_eq!(
+            capture(false, log_sample),
+            "[daily] Session archived: a.md\n[daily] Warning: Failed to update job status\n"
+        );
+    }
+
+    #[test]
+    fn test_verbose_format_shows_level_and_spans() {
+        assert_eq!(
+            capture(true, log_sample),
+            "[daily] summarize{task=\"fix-auth\"}: Session archived: a.md\n\
+             [daily] summarize{task=\"fix-auth\"}: Warning: Failed to update job status\n\
+             [daily] DEBUG summarize{task=\"fix-auth\"}: Claude call took 120ms\n"
+        );
+    }
+}
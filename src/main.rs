@@ -2,9 +2,11 @@ mod archive;
 mod auto_summarize;
 mod cli;
 mod config;
+mod fs_util;
 mod hooks;
 mod insights;
 mod jobs;
+mod logging;
 mod server;
 mod summarizer;
 mod transcript;
@@ -16,6 +18,8 @@ use cli::args::{Cli, Commands, HookType, JobsAction};
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    let json = cli.json;
+    logging::init(cli.verbose);
 
     match cli.command {
         Commands::Init {
@@ -26,31 +30,108 @@ async fn main() -> Result<()> {
         Commands::Hook { hook_type } => match hook_type {
             HookType::SessionStart => hooks::session_start::handle().await,
             HookType::SessionEnd => hooks::session_end::handle().await,
+            HookType::UserPromptSubmit => hooks::user_prompt::handle().await,
         },
         Commands::View {
             date,
             summary_only,
             list,
-        } => cli::commands::view::run(date, summary_only, list).await,
+            chain,
+            session,
+            tag,
+            open_web,
+            all,
+            days,
+        } => match (chain, session, tag) {
+            (Some(session), _, _) => cli::commands::view::run_chain(date, session, json).await,
+            (None, Some(session), _) => cli::commands::view::run_session(date, session, json).await,
+            (None, None, Some(tag)) => cli::commands::view::run_tag(date, tag, json).await,
+            (None, None, None) if all => cli::commands::view::run_all(days, json).await,
+            (None, None, None) => {
+                cli::commands::view::run(date, summary_only, list, open_web, json).await
+            }
+        },
         Commands::Today { summary_only, list } => {
-            cli::commands::view::run_today(summary_only, list).await
+            cli::commands::view::run_today(summary_only, list, json).await
         }
         Commands::Yest { summary_only, list } => {
-            cli::commands::view::run_yesterday(summary_only, list).await
+            cli::commands::view::run_yesterday(summary_only, list, json).await
         }
         Commands::Summarize {
             transcript,
+            session_id,
             task_name,
             cwd,
             foreground,
             job_id,
-        } => cli::commands::summarize::run(transcript, task_name, cwd, foreground, job_id).await,
+            title,
+            date,
+            tags,
+            dry_run,
+            model,
+        } => {
+            let transcript = cli::commands::summarize::resolve_transcript(transcript, session_id)?;
+            if dry_run {
+                cli::commands::summarize::run_dry_run(transcript, cwd).await
+            } else {
+                let overrides = archive::session::SessionOverrides { title, date, tags };
+                cli::commands::summarize::run(
+                    transcript, task_name, cwd, foreground, job_id, overrides, model,
+                )
+                .await
+            }
+        }
+        Commands::Import { dir, date, dry_run } => {
+            cli::commands::import::run(dir, date, dry_run).await
+        }
         Commands::Digest {
             relative_date,
             date,
             background,
             force,
-        } => cli::commands::digest::run(relative_date, date, background, force).await,
+            preview,
+            dry_run,
+            all,
+            from,
+            to,
+            concurrency,
+            allow_today,
+            job_id,
+            model,
+        } => {
+            if all || from.is_some() {
+                cli::commands::digest::run_range(from, to, force, model, concurrency, allow_today)
+                    .await
+            } else {
+                cli::commands::digest::run(
+                    relative_date,
+                    date,
+                    background,
+                    force,
+                    preview,
+                    dry_run,
+                    allow_today,
+                    job_id,
+                    model,
+                )
+                .await
+            }
+        }
+        Commands::Daemon => cli::commands::daemon::run().await,
+        Commands::Watch => cli::commands::watch::run().await,
+        Commands::Undigest { date } => cli::commands::digest::run_undigest(date).await,
+        Commands::Delete { date, yes } => cli::commands::delete::run(date, yes).await,
+        Commands::Merge {
+            sessions,
+            date,
+            title,
+            force,
+        } => cli::commands::merge::run(date, sessions, title, force).await,
+        Commands::Replay { date, session } => cli::commands::replay::run(date, session).await,
+        Commands::Archive { older_than_days } => cli::commands::archive::run(older_than_days).await,
+        Commands::MigrateLayout { layout, dry_run } => {
+            cli::commands::migrate_layout::run(layout, dry_run).await
+        }
         Commands::ExtractSkill {
             date,
             session,
@@ -61,35 +142,97 @@ async fn main() -> Result<()> {
             session,
             output,
         } => cli::commands::extract::run_command(date, session, output).await,
-        Commands::ReviewSkills { install, delete } => {
-            cli::commands::skills::run_review(install, delete).await
-        }
+        Commands::ReviewSkills {
+            install,
+            delete,
+            list,
+            force,
+        } => cli::commands::skills::run_review(install, delete, list, force).await,
         Commands::Config {
             set_storage,
+            set,
             show,
             interactive,
-        } => cli::commands::config::run(set_storage, show, interactive).await,
+            validate_templates,
+            export,
+            import,
+        } => {
+            cli::commands::config::run(
+                set_storage,
+                set,
+                show,
+                interactive,
+                validate_templates,
+                export,
+                import,
+            )
+            .await
+        }
+        Commands::Status => cli::commands::status::run().await,
+        Commands::Doctor => cli::commands::doctor::run().await,
         Commands::Install { scope } => cli::commands::install::run(scope).await,
         Commands::Uninstall { scope } => cli::commands::uninstall::run(scope).await,
         Commands::Trash => cli::commands::trash::run().await,
         Commands::Update { check, version } => cli::commands::update::run(check, version).await,
         Commands::Jobs { action } => match action {
-            JobsAction::List { all } => cli::commands::jobs::list(all).await,
+            JobsAction::List {
+                all,
+                since,
+                format,
+                sort,
+            } => cli::commands::jobs::list(all, since, &sort, json || format == "json").await,
             JobsAction::Log {
                 job_id,
                 tail,
                 follow,
             } => cli::commands::jobs::log(job_id, tail, follow).await,
+            JobsAction::Info { job_id } => cli::commands::jobs::info(job_id).await,
             JobsAction::Kill { job_id } => cli::commands::jobs::kill(job_id).await,
-            JobsAction::Cleanup { days, dry_run } => {
-                cli::commands::jobs::cleanup(days, dry_run).await
-            }
+            JobsAction::RunQueue => cli::commands::jobs::run_queue().await,
+            JobsAction::Retry { job_id } => cli::commands::jobs::retry(job_id).await,
+            JobsAction::Cleanup {
+                days,
+                logs_only,
+                dry_run,
+            } => cli::commands::jobs::cleanup(days, logs_only, dry_run).await,
         },
         Commands::Insights { days } => cli::commands::insights::run(days).await,
+        Commands::Stats { days, cost } => cli::commands::stats::run(days, cost, json).await,
+        Commands::Weekly { date } => cli::commands::weekly::run(date).await,
+        Commands::Export {
+            date,
+            format,
+            output,
+        } => cli::commands::export::run(date, format, output).await,
+        Commands::Search {
+            query,
+            date_from,
+            date_to,
+            tag,
+            session_type,
+            regex,
+            limit,
+        } => {
+            let filters = cli::commands::search::SearchFilters {
+                date_from,
+                date_to,
+                tag,
+                session_type,
+            };
+            cli::commands::search::run(query, filters, regex, limit, json).await
+        }
+        Commands::Reindex => cli::commands::search::reindex().await,
+        Commands::Tag { rename, add, date } => cli::commands::tag::run(rename, add, date).await,
+        Commands::Tags => cli::commands::tag::list(json).await,
+        Commands::Show { status: true, .. } => cli::commands::show::status().await,
+        Commands::Show { stop: true, .. } => cli::commands::show::stop().await,
         Commands::Show {
             port,
             host,
             no_open,
-        } => cli::commands::show::run(port, host, !no_open).await,
+            token,
+            ..
+        } => cli::commands::show::run(port, host, (!no_open).then(|| "/".to_string()), token).await,
+        Commands::Open { date } => cli::commands::open::run(date).await,
     }
 }
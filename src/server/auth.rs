@@ -0,0 +1,273 @@
+use axum::{
+    extract::{Request, State},
+    http::{header, uri::Authority, HeaderValue, Method, StatusCode, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use super::dto::ApiResponse;
+use super::handlers::AppState;
+
+/// Require the auth token on API requests when one is set.
+///
+/// The token is read from `Authorization: Bearer <token>`, or from a `token` query
+/// parameter for WebSocket and EventSource clients, which cannot set headers.
+pub async fn require_token(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(expected) = state.auth_token.as_deref() else {
+        return next.run(request).await;
+    };
+
+    let provided = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::to_string)
+        .or_else(|| query_token(request.uri().query()));
+
+    if provided.is_some_and(|token| tokens_match(token.trim(), expected)) {
+        next.run(request).await
+    } else {
+        (
+            StatusCode::UNAUTHORIZED,
+            Json(ApiResponse::<()>::error("Missing or invalid auth token")),
+        )
+            .into_response()
+    }
+}
+
+/// Without an auth token, refuse changes requested by other sites.
+///
+/// Any web page can send requests to the dashboard, and a DNS-rebound name can even make
+/// them same-origin, so a POST/PATCH/DELETE is only accepted when the `Host` is
+/// `localhost` or an IP address and any `Origin` names the same host. Clients that send
+/// no `Origin`, like curl, are let through.
+pub async fn reject_cross_site(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let safe = matches!(
+        *request.method(),
+        Method::GET | Method::HEAD | Method::OPTIONS
+    );
+    if safe || state.auth_token.is_some() || is_same_site(request.headers()) {
+        next.run(request).await
+    } else {
+        (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::<()>::error(
+                "Cross-site request refused; set server.auth_token to allow remote changes",
+            )),
+        )
+            .into_response()
+    }
+}
+
+fn is_same_site(headers: &axum::http::HeaderMap) -> bool {
+    let host = headers
+        .get(header::HOST)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<Authority>().ok());
+    if host
+        .as_ref()
+        .is_some_and(|host| !is_local_host(host.host()))
+    {
+        return false;
+    }
+    let Some(origin) = headers.get(header::ORIGIN) else {
+        return true;
+    };
+    let origin_host = origin
+        .to_str()
+        .ok()
+        .and_then(|value| value.parse::<Uri>().ok())
+        .and_then(|uri| uri.host().map(str::to_string));
+    match (origin_host, host) {
+        // The Vite dev server proxies from another port of the same host
+        (Some(origin), Some(host)) => origin.eq_ignore_ascii_case(host.host()),
+        (Some(origin), None) => is_local_host(&origin),
+        (None, _) => false,
+    }
+}
+
+/// `localhost` or an IP address, which a rebound DNS name can't pose as
+pub fn is_local_host(host: &str) -> bool {
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .is_ok()
+}
+
+/// Whether a CORS `Origin` is a page served from this machine
+pub fn is_loopback_origin(origin: &HeaderValue) -> bool {
+    origin
+        .to_str()
+        .ok()
+        .and_then(|value| value.parse::<Uri>().ok())
+        .and_then(|uri| uri.host().map(str::to_string))
+        .is_some_and(|host| {
+            host.eq_ignore_ascii_case("localhost")
+                || host
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .parse::<IpAddr>()
+                    .is_ok_and(|ip| ip.is_loopback())
+        })
+}
+
+/// Value of the `token` query parameter
+fn query_token(query: Option<&str>) -> Option<String> {
+    form_urlencoded::parse(query?.as_bytes())
+        .find(|(key, _)| key == "token")
+        .map(|(_, value)| value.into_owned())
+}
+
+/// Compare tokens without returning early on the first differing byte
+fn tokens_match(provided: &str, expected: &str) -> bool {
+    provided.len() == expected.len()
+        && provided
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::server::create_router;
+    use axum::body::Body;
+    use tower::ServiceExt;
+
+    async fn status(token: Option<&str>, uri: &str, header: Option<&str>) -> StatusCode {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+        config.server.auth_token = token.map(String::from);
+        let app = create_router(Arc::new(AppState::new(config)));
+
+        let mut request = Request::builder().uri(uri);
+        if let Some(header) = header {
+            request = request.header(header::AUTHORIZATION, header);
+        }
+        app.oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_require_token() {
+        assert_eq!(status(None, "/api/health", None).await, StatusCode::OK);
+
+        let token = Some("s3cret");
+        assert_eq!(
+            status(token, "/api/health", None).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(token, "/api/health", Some("Bearer wrong")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(token, "/api/health", Some("Bearer s3cret")).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(token, "/api/health?token=s3cret", None).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(Some("a&b=c d"), "/api/health?token=a%26b%3Dc+d", None).await,
+            StatusCode::OK
+        );
+
+        // The SPA shell stays reachable so it can ask for the token
+        assert_ne!(status(token, "/", None).await, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_reject_cross_site() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+        let app = create_router(Arc::new(AppState::new(config)));
+
+        let status = |method: &str, headers: &[(&str, &str)]| {
+            let mut request = Request::builder()
+                .method(method)
+                .uri("/api/dates/2026-01-16");
+            for (name, value) in headers {
+                request = request.header(*name, *value);
+            }
+            let app = app.clone();
+            async move {
+                app.oneshot(request.body(Body::empty()).unwrap())
+                    .await
+                    .unwrap()
+                    .status()
+            }
+        };
+
+        let local = ("host", "127.0.0.1:31456");
+        for headers in [
+            vec![local, ("origin", "https://evil.example")],
+            vec![
+                ("host", "evil.example:31456"),
+                ("origin", "http://evil.example:31456"),
+            ],
+            vec![local, ("origin", "null")],
+        ] {
+            assert_eq!(
+                status("DELETE", &headers).await,
+                StatusCode::FORBIDDEN,
+                "{:?}",
+                headers
+            );
+        }
+        for headers in [
+            vec![local],
+            vec![local, ("origin", "http://127.0.0.1:5173")],
+            vec![
+                ("host", "localhost:31456"),
+                ("origin", "http://localhost:31456"),
+            ],
+        ] {
+            assert_ne!(
+                status("DELETE", &headers).await,
+                StatusCode::FORBIDDEN,
+                "{:?}",
+                headers
+            );
+        }
+        // Reads are left to CORS
+        assert_ne!(
+            status("GET", &[local, ("origin", "https://evil.example")]).await,
+            StatusCode::FORBIDDEN
+        );
+        assert!(is_loopback_origin(&HeaderValue::from_static(
+            "http://[::1]:5173"
+        )));
+        assert!(!is_loopback_origin(&HeaderValue::from_static(
+            "https://evil.example"
+        )));
+    }
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("abc", "abc"));
+        assert!(!tokens_match("abd", "abc"));
+        assert!(!tokens_match("ab", "abc"));
+    }
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
-use crate::jobs::{JobInfo, JobStatus, JobType};
+use crate::archive::search::SearchHit;
+use crate::archive::SessionLink;
+use crate::jobs::{JobInfo, JOB_TIME_FORMAT};
 
 /// Generic API response wrapper
 #[derive(Serialize)]
@@ -30,6 +32,49 @@ impl<T: Serialize> ApiResponse<T> {
     }
 }
 
+/// Default page size for paginated listings
+pub const DEFAULT_PER_PAGE: usize = 30;
+
+/// Optional `?page=&per_page=` query for listing endpoints
+#[derive(Deserialize, Default)]
+pub struct PaginationQuery {
+    pub page: Option<usize>,
+    pub per_page: Option<usize>,
+}
+
+impl PaginationQuery {
+    /// Whether the client asked for pagination at all
+    pub fn is_requested(&self) -> bool {
+        self.page.is_some() || self.per_page.is_some()
+    }
+
+    /// 1-based page number
+    pub fn page(&self) -> usize {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn per_page(&self) -> usize {
+        self.per_page.unwrap_or(DEFAULT_PER_PAGE).max(1)
+    }
+}
+
+/// One page of a listing
+#[derive(Serialize)]
+pub struct Page<T: Serialize> {
+    pub total: usize,
+    pub page: usize,
+    pub per_page: usize,
+    pub items: Vec<T>,
+}
+
+/// Listing response: a plain array unless pagination was requested
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum Listing<T: Serialize> {
+    All(Vec<T>),
+    Paged(Page<T>),
+}
+
 /// Date info for listing
 #[derive(Serialize)]
 pub struct DateInfo {
@@ -69,10 +114,31 @@ pub struct SessionDetailDto {
     pub content: String,
     pub metadata: SessionMetadata,
     pub file_path: String,
+    /// Archive of the session this one was resumed from
+    pub continued_from: Option<SessionRefDto>,
+}
+
+/// Reference to another session archive
+#[derive(Serialize)]
+pub struct SessionRefDto {
+    pub date: String,
+    pub name: String,
+    pub title: String,
+}
+
+impl From<SessionLink> for SessionRefDto {
+    fn from(link: SessionLink) -> Self {
+        Self {
+            date: link.date,
+            name: link.name,
+            title: link.title,
+        }
+    }
 }
 
 /// Session metadata extracted from frontmatter
-#[derive(Serialize, Default)]
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct SessionMetadata {
     pub title: String,
     pub date: String,
@@ -80,10 +146,12 @@ pub struct SessionMetadata {
     pub cwd: Option<String>,
     pub git_branch: Option<String>,
     pub duration: Option<String>,
+    pub confidence: Option<String>,
+    pub parent_session_id: Option<String>,
 }
 
 /// Job DTO for API responses
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct JobDto {
     pub id: String,
     pub pid: u32,
@@ -94,28 +162,22 @@ pub struct JobDto {
     pub started_at: String,
     pub finished_at: Option<String>,
     pub elapsed: String,
+    pub invocations: u32,
+    pub retries: u32,
+    pub total_tokens: u64,
 }
 
 impl From<JobInfo> for JobDto {
     fn from(info: JobInfo) -> Self {
-        let (status, status_type) = match &info.status {
-            JobStatus::Running => ("Running".to_string(), "running".to_string()),
-            JobStatus::Completed => ("Completed".to_string(), "completed".to_string()),
-            JobStatus::Failed { error } => (format!("Failed: {}", error), "failed".to_string()),
-        };
-
-        let job_type = match &info.job_type {
-            JobType::SessionEnd => "session_end".to_string(),
-            JobType::AutoSummarize => "auto_summarize".to_string(),
-            JobType::Manual => "manual".to_string(),
-        };
-
-        // Compute elapsed before moving fields
+        // Compute display fields before moving fields
+        let status = info.status.to_string();
+        let status_type = info.status.kind().to_string();
+        let job_type = info.job_type.key().to_string();
         let elapsed = info.elapsed_human();
-        let started_at = info.started_at.format("%Y-%m-%d %H:%M:%S").to_string();
+        let started_at = info.started_at.format(JOB_TIME_FORMAT).to_string();
         let finished_at = info
             .finished_at
-            .map(|t: chrono::DateTime<chrono::Local>| t.format("%Y-%m-%d %H:%M:%S").to_string());
+            .map(|t| t.format(JOB_TIME_FORMAT).to_string());
 
         Self {
             id: info.id,
@@ -127,6 +189,9 @@ impl From<JobInfo> for JobDto {
             started_at,
             finished_at,
             elapsed,
+            invocations: info.metrics.invocations,
+            retries: info.metrics.retries,
+            total_tokens: info.metrics.total_tokens(),
         }
     }
 }
@@ -143,11 +208,54 @@ pub struct JobLogDto {
 pub struct DigestResponse {
     pub message: String,
     pub session_count: usize,
+    pub job_id: String,
+    /// True when an existing digest job for the date was returned instead of a new one
+    pub already_running: bool,
+}
+
+/// Date deletion response
+#[derive(Serialize)]
+pub struct DeleteDateResponse {
+    pub date: String,
+    pub removed_sessions: usize,
+    pub had_digest: bool,
+}
+
+/// Session rename request
+#[derive(Deserialize)]
+pub struct RenameSessionRequest {
+    pub new_name: String,
+}
+
+/// Session rename response
+#[derive(Serialize)]
+pub struct RenameSessionResponse {
+    pub date: String,
+    pub old_name: String,
+    pub new_name: String,
+}
+
+/// A skill waiting for review, as listed by `GET /api/skills/pending`
+#[derive(Serialize)]
+pub struct PendingSkillDto {
+    /// `yyyy-mm-dd:name`, used in the install and delete routes
+    pub id: String,
+    pub date: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub content: String,
+}
+
+/// Result of installing or deleting a pending skill
+#[derive(Serialize)]
+pub struct SkillActionResponse {
+    pub id: String,
+    /// Installed `SKILL.md`, for installs
+    pub path: Option<String>,
 }
 
 /// WebSocket message types
-#[allow(dead_code)]
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(tag = "type", content = "data")]
 pub enum WsMessage {
     JobUpdated(JobDto),
@@ -369,3 +477,65 @@ pub struct ConversationDto {
     pub page_size: usize,
     pub has_more: bool,
 }
+
+/// `?allow_today=` query for the digest endpoint
+#[derive(Deserialize, Default)]
+pub struct DigestQuery {
+    /// Digest today's sessions so far; refused by default since the day isn't over
+    #[serde(default)]
+    pub allow_today: bool,
+}
+
+/// `?q=&from=&to=` query for the search endpoint
+#[derive(Deserialize, Default)]
+pub struct SearchQuery {
+    #[serde(default)]
+    pub q: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub regex: bool,
+}
+
+/// Identity of the running server, so `daily show` can tell it from a reused pid
+#[derive(Serialize)]
+pub struct ServerDto {
+    pub pid: u32,
+}
+
+/// Search response: the top results plus how many archives matched in total
+#[derive(Serialize)]
+pub struct SearchResponseDto {
+    pub total: usize,
+    pub results: Vec<SearchResultDto>,
+}
+
+/// Search result DTO, ranked by relevance
+#[derive(Serialize)]
+pub struct SearchResultDto {
+    pub date: String,
+    pub session_name: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    pub snippet: String,
+    pub line_number: usize,
+    pub match_count: usize,
+    pub score: usize,
+}
+
+impl From<SearchHit> for SearchResultDto {
+    fn from(hit: SearchHit) -> Self {
+        Self {
+            date: hit.date,
+            session_name: hit.session_name,
+            title: hit.title,
+            session_id: hit.session_id,
+            snippet: hit.snippet,
+            line_number: hit.line_number,
+            match_count: hit.match_count,
+            score: hit.score,
+        }
+    }
+}
@@ -1,62 +1,108 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
-    response::IntoResponse,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
     Json,
 };
+use futures_util::stream::{self, StreamExt};
+use serde::Serialize;
+use std::convert::Infallible;
 use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
 
+use crate::archive::frontmatter::Frontmatter;
+use crate::archive::search::{self, SearchOptions};
+use crate::archive::skills::SkillStore;
 use crate::archive::ArchiveManager;
-use crate::config::{save_config, Config};
+use crate::cli::commands::digest::spawn_background_digest;
+use crate::config::{is_valid_time, save_config, Config};
 use crate::insights::collector::InsightsData;
 use crate::insights::daily::DateInsights;
 use crate::jobs::JobManager;
-use crate::summarizer::Prompts;
+use crate::summarizer::{Prompts, SUPPORTED_LANGUAGES};
 
 use super::dto::*;
+use super::params::{DatePath, SessionPath, SkillPath};
+
+/// Capacity of the WebSocket event channel
+const EVENT_CHANNEL_CAPACITY: usize = 64;
+
+/// Maximum number of results returned by the search endpoint
+const MAX_SEARCH_RESULTS: usize = 50;
+
+/// How often the job log stream checks for new output
+const LOG_POLL_INTERVAL: Duration = Duration::from_millis(500);
 
 /// Shared application state
 pub struct AppState {
     pub config: RwLock<Config>,
+    /// Events forwarded to WebSocket clients
+    pub events: broadcast::Sender<WsMessage>,
+    /// Token required on API requests; kept apart from `config` so a one-run
+    /// `--token` is never written back by a settings update
+    pub auth_token: Option<String>,
+}
+
+impl AppState {
+    pub fn new(config: Config) -> Self {
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self {
+            auth_token: config.server.auth_token.clone(),
+            config: RwLock::new(config),
+            events,
+        }
+    }
+
+    /// Require `token` instead of the configured one, if given
+    pub fn with_auth_token(mut self, token: Option<String>) -> Self {
+        if token.is_some() {
+            self.auth_token = token;
+        }
+        self
+    }
+
+    /// Publish an event to connected WebSocket clients
+    pub fn publish(&self, message: WsMessage) {
+        // Sending only fails when no client is connected
+        let _ = self.events.send(message);
+    }
 }
 
 /// List all available dates
-pub async fn list_dates(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+pub async fn list_dates(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<PaginationQuery>,
+) -> impl IntoResponse {
     let config = state.config.read().unwrap().clone();
     let manager = ArchiveManager::new(config);
 
     match manager.list_dates() {
         Ok(dates) => {
-            let date_infos: Vec<DateInfo> = dates
-                .into_iter()
-                .map(|date| {
-                    let sessions = manager.list_sessions(&date).unwrap_or_default();
-                    let has_digest = manager
-                        .read_daily_summary(&date)
-                        .map(|content| {
-                            content.contains("## Overview")
-                                && !content.contains("No sessions recorded yet")
-                        })
-                        .unwrap_or(false);
+            let date_infos = paginate(dates, &query, |date| {
+                let sessions = manager.list_sessions(&date).unwrap_or_default();
+                let has_digest = manager.has_digest(&date);
 
-                    DateInfo {
-                        date,
-                        session_count: sessions.len(),
-                        has_digest,
-                    }
+                Some(DateInfo {
+                    date,
+                    session_count: sessions.len(),
+                    has_digest,
                 })
-                .collect();
+            });
 
             Json(ApiResponse::success(date_infos))
         }
-        Err(e) => Json(ApiResponse::<Vec<DateInfo>>::error(e.to_string())),
+        Err(e) => Json(ApiResponse::<Listing<DateInfo>>::error(e.to_string())),
     }
 }
 
 /// Get daily summary for a specific date
 pub async fn get_daily_summary(
     State(state): State<Arc<AppState>>,
-    Path(date): Path<String>,
+    DatePath(date): DatePath,
 ) -> impl IntoResponse {
     let config = state.config.read().unwrap().clone();
     let manager = ArchiveManager::new(config);
@@ -75,57 +121,135 @@ pub async fn get_daily_summary(
 /// List sessions for a specific date
 pub async fn list_sessions(
     State(state): State<Arc<AppState>>,
-    Path(date): Path<String>,
+    DatePath(date): DatePath,
+    Query(query): Query<PaginationQuery>,
 ) -> impl IntoResponse {
     let config = state.config.read().unwrap().clone();
     let manager = ArchiveManager::new(config);
 
     match manager.list_sessions(&date) {
         Ok(sessions) => {
-            let session_briefs: Vec<SessionBrief> = sessions
-                .into_iter()
-                .filter_map(|name| {
-                    manager.read_session(&date, &name).ok().map(|content| {
-                        let (title, summary) = extract_session_preview(&content);
-                        SessionBrief {
-                            name,
-                            title,
-                            summary_preview: summary,
-                        }
-                    })
-                })
-                .collect();
+            let session_briefs = paginate(sessions, &query, |name| {
+                session_brief(&manager, &date, name)
+            });
 
             Json(ApiResponse::success(session_briefs))
         }
-        Err(e) => Json(ApiResponse::<Vec<SessionBrief>>::error(e.to_string())),
+        Err(e) => Json(ApiResponse::<Listing<SessionBrief>>::error(e.to_string())),
+    }
+}
+
+/// Build a listing, only converting the items on the requested page
+fn paginate<T, U: Serialize>(
+    items: Vec<T>,
+    query: &PaginationQuery,
+    convert: impl FnMut(T) -> Option<U>,
+) -> Listing<U> {
+    if !query.is_requested() {
+        return Listing::All(items.into_iter().filter_map(convert).collect());
     }
+
+    let total = items.len();
+    let (page, per_page) = (query.page(), query.per_page());
+    let items = items
+        .into_iter()
+        .skip((page - 1).saturating_mul(per_page))
+        .take(per_page)
+        .filter_map(convert)
+        .collect();
+
+    Listing::Paged(Page {
+        total,
+        page,
+        per_page,
+        items,
+    })
 }
 
 /// Get session details
 pub async fn get_session(
     State(state): State<Arc<AppState>>,
-    Path((date, name)): Path<(String, String)>,
+    SessionPath(date, name): SessionPath,
 ) -> impl IntoResponse {
     let config = state.config.read().unwrap().clone();
     let manager = ArchiveManager::new(config);
 
     match manager.read_session(&date, &name) {
-        Ok(content) => {
-            let metadata = extract_session_metadata(&content);
-            let file_path = manager.session_archive_path(&date, &name);
-            let detail = SessionDetailDto {
-                name,
-                content,
-                metadata,
-                file_path: file_path.to_string_lossy().to_string(),
-            };
-            Json(ApiResponse::success(detail))
-        }
+        Ok(content) => Json(ApiResponse::success(session_detail(
+            &manager, &date, name, content,
+        ))),
         Err(e) => Json(ApiResponse::<SessionDetailDto>::error(e.to_string())),
     }
 }
 
+/// Build a session's list entry, skipping sessions that can't be read
+pub(crate) fn session_brief(
+    manager: &ArchiveManager,
+    date: &str,
+    name: String,
+) -> Option<SessionBrief> {
+    manager.read_session(date, &name).ok().map(|content| {
+        let (title, summary) = extract_session_preview(&content);
+        SessionBrief {
+            name,
+            title,
+            summary_preview: summary,
+        }
+    })
+}
+
+/// Build the session detail DTO, resolving the session it was resumed from
+pub(crate) fn session_detail(
+    manager: &ArchiveManager,
+    date: &str,
+    name: String,
+    content: String,
+) -> SessionDetailDto {
+    let metadata = extract_session_metadata(&content);
+    let file_path = manager.session_archive_path(date, &name);
+    let continued_from = metadata
+        .parent_session_id
+        .as_deref()
+        .and_then(|id| manager.find_session_by_id(id).ok().flatten())
+        .map(SessionRefDto::from);
+    SessionDetailDto {
+        name,
+        content,
+        metadata,
+        file_path: file_path.to_string_lossy().to_string(),
+        continued_from,
+    }
+}
+
+/// Search session archives and daily summaries
+pub async fn search_archives(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<SearchQuery>,
+) -> impl IntoResponse {
+    let config = state.config.read().unwrap().clone();
+    let manager = ArchiveManager::new(config);
+
+    let options = SearchOptions {
+        query: query.q,
+        date_from: query.from,
+        date_to: query.to,
+        tag: query.tag,
+        regex: query.regex,
+    };
+
+    match search::search(&manager, &options) {
+        Ok(hits) => Json(ApiResponse::success(SearchResponseDto {
+            total: hits.len(),
+            results: hits
+                .into_iter()
+                .take(MAX_SEARCH_RESULTS)
+                .map(Into::into)
+                .collect(),
+        })),
+        Err(e) => Json(ApiResponse::<SearchResponseDto>::error(e.to_string())),
+    }
+}
+
 /// List all jobs
 pub async fn list_jobs(State(state): State<Arc<AppState>>) -> impl IntoResponse {
     let config = state.config.read().unwrap();
@@ -174,6 +298,52 @@ pub async fn get_job_log(
     }
 }
 
+/// Stream a job's log as Server-Sent Events.
+///
+/// Starts at the end of the log, sends each new line as a message event, and
+/// finishes with a `done` event carrying the status once the job leaves `Running`.
+pub async fn stream_job_log(
+    State(state): State<Arc<AppState>>,
+    Path(job_id): Path<String>,
+) -> Response {
+    let manager = JobManager::new(&state.config.read().unwrap());
+    let manager = match manager {
+        Ok(manager) => manager,
+        Err(e) => return Json(ApiResponse::<()>::error(e.to_string())).into_response(),
+    };
+    if let Err(e) = manager.load_job(&job_id) {
+        return Json(ApiResponse::<()>::error(e.to_string())).into_response();
+    }
+
+    let offset = manager.log_len(&job_id);
+    let events = stream::unfold(Some((manager, job_id, offset)), |follow| async move {
+        let (manager, job_id, offset) = follow?;
+        tokio::time::sleep(LOG_POLL_INTERVAL).await;
+
+        let stopped = manager.stopped_status(&job_id);
+        let (output, offset) = manager
+            .read_log_since(&job_id, offset)
+            .unwrap_or((String::new(), offset));
+        let mut events: Vec<Event> = output
+            .lines()
+            .map(|line| Event::default().data(line.replace('\r', "")))
+            .collect();
+
+        match stopped {
+            Some(status) => {
+                events.push(Event::default().event("done").data(status.to_string()));
+                Some((events, None))
+            }
+            None => Some((events, Some((manager, job_id, offset)))),
+        }
+    })
+    .flat_map(|events| stream::iter(events.into_iter().map(Ok::<_, Infallible>)));
+
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
 /// Kill a job
 pub async fn kill_job(
     State(state): State<Arc<AppState>>,
@@ -184,6 +354,9 @@ pub async fn kill_job(
         Ok(manager) => match manager.kill(&job_id) {
             Ok(killed) => {
                 if killed {
+                    if let Ok(job) = manager.load_job(&job_id) {
+                        state.publish(WsMessage::JobUpdated(job.into()));
+                    }
                     Json(ApiResponse::success(serde_json::json!({ "killed": true })))
                 } else {
                     Json(ApiResponse::error("Job not running or could not be killed"))
@@ -195,12 +368,121 @@ pub async fn kill_job(
     }
 }
 
+/// Delete a date's archive
+pub async fn delete_date(
+    State(state): State<Arc<AppState>>,
+    DatePath(date): DatePath,
+) -> impl IntoResponse {
+    let config = state.config.read().unwrap().clone();
+    let manager = ArchiveManager::new(config);
+
+    match manager.delete_date(&date) {
+        Ok(deleted) => Json(ApiResponse::success(DeleteDateResponse {
+            date,
+            removed_sessions: deleted.sessions,
+            had_digest: deleted.had_digest,
+        })),
+        Err(e) => Json(ApiResponse::<DeleteDateResponse>::error(e.to_string())),
+    }
+}
+
+/// Rename a session archive, e.g. to fix a poor generated title
+pub async fn rename_session(
+    State(state): State<Arc<AppState>>,
+    SessionPath(date, name): SessionPath,
+    Json(req): Json<RenameSessionRequest>,
+) -> impl IntoResponse {
+    let config = state.config.read().unwrap().clone();
+    let manager = ArchiveManager::new(config);
+
+    match manager.rename_session(&date, &name, &req.new_name) {
+        Ok(_) => Json(ApiResponse::success(RenameSessionResponse {
+            date,
+            old_name: name,
+            new_name: req.new_name,
+        })),
+        Err(e) => Json(ApiResponse::<RenameSessionResponse>::error(e.to_string())),
+    }
+}
+
+/// List skills waiting for review
+pub async fn list_pending_skills(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let config = state.config.read().unwrap().clone();
+    let store = SkillStore::new(&config);
+
+    match store.list() {
+        Ok(skills) => {
+            let skills = skills
+                .into_iter()
+                .map(|skill| {
+                    let content = std::fs::read_to_string(&skill.path).unwrap_or_default();
+                    let description = Frontmatter::parse(&content)
+                        .get("description")
+                        .map(str::to_string);
+                    PendingSkillDto {
+                        id: skill.id(),
+                        date: skill.date,
+                        name: skill.name,
+                        description,
+                        content,
+                    }
+                })
+                .collect::<Vec<_>>();
+            Json(ApiResponse::success(skills))
+        }
+        Err(e) => Json(ApiResponse::<Vec<PendingSkillDto>>::error(e.to_string())),
+    }
+}
+
+/// Install a pending skill into `~/.claude/skills/`
+pub async fn install_skill(
+    State(state): State<Arc<AppState>>,
+    SkillPath(date, name): SkillPath,
+) -> impl IntoResponse {
+    let config = state.config.read().unwrap().clone();
+    let store = SkillStore::new(&config);
+
+    match store.install(&date, &name) {
+        Ok(path) => Json(ApiResponse::success(SkillActionResponse {
+            id: format!("{}:{}", date, name),
+            path: Some(path.display().to_string()),
+        })),
+        Err(e) => Json(ApiResponse::<SkillActionResponse>::error(e.to_string())),
+    }
+}
+
+/// Discard a pending skill
+pub async fn delete_skill(
+    State(state): State<Arc<AppState>>,
+    SkillPath(date, name): SkillPath,
+) -> impl IntoResponse {
+    let config = state.config.read().unwrap().clone();
+    let store = SkillStore::new(&config);
+
+    match store.delete(&date, &name) {
+        Ok(()) => Json(ApiResponse::success(SkillActionResponse {
+            id: format!("{}:{}", date, name),
+            path: None,
+        })),
+        Err(e) => Json(ApiResponse::<SkillActionResponse>::error(e.to_string())),
+    }
+}
+
 /// Trigger digest for a specific date
 pub async fn trigger_digest(
     State(state): State<Arc<AppState>>,
-    Path(date): Path<String>,
+    DatePath(date): DatePath,
+    Query(query): Query<DigestQuery>,
 ) -> impl IntoResponse {
     let config = state.config.read().unwrap().clone();
+    // Same guard as `daily digest`: sessions archived later today would be missing. The
+    // dashboard looks for `allow_today` in this error to offer digesting anyway
+    if date == config.today() && !query.allow_today {
+        return Json(ApiResponse::<DigestResponse>::error(format!(
+            "{} is today; digest is meant for finished days. Pass allow_today=true to digest the day so far",
+            date
+        )));
+    }
     let manager = ArchiveManager::new(config);
 
     // Check if there are sessions to digest
@@ -213,28 +495,37 @@ pub async fn trigger_digest(
                 )));
             }
 
-            // Spawn background digest process
-            let exe = match std::env::current_exe() {
-                Ok(e) => e,
-                Err(e) => {
-                    return Json(ApiResponse::<DigestResponse>::error(format!(
-                        "Failed to get executable: {}",
-                        e
-                    )));
-                }
-            };
+            let config = state.config.read().unwrap().clone();
 
-            match std::process::Command::new(&exe)
-                .args(["digest", "--date", &date])
-                .stdin(std::process::Stdio::null())
-                .stdout(std::process::Stdio::null())
-                .stderr(std::process::Stdio::null())
-                .spawn()
-            {
-                Ok(_) => Json(ApiResponse::success(DigestResponse {
-                    message: format!("Digest started for {} ({} sessions)", date, sessions.len()),
+            // Don't race a digest that is already writing this date's daily.md
+            let running = JobManager::new(&config)
+                .and_then(|jobs| jobs.running_digest(&date))
+                .ok()
+                .flatten();
+            if let Some(job) = running {
+                return Json(ApiResponse::success(DigestResponse {
+                    message: format!("Digest already in progress for {}", date),
                     session_count: sessions.len(),
-                })),
+                    job_id: job.id,
+                    already_running: true,
+                }));
+            }
+
+            // Spawn background digest process, tracked as a job
+            match spawn_background_digest(&config, &date, false, query.allow_today, None) {
+                Ok(job) => {
+                    state.publish(WsMessage::JobUpdated(job.clone().into()));
+                    Json(ApiResponse::success(DigestResponse {
+                        message: format!(
+                            "Digest started for {} ({} sessions)",
+                            date,
+                            sessions.len()
+                        ),
+                        session_count: sessions.len(),
+                        job_id: job.id,
+                        already_running: false,
+                    }))
+                }
                 Err(e) => Json(ApiResponse::<DigestResponse>::error(format!(
                     "Failed to start digest: {}",
                     e
@@ -245,15 +536,21 @@ pub async fn trigger_digest(
     }
 }
 
+/// Pid of this server, checked by `daily show` before trusting `.server.json`
+pub async fn server_info() -> Json<ApiResponse<ServerDto>> {
+    Json(ApiResponse::success(ServerDto {
+        pid: std::process::id(),
+    }))
+}
+
 /// Health check endpoint
 pub async fn health_check() -> impl IntoResponse {
     (StatusCode::OK, "OK")
 }
 
-/// Get current configuration
-pub async fn get_config(State(state): State<Arc<AppState>>) -> impl IntoResponse {
-    let config = state.config.read().unwrap();
-    let config_dto = ConfigDto {
+/// Build the API view of a configuration
+fn config_dto(config: &Config) -> ConfigDto {
+    ConfigDto {
         storage_path: config.storage.path.to_string_lossy().to_string(),
         model: config.summarization.model.clone(),
         summary_language: config.summarization.summary_language.clone(),
@@ -271,8 +568,13 @@ pub async fn get_config(State(state): State<Arc<AppState>>) -> impl IntoResponse
         auto_summarize_enabled: config.summarization.auto_summarize_enabled,
         auto_summarize_on_show: config.summarization.auto_summarize_on_show,
         auto_summarize_inactive_minutes: config.summarization.auto_summarize_inactive_minutes,
-    };
-    Json(ApiResponse::success(config_dto))
+    }
+}
+
+/// Get current configuration
+pub async fn get_config(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let config = state.config.read().unwrap();
+    Json(ApiResponse::success(config_dto(&config)))
 }
 
 /// Update configuration
@@ -280,26 +582,66 @@ pub async fn update_config(
     State(state): State<Arc<AppState>>,
     Json(req): Json<ConfigUpdateRequest>,
 ) -> impl IntoResponse {
-    let mut config = state.config.write().unwrap();
+    // Hold the write lock until saved, so concurrent updates can't drop each other's fields
+    let mut live = state.config.write().unwrap();
+    // Validate against a copy so a rejected request leaves the live config untouched
+    let mut config = live.clone();
+    if let Err(e) = apply_config_update(&mut config, req) {
+        return Json(ApiResponse::<ConfigDto>::error(e));
+    }
+
+    // Save config to file
+    if let Err(e) = save_config(&config) {
+        return Json(ApiResponse::<ConfigDto>::error(format!(
+            "Failed to save config: {}",
+            e
+        )));
+    }
+
+    let config_dto = config_dto(&config);
+    *live = config;
+    Json(ApiResponse::success(config_dto))
+}
+
+/// Apply the provided fields of an update request, rejecting invalid values
+fn apply_config_update(config: &mut Config, req: ConfigUpdateRequest) -> Result<(), String> {
+    // Reject custom templates missing required placeholders before changing anything
+    if let Some(templates) = &req.prompt_templates {
+        let updates = [
+            ("session_summary", &templates.session_summary),
+            ("daily_summary", &templates.daily_summary),
+            ("skill_extract", &templates.skill_extract),
+            ("command_extract", &templates.command_extract),
+        ];
+        for (kind, template) in updates {
+            if let Some(t) = template.as_deref().filter(|t| !t.is_empty()) {
+                let result = Prompts::validate_template(kind, t);
+                if !result.is_valid() {
+                    return Err(format!(
+                        "Invalid {} template: {}",
+                        kind,
+                        result.messages().join("; ")
+                    ));
+                }
+            }
+        }
+    }
 
     // Update fields if provided
     if let Some(lang) = req.summary_language {
-        if lang == "en" || lang == "zh" {
-            config.summarization.summary_language = lang;
-        } else {
-            return Json(ApiResponse::<ConfigDto>::error(
-                "Invalid language. Must be 'en' or 'zh'",
+        if !SUPPORTED_LANGUAGES.contains(&lang.as_str()) {
+            return Err(format!(
+                "Invalid language. Must be one of: {}",
+                SUPPORTED_LANGUAGES.join(", ")
             ));
         }
+        config.summarization.summary_language = lang;
     }
     if let Some(model) = req.model {
-        if model == "sonnet" || model == "haiku" {
-            config.summarization.model = model;
-        } else {
-            return Json(ApiResponse::<ConfigDto>::error(
-                "Invalid model. Must be 'sonnet' or 'haiku'",
-            ));
+        if model != "sonnet" && model != "haiku" {
+            return Err("Invalid model. Must be 'sonnet' or 'haiku'".into());
         }
+        config.summarization.model = model;
     }
     if let Some(enable) = req.enable_daily_summary {
         config.summarization.enable_daily_summary = enable;
@@ -311,15 +653,10 @@ pub async fn update_config(
         config.summarization.auto_digest_enabled = enable;
     }
     if let Some(time) = req.digest_time {
-        // Validate time format
-        let parts: Vec<&str> = time.split(':').collect();
-        if parts.len() == 2 {
-            if let (Ok(h), Ok(m)) = (parts[0].parse::<u32>(), parts[1].parse::<u32>()) {
-                if h < 24 && m < 60 {
-                    config.summarization.digest_time = time;
-                }
-            }
+        if !is_valid_time(&time) {
+            return Err("Invalid digest time. Must be HH:MM".into());
         }
+        config.summarization.digest_time = time;
     }
     if let Some(author) = req.author {
         config.archive.author = if author.is_empty() {
@@ -354,40 +691,13 @@ pub async fn update_config(
     }
     if let Some(minutes) = req.auto_summarize_inactive_minutes {
         // Validate range: 5 minutes to 8 hours
-        if (5..=480).contains(&minutes) {
-            config.summarization.auto_summarize_inactive_minutes = minutes;
+        if !(5..=480).contains(&minutes) {
+            return Err("Invalid inactive minutes. Must be between 5 and 480".into());
         }
+        config.summarization.auto_summarize_inactive_minutes = minutes;
     }
 
-    // Save config to file
-    if let Err(e) = save_config(&config) {
-        return Json(ApiResponse::<ConfigDto>::error(format!(
-            "Failed to save config: {}",
-            e
-        )));
-    }
-
-    // Return updated config
-    let config_dto = ConfigDto {
-        storage_path: config.storage.path.to_string_lossy().to_string(),
-        model: config.summarization.model.clone(),
-        summary_language: config.summarization.summary_language.clone(),
-        enable_daily_summary: config.summarization.enable_daily_summary,
-        enable_extraction_hints: config.summarization.enable_extraction_hints,
-        auto_digest_enabled: config.summarization.auto_digest_enabled,
-        digest_time: config.summarization.digest_time.clone(),
-        author: config.archive.author.clone(),
-        prompt_templates: PromptTemplatesDto {
-            session_summary: config.prompt_templates.session_summary.clone(),
-            daily_summary: config.prompt_templates.daily_summary.clone(),
-            skill_extract: config.prompt_templates.skill_extract.clone(),
-            command_extract: config.prompt_templates.command_extract.clone(),
-        },
-        auto_summarize_enabled: config.summarization.auto_summarize_enabled,
-        auto_summarize_on_show: config.summarization.auto_summarize_on_show,
-        auto_summarize_inactive_minutes: config.summarization.auto_summarize_inactive_minutes,
-    };
-    Json(ApiResponse::success(config_dto))
+    Ok(())
 }
 
 /// Get default prompt templates
@@ -523,7 +833,7 @@ pub async fn get_insights(
 /// Get per-day insights combining session facet data
 pub async fn get_date_insights(
     State(state): State<Arc<AppState>>,
-    Path(date): Path<String>,
+    DatePath(date): DatePath,
 ) -> impl IntoResponse {
     let config = state.config.read().unwrap().clone();
 
@@ -563,7 +873,7 @@ pub async fn get_date_insights(
 /// Get session conversation (transcript parsed into chat messages)
 pub async fn get_session_conversation(
     State(state): State<Arc<AppState>>,
-    Path((date, name)): Path<(String, String)>,
+    SessionPath(date, name): SessionPath,
     axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
 ) -> impl IntoResponse {
     let config = state.config.read().unwrap().clone();
@@ -624,7 +934,7 @@ pub async fn get_session_conversation(
 
 // Helper functions
 
-fn parse_daily_summary(date: &str, content: &str) -> DailySummaryDto {
+pub(crate) fn parse_daily_summary(date: &str, content: &str) -> DailySummaryDto {
     let extract_section = |header: &str| -> Option<String> {
         let pattern = format!("## {}\n", header);
         if let Some(start) = content.find(&pattern) {
@@ -644,30 +954,23 @@ fn parse_daily_summary(date: &str, content: &str) -> DailySummaryDto {
         }
     };
 
-    // Extract session names from frontmatter or content
-    let sessions: Vec<String> = if let Some(start) = content.find("sessions:") {
-        let start = start + 9;
-        let end = content[start..]
-            .find("\n---")
-            .or_else(|| content[start..].find("\ntags:"))
-            .map(|i| start + i)
-            .unwrap_or(content.len());
-        content[start..end]
-            .lines()
-            .filter_map(|line| {
-                let line = line.trim();
-                line.strip_prefix("- ")
-                    .map(|stripped| stripped.trim_matches('"').to_string())
-            })
-            .collect()
+    // Extract session names from frontmatter, falling back to the recorded count
+    let frontmatter = Frontmatter::parse(content);
+    let sessions = frontmatter.get_list("sessions");
+    let session_count = if sessions.is_empty() {
+        frontmatter
+            .get("session_count")
+            .or_else(|| frontmatter.get("total_sessions"))
+            .and_then(|count| count.parse().ok())
+            .unwrap_or(0)
     } else {
-        Vec::new()
+        sessions.len()
     };
 
     DailySummaryDto {
         date: date.to_string(),
         overview: extract_section("Overview").unwrap_or_default(),
-        session_count: sessions.len(),
+        session_count,
         sessions,
         insights: extract_section("Key Insights"),
         skills: extract_section("Skills"),
@@ -681,13 +984,8 @@ fn parse_daily_summary(date: &str, content: &str) -> DailySummaryDto {
 
 fn extract_session_preview(content: &str) -> (String, String) {
     // Extract title from frontmatter or first heading
-    let title = if let Some(start) = content.find("title:") {
-        let start = start + 6;
-        let end = content[start..]
-            .find('\n')
-            .map(|i| start + i)
-            .unwrap_or(content.len());
-        content[start..end].trim().trim_matches('"').to_string()
+    let title = if let Some(title) = Frontmatter::parse(content).get("title") {
+        title.to_string()
     } else if let Some(start) = content.find("# ") {
         let start = start + 2;
         let end = content[start..]
@@ -722,23 +1020,10 @@ fn extract_session_preview(content: &str) -> (String, String) {
 
 /// Extract transcript_path from session markdown YAML frontmatter
 fn extract_transcript_path(content: &str) -> Option<String> {
-    if let Some(stripped) = content.strip_prefix("---\n") {
-        if let Some(end) = stripped.find("\n---") {
-            let frontmatter = &stripped[..end];
-            for line in frontmatter.lines() {
-                if let Some((key, value)) = line.split_once(':') {
-                    let key = key.trim();
-                    if key == "transcript_path" {
-                        let value = value.trim().trim_matches('"');
-                        if value != "N/A" && !value.is_empty() {
-                            return Some(value.to_string());
-                        }
-                    }
-                }
-            }
-        }
-    }
-    None
+    Frontmatter::parse(content)
+        .get("transcript_path")
+        .filter(|value| *value != "N/A" && !value.is_empty())
+        .map(str::to_string)
 }
 
 /// Parse JSONL transcript file into paginated ConversationDto
@@ -810,16 +1095,12 @@ fn parse_transcript_to_conversation(
                     .or_else(|| entry.get("content"));
 
                 match content_val {
-                    Some(serde_json::Value::String(text)) => {
-                        if !text.trim().is_empty() {
-                            conversation_messages.push(ConversationMessage {
-                                role: "user".to_string(),
-                                content: vec![ConversationContentBlock::Text {
-                                    text: text.clone(),
-                                }],
-                                timestamp,
-                            });
-                        }
+                    Some(serde_json::Value::String(text)) if !text.trim().is_empty() => {
+                        conversation_messages.push(ConversationMessage {
+                            role: "user".to_string(),
+                            content: vec![ConversationContentBlock::Text { text: text.clone() }],
+                            timestamp,
+                        });
                     }
                     Some(serde_json::Value::Array(arr)) => {
                         // Tool result blocks - collect for pairing
@@ -896,19 +1177,17 @@ fn parse_transcript_to_conversation(
                             }
                         }
                     }
-                    Some(serde_json::Value::String(text)) => {
+                    Some(serde_json::Value::String(text)) if !text.trim().is_empty() => {
                         // Old format: content as string
-                        if !text.trim().is_empty() {
-                            if current_assistant_timestamp.is_none() {
-                                current_assistant_timestamp = entry
-                                    .get("timestamp")
-                                    .and_then(|v| v.as_str())
-                                    .map(String::from);
-                            }
-                            current_assistant_blocks.push(ConversationContentBlock::Text {
-                                text: text.to_string(),
-                            });
+                        if current_assistant_timestamp.is_none() {
+                            current_assistant_timestamp = entry
+                                .get("timestamp")
+                                .and_then(|v| v.as_str())
+                                .map(String::from);
                         }
+                        current_assistant_blocks.push(ConversationContentBlock::Text {
+                            text: text.to_string(),
+                        });
                     }
                     _ => {}
                 }
@@ -1042,29 +1321,135 @@ fn truncate_json_value(value: serde_json::Value, max_str_len: usize) -> serde_js
 }
 
 fn extract_session_metadata(content: &str) -> SessionMetadata {
-    let mut metadata = SessionMetadata::default();
-
-    // Parse YAML frontmatter
-    if let Some(stripped) = content.strip_prefix("---\n") {
-        if let Some(end) = stripped.find("\n---") {
-            let frontmatter = &stripped[..end];
-            for line in frontmatter.lines() {
-                if let Some((key, value)) = line.split_once(':') {
-                    let key = key.trim();
-                    let value = value.trim().trim_matches('"');
-                    match key {
-                        "title" => metadata.title = value.to_string(),
-                        "date" => metadata.date = value.to_string(),
-                        "session_id" => metadata.session_id = Some(value.to_string()),
-                        "cwd" => metadata.cwd = Some(value.to_string()),
-                        "git_branch" => metadata.git_branch = Some(value.to_string()),
-                        "duration" => metadata.duration = Some(value.to_string()),
-                        _ => {}
-                    }
-                }
-            }
-        }
+    Frontmatter::parse(content).to_struct().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_session_metadata_keeps_colons() {
+        let content = "---\ntitle: \"Fix: auth bug\"\ndate: 2026-01-16\nsession_id: abc\ncwd: \"C:\\\\work\\\\app\"\ngit_branch: \"feature/x\"\ntags: [claude-code]\ncreated: 2026-01-16T10:00:00+08:00\n---\n\n# Body\n";
+        let metadata = extract_session_metadata(content);
+
+        assert_eq!(metadata.title, "Fix: auth bug");
+        assert_eq!(metadata.date, "2026-01-16");
+        assert_eq!(metadata.session_id.as_deref(), Some("abc"));
+        assert_eq!(metadata.cwd.as_deref(), Some(r"C:\work\app"));
+        assert_eq!(metadata.git_branch.as_deref(), Some("feature/x"));
+        assert_eq!(metadata.duration, None);
+    }
+
+    #[test]
+    fn test_parse_daily_summary_empty_sessions() {
+        let content = "---\ndate: 2026-01-16\ntags: [daily-summary, claude-code]\nsessions: []\ntotal_sessions: 0\n---\n\n# Daily Summary - 2026-01-16\n\n## Overview\n\n_No sessions archived yet._\n";
+        let summary = parse_daily_summary("2026-01-16", content);
+
+        assert!(summary.sessions.is_empty());
+        assert_eq!(summary.session_count, 0);
+    }
+
+    #[test]
+    fn test_parse_daily_summary_one_session() {
+        let content = "---\ndate: 2026-01-16\nsessions:\n  - \"10_00-fix-auth\"\ntags: [daily-summary]\n---\n\n## Overview\n\nFixed auth.\n";
+        let summary = parse_daily_summary("2026-01-16", content);
+
+        assert_eq!(summary.sessions, vec!["10_00-fix-auth"]);
+        assert_eq!(summary.session_count, 1);
+        assert_eq!(summary.overview, "Fixed auth.");
+    }
+
+    #[test]
+    fn test_parse_daily_summary_many_sessions() {
+        let content = "---\ndate: 2026-01-16\nsessions:\n  - 10_00-one\n  - 11_00-two\n  - 12_00-three\n---\n\n## Overview\n\nBusy day.\n\n## Notes\n\n- not a session\n";
+        let summary = parse_daily_summary("2026-01-16", content);
+
+        assert_eq!(
+            summary.sessions,
+            vec!["10_00-one", "11_00-two", "12_00-three"]
+        );
+        assert_eq!(summary.session_count, 3);
+    }
+
+    #[test]
+    fn test_parse_daily_summary_session_count_fallback() {
+        let content = "---\ndate: 2026-01-16\nsession_count: 4\n---\n\n## Overview\n\nDone.\n";
+        let summary = parse_daily_summary("2026-01-16", content);
+
+        assert!(summary.sessions.is_empty());
+        assert_eq!(summary.session_count, 4);
+    }
+
+    #[test]
+    fn test_apply_config_update_rejects_invalid_digest_time() {
+        let mut config = Config::default();
+        let original = config.summarization.digest_time.clone();
+        let req: ConfigUpdateRequest = serde_json::from_value(serde_json::json!({
+            "model": "haiku",
+            "digest_time": "25:00"
+        }))
+        .unwrap();
+
+        assert!(apply_config_update(&mut config, req).is_err());
+        assert_eq!(config.summarization.digest_time, original);
+
+        let req: ConfigUpdateRequest = serde_json::from_value(serde_json::json!({
+            "model": "haiku",
+            "digest_time": "07:30"
+        }))
+        .unwrap();
+
+        apply_config_update(&mut config, req).unwrap();
+        assert_eq!(config.summarization.model, "haiku");
+        assert_eq!(config.summarization.digest_time, "07:30");
     }
 
-    metadata
+    #[tokio::test]
+    async fn test_trigger_digest_refuses_today() {
+        use axum::body::Body;
+        use axum::http::Request;
+        use tower::ServiceExt;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+        let today = config.today();
+        ArchiveManager::new(config.clone())
+            .write_session(&today, "proj", "10_00-auth", "## Summary\n\nFixed it.\n")
+            .unwrap();
+        let app = crate::server::create_router(Arc::new(AppState::new(config)));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("/api/dates/{}/digest", today))
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["success"], false);
+        assert!(body["error"].as_str().unwrap().contains("allow_today"));
+    }
+
+    #[test]
+    fn test_paginate_only_when_requested() {
+        let items: Vec<u32> = (1..=5).collect();
+
+        let all = paginate(items.clone(), &PaginationQuery::default(), Some);
+        assert!(matches!(all, Listing::All(ref v) if v.len() == 5));
+
+        let query = PaginationQuery {
+            page: Some(2),
+            per_page: Some(2),
+        };
+        let Listing::Paged(page) = paginate(items, &query, Some) else {
+            panic!("expected a paged listing");
+        };
+        assert_eq!(page.total, 5);
+        assert_eq!(page.page, 2);
+        assert_eq!(page.items, vec![3, 4]);
+    }
 }
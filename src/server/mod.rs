@@ -1,6 +1,9 @@
+pub mod auth;
 pub mod dto;
 pub mod handlers;
+pub mod params;
 pub mod router;
 pub mod static_files;
+pub mod ws;
 
 pub use router::create_router;
@@ -0,0 +1,167 @@
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Path},
+    http::{request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use super::dto::ApiResponse;
+use crate::archive::skills::parse_skill_ref;
+
+/// `:date` path parameter, rejected with 400 unless it is a `yyyy-mm-dd` date
+pub struct DatePath(pub String);
+
+/// `:date/:name` path parameters, rejected with 400 if they could escape the archive
+pub struct SessionPath(pub String, pub String);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for DatePath {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(date) = Path::<String>::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        validate_date(&date).map_err(bad_request)?;
+        Ok(Self(date))
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for SessionPath {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path((date, name)) = Path::<(String, String)>::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        validate_date(&date).map_err(bad_request)?;
+        validate_session_name(&name).map_err(bad_request)?;
+        Ok(Self(date, name))
+    }
+}
+
+/// `:id` of a pending skill (`yyyy-mm-dd:name`), rejected with 400 unless it parses
+pub struct SkillPath(pub String, pub String);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for SkillPath {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(id) = Path::<String>::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        let (date, name) = parse_skill_ref(&id).map_err(|e| bad_request(e.to_string()))?;
+        Ok(Self(date, name))
+    }
+}
+
+/// Check that a date matches `\d{4}-\d{2}-\d{2}`
+pub fn validate_date(date: &str) -> Result<(), String> {
+    let valid = date.len() == 10
+        && date.bytes().enumerate().all(|(i, b)| match i {
+            4 | 7 => b == b'-',
+            _ => b.is_ascii_digit(),
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid date '{}', expected yyyy-mm-dd", date))
+    }
+}
+
+/// Check that a session name is a plain file name, without separators or `..`
+pub fn validate_session_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.contains(['/', '\\', '\0']) || name.contains("..") {
+        Err(format!("Invalid session name '{}'", name))
+    } else {
+        Ok(())
+    }
+}
+
+fn bad_request(message: String) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ApiResponse::<()>::error(message)),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::server::{create_router, handlers::AppState};
+    use axum::body::Body;
+    use axum::http::Request;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_validate_date_and_name() {
+        assert!(validate_date("2026-01-16").is_ok());
+        for date in [
+            "..",
+            "2026-1-16",
+            "../../etc",
+            "2026-01-16/..",
+            "abcd-ef-gh",
+        ] {
+            assert!(validate_date(date).is_err(), "{}", date);
+        }
+
+        assert!(validate_session_name("10_00-fix-auth").is_ok());
+        for name in ["", "..", "../daily", "a/b", "a\\b"] {
+            assert!(validate_session_name(name).is_err(), "{}", name);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handlers_reject_traversal() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+        let app = create_router(Arc::new(AppState::new(config)));
+
+        let status = |method: &str, uri: &str| {
+            let request = Request::builder()
+                .method(method)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap();
+            let app = app.clone();
+            async move { app.oneshot(request).await.unwrap().status() }
+        };
+
+        for (method, uri) in [
+            ("GET", "/api/dates/..%2F..%2Fetc"),
+            ("GET", "/api/dates/..%2F..%2Fetc/sessions"),
+            ("GET", "/api/dates/2026-01-16/sessions/..%2F..%2Fpasswd"),
+            ("GET", "/api/dates/..%2F2026-01-16/sessions/x"),
+            (
+                "GET",
+                "/api/dates/2026-01-16/sessions/..%5Csecret/conversation",
+            ),
+            ("GET", "/api/dates/....%2F/insights"),
+            ("POST", "/api/dates/..%2F..%2Ftmp/digest"),
+            ("DELETE", "/api/dates/.."),
+            ("POST", "/api/skills/2026-01-16:..%2F..%2Fetc/install"),
+            ("DELETE", "/api/skills/not-a-skill"),
+        ] {
+            assert_eq!(
+                status(method, uri).await,
+                StatusCode::BAD_REQUEST,
+                "{}",
+                uri
+            );
+        }
+
+        assert_eq!(
+            status("GET", "/api/dates/2026-01-16/sessions").await,
+            StatusCode::OK
+        );
+        assert_eq!(status("GET", "/api/skills/pending").await, StatusCode::OK);
+    }
+}
@@ -1,12 +1,15 @@
 use axum::{
-    routing::{get, patch, post},
+    middleware,
+    routing::{delete, get, patch, post},
     Router,
 };
 use std::sync::Arc;
-use tower_http::cors::{Any, CorsLayer};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 
+use super::auth;
 use super::handlers::{self, AppState};
 use super::static_files::serve_static;
+use super::ws;
 
 /// Create the main router with all routes
 pub fn create_router(state: Arc<AppState>) -> Router {
@@ -14,19 +17,33 @@ pub fn create_router(state: Arc<AppState>) -> Router {
     let api_routes = Router::new()
         // Date/Archive routes
         .route("/dates", get(handlers::list_dates))
-        .route("/dates/:date", get(handlers::get_daily_summary))
+        .route(
+            "/dates/:date",
+            get(handlers::get_daily_summary).delete(handlers::delete_date),
+        )
         .route("/dates/:date/digest", post(handlers::trigger_digest))
         .route("/dates/:date/insights", get(handlers::get_date_insights))
         .route("/dates/:date/sessions", get(handlers::list_sessions))
         .route("/dates/:date/sessions/:name", get(handlers::get_session))
+        .route(
+            "/dates/:date/sessions/:name/rename",
+            post(handlers::rename_session),
+        )
         .route(
             "/dates/:date/sessions/:name/conversation",
             get(handlers::get_session_conversation),
         )
+        // Search
+        .route("/search", get(handlers::search_archives))
+        // Pending skills
+        .route("/skills/pending", get(handlers::list_pending_skills))
+        .route("/skills/:id", delete(handlers::delete_skill))
+        .route("/skills/:id/install", post(handlers::install_skill))
         // Job routes
         .route("/jobs", get(handlers::list_jobs))
         .route("/jobs/:id", get(handlers::get_job))
         .route("/jobs/:id/log", get(handlers::get_job_log))
+        .route("/jobs/:id/log/stream", get(handlers::stream_job_log))
         .route("/jobs/:id/kill", post(handlers::kill_job))
         // Config routes
         .route("/config", get(handlers::get_config))
@@ -35,14 +52,29 @@ pub fn create_router(state: Arc<AppState>) -> Router {
             "/config/templates/defaults",
             get(handlers::get_default_templates),
         )
+        // Live updates
+        .route("/ws", get(ws::ws_handler))
         // Health check
         .route("/health", get(handlers::health_check))
         // Insights routes
-        .route("/insights", get(handlers::get_insights));
+        .route("/insights", get(handlers::get_insights))
+        // Token check (no-op unless server.auth_token or --token is set)
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::require_token,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::reject_cross_site,
+        ))
+        // Added after the token check: `daily show` probes it without knowing `--token`
+        .route("/server", get(handlers::server_info));
 
-    // CORS layer for development
+    // CORS layer for development: only pages served from this machine may read the API
     let cors = CorsLayer::new()
-        .allow_origin(Any)
+        .allow_origin(AllowOrigin::predicate(|origin, _| {
+            auth::is_loopback_origin(origin)
+        }))
         .allow_methods(Any)
         .allow_headers(Any);
 
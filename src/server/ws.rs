@@ -0,0 +1,219 @@
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::IntoResponse,
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::archive::ArchiveManager;
+use crate::config::Config;
+use crate::jobs::{JobManager, JobStatus, JobType};
+
+use super::dto::{JobDto, WsMessage};
+use super::handlers::AppState;
+
+/// Interval between keepalive pings
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Interval between scans for job and archive changes
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Upgrade to a WebSocket that streams `WsMessage` events
+pub async fn ws_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<AppState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_socket(socket, state))
+}
+
+/// Send `Connected`, then forward broadcast events until the client goes away
+async fn handle_socket(mut socket: WebSocket, state: Arc<AppState>) {
+    let mut events = state.events.subscribe();
+    if send(&mut socket, &WsMessage::Connected).await.is_err() {
+        return;
+    }
+
+    let mut keepalive = tokio::time::interval(KEEPALIVE_INTERVAL);
+    keepalive.tick().await; // The first tick completes immediately
+
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(message) => {
+                    if send(&mut socket, &message).await.is_err() {
+                        break;
+                    }
+                }
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            },
+            _ = keepalive.tick() => {
+                if socket.send(Message::Ping(Vec::new())).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                // Pongs and client messages need no reply
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+/// Serialize and send one event
+async fn send(socket: &mut WebSocket, message: &WsMessage) -> Result<(), axum::Error> {
+    let text = serde_json::to_string(message).unwrap_or_default();
+    socket.send(Message::Text(text)).await
+}
+
+/// Watch for changes made by other processes and publish them.
+///
+/// Background jobs run as separate `daily` processes, so their progress is
+/// picked up by polling the job files and today's archive.
+pub fn spawn_watcher(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let mut snapshot = Snapshot::default();
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+        loop {
+            interval.tick().await;
+            // Nobody is listening; start over from a fresh baseline once someone connects
+            if state.events.receiver_count() == 0 {
+                snapshot = Snapshot::default();
+                continue;
+            }
+            let config = state.config.read().unwrap().clone();
+            for message in snapshot.changes(&config) {
+                state.publish(message);
+            }
+        }
+    });
+}
+
+/// Last seen job statuses and recent sessions
+#[derive(Default)]
+struct Snapshot {
+    initialized: bool,
+    /// Job ID to status type
+    jobs: HashMap<String, String>,
+    /// (date, session name) pairs for today and yesterday
+    sessions: HashSet<(String, String)>,
+}
+
+impl Snapshot {
+    /// Events since the previous scan; the first scan only records a baseline
+    fn changes(&mut self, config: &Config) -> Vec<WsMessage> {
+        let mut messages = Vec::new();
+
+        let jobs = JobManager::new(config)
+            .and_then(|manager| manager.list(true))
+            .unwrap_or_default();
+        // Forget jobs whose files were cleaned up
+        let ids: HashSet<&str> = jobs.iter().map(|job| job.id.as_str()).collect();
+        self.jobs.retain(|id, _| ids.contains(id.as_str()));
+        for job in jobs {
+            let digest_date = match (&job.job_type, &job.status) {
+                (JobType::Digest, JobStatus::Completed) => job
+                    .task_name
+                    .strip_prefix("digest-")
+                    .map(|date| date.to_string()),
+                _ => None,
+            };
+
+            let dto = JobDto::from(job);
+            if self.jobs.get(&dto.id) == Some(&dto.status_type) {
+                continue;
+            }
+            self.jobs.insert(dto.id.clone(), dto.status_type.clone());
+
+            if self.initialized {
+                messages.push(WsMessage::JobUpdated(dto));
+                if let Some(date) = digest_date {
+                    messages.push(WsMessage::DigestCompleted { date });
+                }
+            }
+        }
+
+        // Rebuilt each scan, so deleted sessions and days out of the window are dropped
+        let manager = ArchiveManager::new(config.clone());
+        let mut sessions = HashSet::new();
+        for date in [config.yesterday(), config.today()] {
+            for name in manager.list_sessions(&date).unwrap_or_default() {
+                let key = (date.clone(), name);
+                if self.initialized && !self.sessions.contains(&key) {
+                    messages.push(WsMessage::NewSession {
+                        date: key.0.clone(),
+                        name: key.1.clone(),
+                    });
+                }
+                sessions.insert(key);
+            }
+        }
+        self.sessions = sessions;
+
+        self.initialized = true;
+        messages
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_snapshot_reports_changes_after_baseline() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+        let today = config.today();
+
+        let archive = ArchiveManager::new(config.clone());
+        archive
+            .write_session(&today, "proj", "09_00-existing", "# existing\n")
+            .unwrap();
+
+        let mut snapshot = Snapshot::default();
+        assert!(snapshot.changes(&config).is_empty());
+
+        archive
+            .write_session(&today, "proj", "10_00-new", "# new\n")
+            .unwrap();
+        let jobs = JobManager::new(&config).unwrap();
+        let task_name = format!("digest-{}", today);
+        let job_id = JobManager::generate_job_id(&task_name);
+        jobs.register(
+            &job_id,
+            std::process::id(),
+            &task_name,
+            Path::new("/tmp/daily.md"),
+            JobType::Digest,
+        )
+        .unwrap();
+        jobs.mark_completed(&job_id).unwrap();
+
+        let messages = snapshot.changes(&config);
+        assert_eq!(messages.len(), 3);
+        assert!(
+            matches!(&messages[0], WsMessage::JobUpdated(job) if job.status_type == "completed")
+        );
+        assert!(matches!(&messages[1], WsMessage::DigestCompleted { date } if *date == today));
+        assert!(matches!(&messages[2], WsMessage::NewSession { name, .. } if name == "10_00-new"));
+
+        assert!(snapshot.changes(&config).is_empty());
+
+        // Removed jobs and sessions are forgotten
+        std::fs::remove_file(jobs.job_path(&job_id)).unwrap();
+        archive.delete_date(&today).unwrap();
+        assert!(snapshot.changes(&config).is_empty());
+        assert!(snapshot.jobs.is_empty());
+        assert!(snapshot.sessions.is_empty());
+    }
+}
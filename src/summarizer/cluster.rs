@@ -0,0 +1,231 @@
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Tags added to every archive, which carry no topical signal
+const GENERIC_TAGS: &[&str] = &[
+    "claude-code",
+    "session-archive",
+    "daily-summary",
+    "daily-archive",
+];
+
+/// Title words too common to indicate a shared topic
+const STOP_WORDS: &[&str] = &[
+    "the",
+    "and",
+    "for",
+    "with",
+    "from",
+    "into",
+    "fix",
+    "add",
+    "update",
+    "implement",
+    "refactor",
+    "improve",
+    "support",
+    "session",
+    "debug",
+    "change",
+    "new",
+];
+
+/// Minimum title-word overlap (Jaccard) for two sessions to share a topic
+const TITLE_SIMILARITY_THRESHOLD: f64 = 0.3;
+
+/// A session considered for the weekly digest
+#[derive(Debug, Clone, Serialize)]
+pub struct WeeklySession {
+    pub date: String,
+    pub title: String,
+    #[serde(skip)]
+    pub tags: Vec<String>,
+    pub summary: String,
+}
+
+/// A group of sessions sharing a topic
+#[derive(Debug, Clone, Serialize)]
+pub struct TopicCluster {
+    pub theme: String,
+    pub sessions: Vec<WeeklySession>,
+}
+
+/// Group sessions by shared tags, then by title-word similarity.
+///
+/// Clusters are ordered by size (largest first), then by theme.
+pub fn cluster_sessions(sessions: Vec<WeeklySession>) -> Vec<TopicCluster> {
+    let mut groups: Vec<Vec<WeeklySession>> = Vec::new();
+
+    for session in sessions {
+        let tags = topical_tags(&session);
+        let words = title_words(&session.title);
+
+        let by_tag = groups.iter().position(|group| {
+            group
+                .iter()
+                .any(|member| topical_tags(member).iter().any(|t| tags.contains(t)))
+        });
+
+        let target = by_tag.or_else(|| {
+            groups
+                .iter()
+                .enumerate()
+                .map(|(i, group)| {
+                    let group_words: HashSet<String> =
+                        group.iter().flat_map(|m| title_words(&m.title)).collect();
+                    (i, jaccard(&words, &group_words))
+                })
+                .filter(|(_, score)| *score >= TITLE_SIMILARITY_THRESHOLD)
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(i, _)| i)
+        });
+
+        match target {
+            Some(i) => groups[i].push(session),
+            None => groups.push(vec![session]),
+        }
+    }
+
+    let mut clusters: Vec<TopicCluster> = groups
+        .into_iter()
+        .map(|sessions| TopicCluster {
+            theme: theme_label(&sessions),
+            sessions,
+        })
+        .collect();
+
+    clusters.sort_by(|a, b| {
+        b.sessions
+            .len()
+            .cmp(&a.sessions.len())
+            .then_with(|| a.theme.cmp(&b.theme))
+    });
+    clusters
+}
+
+/// Tags of a session excluding generic archive tags
+fn topical_tags(session: &WeeklySession) -> Vec<String> {
+    session
+        .tags
+        .iter()
+        .filter(|t| !GENERIC_TAGS.contains(&t.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Meaningful lowercase words from a session title (time prefix removed)
+fn title_words(title: &str) -> HashSet<String> {
+    strip_time_prefix(title)
+        .split(|c: char| !c.is_alphanumeric())
+        .map(|w| w.to_lowercase())
+        .filter(|w| w.chars().count() >= 3 && !STOP_WORDS.contains(&w.as_str()))
+        .collect()
+}
+
+/// Strip the "HH_MM-" prefix from a session title
+fn strip_time_prefix(title: &str) -> &str {
+    let bytes = title.as_bytes();
+    if bytes.len() > 6
+        && bytes[..2].iter().all(u8::is_ascii_digit)
+        && bytes[2] == b'_'
+        && bytes[3..5].iter().all(u8::is_ascii_digit)
+        && bytes[5] == b'-'
+    {
+        &title[6..]
+    } else {
+        title
+    }
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let intersection = a.intersection(b).count() as f64;
+    let union = a.union(b).count() as f64;
+    intersection / union
+}
+
+/// Pick a human-readable label for a cluster
+fn theme_label(sessions: &[WeeklySession]) -> String {
+    let mut tag_counts: HashMap<String, usize> = HashMap::new();
+    for session in sessions {
+        for tag in topical_tags(session) {
+            *tag_counts.entry(tag).or_default() += 1;
+        }
+    }
+    if let Some(tag) = most_common(tag_counts) {
+        return tag;
+    }
+
+    if sessions.len() == 1 {
+        return strip_time_prefix(&sessions[0].title).replace(['-', '_'], " ");
+    }
+
+    let mut word_counts: HashMap<String, usize> = HashMap::new();
+    for session in sessions {
+        for word in title_words(&session.title) {
+            *word_counts.entry(word).or_default() += 1;
+        }
+    }
+    most_common(word_counts).unwrap_or_else(|| "misc".to_string())
+}
+
+/// Most frequent key, ties broken alphabetically
+fn most_common(counts: HashMap<String, usize>) -> Option<String> {
+    counts
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+        .map(|(key, _)| key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(title: &str, tags: &[&str]) -> WeeklySession {
+        WeeklySession {
+            date: "2026-01-16".to_string(),
+            title: title.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            summary: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_cluster_by_shared_tag() {
+        let clusters = cluster_sessions(vec![
+            session("10_00-login-flow", &["claude-code", "auth"]),
+            session("11_00-render-speed", &["claude-code"]),
+            session("14_00-token-expiry", &["auth"]),
+        ]);
+
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].theme, "auth");
+        assert_eq!(clusters[0].sessions.len(), 2);
+    }
+
+    #[test]
+    fn test_cluster_by_title_similarity() {
+        let clusters = cluster_sessions(vec![
+            session("10_00-fix-oauth-refresh", &[]),
+            session("11_00-oauth-refresh-tests", &[]),
+            session("12_00-dashboard-layout", &[]),
+        ]);
+
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].sessions.len(), 2);
+        assert_eq!(clusters[0].theme, "oauth");
+        assert_eq!(clusters[1].theme, "dashboard layout");
+    }
+
+    #[test]
+    fn test_generic_words_do_not_cluster() {
+        let clusters = cluster_sessions(vec![
+            session("10_00-fix-auth", &[]),
+            session("11_00-fix-perf", &[]),
+        ]);
+
+        assert_eq!(clusters.len(), 2);
+    }
+}
@@ -1,25 +1,62 @@
 use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use std::io::Write;
+use std::path::PathBuf;
 use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::time::Duration;
 
+use super::cluster::{cluster_sessions, WeeklySession};
 use super::prompts::Prompts;
-use crate::archive::{ArchiveManager, DailySummary, SessionArchive};
+use super::TemplateEngine;
+use crate::archive::frontmatter::Frontmatter;
+use crate::archive::session::{content_hash, markdown_section, SessionOverrides, SessionUsage};
+use crate::archive::{ArchiveManager, DailySummary, SessionArchive, WeeklySummary};
 use crate::config::Config;
-use crate::transcript::TranscriptParser;
+use crate::jobs::{JobManager, JobMetrics};
+use crate::transcript::source::parse_transcript;
+use crate::transcript::{TranscriptData, TranscriptParser};
+use tracing::{debug, info, warn};
+
+/// A JSON object Claude is asked for, with a placeholder for each field it may omit
+trait ResponseSchema: DeserializeOwned {
+    /// Name used when logging missing fields
+    const NAME: &'static str;
+    /// Expected text fields and the placeholder used when one is missing or blank
+    const FIELDS: &'static [(&'static str, &'static str)];
+}
+
+/// Placeholder for a generated section Claude left out
+const NOT_PROVIDED: &str = "_Not provided._";
 
 /// Response structure from session summarization
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
 struct SessionSummaryResponse {
     topic: String,
     summary: String,
     decisions: String,
     learnings: String,
     skill_hints: String,
+    confidence: Option<String>,
+    tags: Vec<String>,
+}
+
+impl ResponseSchema for SessionSummaryResponse {
+    const NAME: &'static str = "Session summary";
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("topic", "session"),
+        ("summary", NOT_PROVIDED),
+        ("decisions", NOT_PROVIDED),
+        ("learnings", NOT_PROVIDED),
+        ("skill_hints", "None identified"),
+    ];
 }
 
 /// Response structure from daily summarization
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
 struct DailySummaryResponse {
     overview: String,
     session_details: String,
@@ -30,24 +67,404 @@ struct DailySummaryResponse {
     tomorrow_focus: String,
 }
 
+impl ResponseSchema for DailySummaryResponse {
+    const NAME: &'static str = "Daily summary";
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("overview", NOT_PROVIDED),
+        ("session_details", NOT_PROVIDED),
+        ("insights", NOT_PROVIDED),
+        ("skills", "None identified"),
+        ("commands", "None identified"),
+        ("reflections", NOT_PROVIDED),
+        ("tomorrow_focus", NOT_PROVIDED),
+    ];
+}
+
+/// Response structure from weekly summarization
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct WeeklySummaryResponse {
+    overview: String,
+    themes: String,
+    insights: String,
+    next_week: String,
+}
+
+impl ResponseSchema for WeeklySummaryResponse {
+    const NAME: &'static str = "Weekly summary";
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("overview", NOT_PROVIDED),
+        ("themes", NOT_PROVIDED),
+        ("insights", NOT_PROVIDED),
+        ("next_week", NOT_PROVIDED),
+    ];
+}
+
+/// Output of `claude --print --output-format json`
+#[derive(Debug, Deserialize)]
+struct ClaudeCliOutput {
+    result: String,
+    #[serde(default)]
+    is_error: bool,
+    #[serde(default)]
+    usage: ClaudeUsage,
+}
+
+/// Token usage reported by the Claude CLI
+#[derive(Debug, Default, Deserialize)]
+struct ClaudeUsage {
+    #[serde(default)]
+    input_tokens: u64,
+    #[serde(default)]
+    cache_creation_input_tokens: u64,
+    #[serde(default)]
+    cache_read_input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
+}
+
+impl ClaudeUsage {
+    fn to_metrics(&self) -> JobMetrics {
+        JobMetrics {
+            invocations: 1,
+            retries: 0,
+            input_tokens: self.input_tokens
+                + self.cache_creation_input_tokens
+                + self.cache_read_input_tokens,
+            output_tokens: self.output_tokens,
+        }
+    }
+}
+
+/// Response body of the Anthropic Messages API
+#[derive(Debug, Deserialize)]
+struct ApiMessageResponse {
+    content: Vec<ApiContentBlock>,
+    #[serde(default)]
+    usage: ClaudeUsage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiContentBlock {
+    #[serde(rename = "type")]
+    block_type: String,
+    #[serde(default)]
+    text: String,
+}
+
+impl ApiMessageResponse {
+    /// Concatenated text of all text blocks
+    fn text(&self) -> String {
+        self.content
+            .iter()
+            .filter(|block| block.block_type == "text")
+            .map(|block| block.text.as_str())
+            .collect()
+    }
+}
+
+/// Text and usage collected from a streamed (`stream: true`) Messages API response
+#[derive(Debug, Default)]
+struct StreamedMessage {
+    text: String,
+    usage: ClaudeUsage,
+    /// Whether `message_stop` arrived, i.e. the response is complete
+    stopped: bool,
+    /// Bytes of a server-sent event line not terminated yet
+    pending: Vec<u8>,
+}
+
+impl StreamedMessage {
+    /// Feed a chunk of the event stream and return the text it added
+    fn push(&mut self, chunk: &[u8]) -> Result<String> {
+        self.pending.extend_from_slice(chunk);
+        let mut added = String::new();
+        while let Some(end) = self.pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            // `event:` lines repeat the type carried in the data
+            let Some(data) = line.trim_end().strip_prefix("data:") else {
+                continue;
+            };
+            let Ok(event) = serde_json::from_str::<serde_json::Value>(data.trim()) else {
+                continue;
+            };
+            match event["type"].as_str() {
+                Some("message_start") => {
+                    if let Ok(usage) = serde_json::from_value(event["message"]["usage"].clone()) {
+                        self.usage = usage;
+                    }
+                }
+                Some("content_block_delta") => {
+                    if let Some(text) = event["delta"]["text"].as_str() {
+                        self.text.push_str(text);
+                        added.push_str(text);
+                    }
+                }
+                Some("message_delta") => {
+                    // Output tokens are reported as a running total
+                    if let Some(output_tokens) = event["usage"]["output_tokens"].as_u64() {
+                        self.usage.output_tokens = output_tokens;
+                    }
+                }
+                Some("message_stop") => self.stopped = true,
+                Some("error") => anyhow::bail!(
+                    "Anthropic API stream failed: {}",
+                    event["error"]["message"]
+                        .as_str()
+                        .unwrap_or("unknown error")
+                ),
+                _ => {}
+            }
+        }
+        Ok(added)
+    }
+}
+
+/// How the engine talks to Claude
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiBackend {
+    /// Spawn the `claude` CLI
+    Cli,
+    /// Call the Messages API directly
+    Api { api_key: String, base_url: String },
+}
+
+impl ApiBackend {
+    /// Select the backend from `summarization.backend`, falling back to the CLI without a key
+    pub fn from_config(config: &Config) -> Self {
+        if config.summarization.backend != "api" {
+            return ApiBackend::Cli;
+        }
+
+        let api_key = std::env::var("ANTHROPIC_API_KEY")
+            .ok()
+            .filter(|key| !key.is_empty())
+            .or_else(|| config.summarization.api_key.clone());
+        match api_key {
+            Some(api_key) => ApiBackend::Api {
+                api_key,
+                base_url: config
+                    .summarization
+                    .api_base_url
+                    .trim_end_matches('/')
+                    .to_string(),
+            },
+            None => {
+                warn!("backend is \"api\" but no API key is set, using claude CLI");
+                ApiBackend::Cli
+            }
+        }
+    }
+}
+
+/// Map the CLI model aliases to API model IDs
+fn api_model_id(model: &str) -> &str {
+    match model {
+        "sonnet" => "claude-sonnet-4-5",
+        "haiku" => "claude-haiku-4-5",
+        "opus" => "claude-opus-4-1",
+        other => other,
+    }
+}
+
+/// Failure of a single Claude invocation
+enum InvokeFailure {
+    /// Worth retrying: non-zero exit, empty output, rate limits, server errors
+    Transient(anyhow::Error),
+    /// Retrying cannot help, e.g. the CLI is not installed
+    Fatal(anyhow::Error),
+}
+
 /// Engine for summarizing transcripts using Claude CLI
 pub struct SummarizerEngine {
     config: Config,
+    job_id: Option<String>,
+    backend: ApiBackend,
+    /// Program spawned by the CLI backend
+    cli_program: PathBuf,
+    /// Delay before the first retry; doubles after each attempt
+    retry_delay: Duration,
 }
 
 impl SummarizerEngine {
     pub fn new(config: Config) -> Self {
-        Self { config }
+        let backend = ApiBackend::from_config(&config);
+        let cli_program = config
+            .summarization
+            .claude_binary_path
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("claude"));
+        Self {
+            config,
+            job_id: None,
+            backend,
+            cli_program,
+            retry_delay: Duration::from_secs(1),
+        }
     }
 
-    /// Invoke Claude CLI with a prompt and return the response
-    fn invoke_claude(&self, prompt: &str) -> Result<String> {
-        let mut child = Command::new("claude")
+    /// Record claude usage against a background job
+    pub fn with_job_id(mut self, job_id: Option<String>) -> Self {
+        self.job_id = job_id;
+        self
+    }
+
+    /// Invoke Claude with a prompt through the configured backend and return the response.
+    async fn invoke_claude(&self, prompt: &str) -> Result<String> {
+        Ok(self.invoke_claude_with_usage(prompt).await?.0)
+    }
+
+    /// Like `invoke_claude`, also returning the token usage of the successful call.
+    ///
+    /// Transient failures are retried up to `summarization.max_retries` times with
+    /// exponential backoff.
+    async fn invoke_claude_with_usage(&self, prompt: &str) -> Result<(String, JobMetrics)> {
+        let attempts = self.config.summarization.max_retries + 1;
+        let mut delay = self.retry_delay;
+
+        let mut attempt = 1;
+
+        loop {
+            debug!(
+                "Invoking Claude (attempt {}/{}, prompt {} bytes)",
+                attempt,
+                attempts,
+                prompt.len()
+            );
+            let result = match &self.backend {
+                ApiBackend::Cli => self.invoke_cli(prompt),
+                ApiBackend::Api { api_key, base_url } => {
+                    self.invoke_api(prompt, api_key, base_url).await
+                }
+            };
+
+            let error = match result {
+                Ok(response) => return Ok(response),
+                Err(InvokeFailure::Fatal(e)) => return Err(e),
+                Err(InvokeFailure::Transient(e)) => e,
+            };
+
+            if attempt == attempts {
+                return Err(error.context(format!("Claude failed after {} attempts", attempts)));
+            }
+
+            warn!(
+                "Claude call failed (attempt {}/{}), retrying in {}s: {}",
+                attempt,
+                attempts,
+                delay.as_secs_f32(),
+                error
+            );
+            self.record_metrics(&JobMetrics {
+                retries: 1,
+                ..Default::default()
+            });
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+            attempt += 1;
+        }
+    }
+
+    /// Invoke the Anthropic Messages API with a prompt
+    ///
+    /// Under a job the response is streamed, so the job log shows it as it's written.
+    async fn invoke_api(
+        &self,
+        prompt: &str,
+        api_key: &str,
+        base_url: &str,
+    ) -> std::result::Result<(String, JobMetrics), InvokeFailure> {
+        let stream = self.job_id.is_some();
+        let body = serde_json::json!({
+            "model": api_model_id(&self.config.summarization.model),
+            "max_tokens": self.config.summarization.max_tokens,
+            "messages": [{ "role": "user", "content": prompt }],
+            "stream": stream,
+        });
+
+        let response = reqwest::Client::new()
+            .post(format!("{}/v1/messages", base_url))
+            .header("x-api-key", api_key)
+            .header("anthropic-version", "2023-06-01")
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| {
+                InvokeFailure::Transient(
+                    anyhow::Error::new(e).context("Failed to reach the Anthropic API"),
+                )
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error = response.text().await.unwrap_or_default();
+            let error = anyhow::anyhow!("Anthropic API failed ({}): {}", status, error);
+            return Err(if status.as_u16() == 429 || status.is_server_error() {
+                InvokeFailure::Transient(error)
+            } else {
+                InvokeFailure::Fatal(error)
+            });
+        }
+
+        if stream {
+            return self.read_api_stream(response).await;
+        }
+
+        let message: ApiMessageResponse = response.json().await.map_err(|e| {
+            InvokeFailure::Transient(
+                anyhow::Error::new(e).context("Failed to parse Anthropic API response"),
+            )
+        })?;
+        let metrics = message.usage.to_metrics();
+        self.record_metrics(&metrics);
+        Ok((message.text(), metrics))
+    }
+
+    /// Collect a streamed API response, echoing its text to stderr (a job's log) as it arrives
+    async fn read_api_stream(
+        &self,
+        mut response: reqwest::Response,
+    ) -> std::result::Result<(String, JobMetrics), InvokeFailure> {
+        let mut message = StreamedMessage::default();
+        let mut log = std::io::stderr();
+        let _ = writeln!(log, "[daily] Streaming response:");
+
+        while let Some(chunk) = response.chunk().await.map_err(|e| {
+            InvokeFailure::Transient(
+                anyhow::Error::new(e).context("Anthropic API stream interrupted"),
+            )
+        })? {
+            let added = message.push(&chunk).map_err(InvokeFailure::Transient)?;
+            if !added.is_empty() {
+                let _ = log.write_all(added.as_bytes());
+                let _ = log.flush();
+            }
+        }
+        let _ = writeln!(log);
+
+        if !message.stopped {
+            return Err(InvokeFailure::Transient(anyhow::anyhow!(
+                "Anthropic API stream ended before the response was complete"
+            )));
+        }
+        let metrics = message.usage.to_metrics();
+        self.record_metrics(&metrics);
+        Ok((message.text, metrics))
+    }
+
+    /// Build the `claude` CLI command for a prompt passed on stdin
+    fn cli_command(&self) -> Command {
+        let mut command = Command::new(&self.cli_program);
+        command
             .args([
                 "--model",
                 &self.config.summarization.model,
                 "--print", // Print response and exit
                 "-p",      // Prompt mode
+                // JSON output carries token usage alongside the response text
+                "--output-format",
+                "json",
                 // Disable hooks to prevent infinite loop (daily hooks -> claude -> daily hooks -> ...)
                 "--settings",
                 r#"{"hooks":{}}"#,
@@ -58,123 +475,356 @@ impl SummarizerEngine {
             ])
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
+            .stderr(Stdio::piped());
+        command
+    }
+
+    /// Explain a failure to start the CLI, pointing at the config when it can't be found
+    fn spawn_error(&self, error: std::io::Error) -> anyhow::Error {
+        if error.kind() != std::io::ErrorKind::NotFound {
+            return anyhow::Error::new(error).context("Failed to spawn claude CLI");
+        }
+
+        if self.config.summarization.claude_binary_path.is_some() {
+            return anyhow::anyhow!(
+                "claude not found at {}; check summarization.claude_binary_path",
+                self.cli_program.display()
+            );
+        }
+
+        match login_shell_claude_path() {
+            Some(path) => anyhow::anyhow!(
+                "claude is not on this PATH, but your login shell finds it at {}; set summarization.claude_binary_path to that path",
+                path
+            ),
+            None => {
+                anyhow::anyhow!("claude not found on PATH; set summarization.claude_binary_path")
+            }
+        }
+    }
+
+    /// Invoke Claude CLI with a prompt and return the response
+    fn invoke_cli(&self, prompt: &str) -> std::result::Result<(String, JobMetrics), InvokeFailure> {
+        let mut child = self
+            .cli_command()
             .spawn()
-            .context("Failed to spawn claude CLI. Is it installed?")?;
+            .map_err(|e| InvokeFailure::Fatal(self.spawn_error(e)))?;
 
-        // Write prompt to stdin
+        // Write prompt to stdin; a process that exits early is reported below
         if let Some(mut stdin) = child.stdin.take() {
-            stdin
-                .write_all(prompt.as_bytes())
-                .context("Failed to write prompt to claude")?;
+            let _ = stdin.write_all(prompt.as_bytes());
         }
 
         let output = child
             .wait_with_output()
-            .context("Failed to wait for claude")?;
+            .context("Failed to wait for claude")
+            .map_err(InvokeFailure::Transient)?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("Claude CLI failed: {}", stderr);
+            return Err(InvokeFailure::Transient(anyhow::anyhow!(
+                "Claude CLI failed: {}",
+                stderr
+            )));
         }
 
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
-    }
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        if stdout.trim().is_empty() {
+            return Err(InvokeFailure::Transient(anyhow::anyhow!(
+                "Claude CLI returned no output"
+            )));
+        }
 
-    /// Extract JSON from Claude's response (handles markdown code blocks)
-    fn extract_json(&self, response: &str) -> Result<String> {
-        // Try to find JSON in code block first
-        if let Some(start) = response.find("```json") {
-            if let Some(end) = response[start..]
-                .find("```\n")
-                .or(response[start..].rfind("```"))
-            {
-                let json_start = start + 7; // Skip ```json
-                let json_end = start + end;
-                if json_end > json_start {
-                    return Ok(response[json_start..json_end].trim().to_string());
-                }
-            }
+        let Ok(parsed) = serde_json::from_str::<ClaudeCliOutput>(&stdout) else {
+            // Older CLIs ignore --output-format and print plain text
+            let metrics = JobMetrics {
+                invocations: 1,
+                ..Default::default()
+            };
+            self.record_metrics(&metrics);
+            return Ok((stdout, metrics));
+        };
+
+        let metrics = parsed.usage.to_metrics();
+        self.record_metrics(&metrics);
+        if parsed.is_error {
+            return Err(InvokeFailure::Transient(anyhow::anyhow!(
+                "Claude CLI failed: {}",
+                parsed.result
+            )));
         }
+        Ok((parsed.result, metrics))
+    }
 
-        // Try to find raw JSON object
-        if let Some(start) = response.find('{') {
-            if let Some(end) = response.rfind('}') {
-                if end > start {
-                    return Ok(response[start..=end].to_string());
-                }
-            }
+    /// Add usage to the job this engine runs under, if any
+    fn record_metrics(&self, metrics: &JobMetrics) {
+        let Some(ref job_id) = self.job_id else {
+            return;
+        };
+        let result = JobManager::new(&self.config).and_then(|m| m.record_metrics(job_id, metrics));
+        if let Err(e) = result {
+            warn!("Failed to record job metrics: {}", e);
         }
+    }
 
-        // Return as-is if no JSON found
-        Ok(response.to_string())
+    /// Look up a configured prompt template, refusing one without its required placeholders
+    fn custom_template(&self, kind: &str) -> Result<Option<&str>> {
+        let Some(template) = self.config.prompt_templates.get(kind) else {
+            return Ok(None);
+        };
+        let validation = Prompts::validate_template(kind, template);
+        if !validation.is_valid() {
+            anyhow::bail!(
+                "prompt_templates.{} is missing required placeholder {}; fix it or run `daily config --validate-templates`",
+                kind,
+                validation
+                    .missing
+                    .iter()
+                    .map(|v| format!("{{{{{}}}}}", v))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        Ok(Some(template))
     }
 
-    /// Summarize a session transcript and create archive
-    pub async fn summarize_session(
+    /// Build the session summary prompt sent to Claude for a parsed transcript
+    pub fn session_summary_prompt(
         &self,
-        transcript_path: &std::path::Path,
-        _task_name: &str,
+        transcript_data: &TranscriptData,
         cwd: &str,
-    ) -> Result<SessionArchive> {
-        // Parse transcript
-        let transcript_data = TranscriptParser::parse(transcript_path)?;
-        let transcript_text = TranscriptParser::to_condensed_text(&transcript_data);
-
-        // Get git branch
+    ) -> Result<String> {
+        let transcript_text = self.condensed_transcript(transcript_data);
         let git_branch = crate::archive::session::get_git_branch(cwd);
+        self.session_prompt_for_text(&transcript_text, cwd, git_branch.as_deref())
+    }
 
-        // Build prompt and invoke Claude (using custom template if configured)
+    /// Condense a transcript to the configured token budget
+    fn condensed_transcript(&self, transcript_data: &TranscriptData) -> String {
+        let max_tokens = self.config.summarization.max_transcript_tokens;
+        if transcript_data.estimated_tokens() > max_tokens {
+            info!(
+                "Transcript is ~{} tokens, condensing to fit {}",
+                transcript_data.estimated_tokens(),
+                max_tokens
+            );
+        }
+        TranscriptParser::to_condensed_text(
+            transcript_data,
+            self.config.summarization.include_thinking,
+            Some(max_tokens),
+        )
+    }
+
+    /// Build the session summary prompt for already condensed transcript text
+    fn session_prompt_for_text(
+        &self,
+        transcript_text: &str,
+        cwd: &str,
+        git_branch: Option<&str>,
+    ) -> Result<String> {
+        // Use the custom template if configured
         let language = &self.config.summarization.summary_language;
-        let custom_template = self.config.prompt_templates.session_summary.as_deref();
-        let prompt = Prompts::session_summary_with_template(
+        let custom_template = self.custom_template("session_summary")?;
+        Ok(Prompts::session_summary_with_template(
             custom_template,
-            &transcript_text,
+            transcript_text,
             cwd,
-            git_branch.as_deref(),
+            git_branch,
             language,
+        ))
+    }
+
+    /// Summarize a session transcript and create archive.
+    ///
+    /// Returns `None` without calling Claude when a session with the same condensed
+    /// transcript is already archived on the target date.
+    pub async fn summarize_session(
+        &self,
+        transcript_path: &std::path::Path,
+        _task_name: &str,
+        cwd: &str,
+        overrides: &SessionOverrides,
+    ) -> Result<Option<SessionArchive>> {
+        let transcript_data = parse_transcript(&self.config, transcript_path)?;
+        let transcript_text = self.condensed_transcript(&transcript_data);
+        debug!(
+            "Parsed {} ({} entries, {} bytes condensed)",
+            transcript_path.display(),
+            transcript_data.entries.len(),
+            transcript_text.len()
         );
 
-        let response = self.invoke_claude(&prompt)?;
-        let json_str = self.extract_json(&response)?;
+        let today = overrides
+            .date
+            .clone()
+            .unwrap_or_else(|| self.config.today());
+        let hash = content_hash(&transcript_text);
+        let manager = ArchiveManager::new(self.config.clone());
+        if let Some(existing) = manager.session_exists_with_hash(&today, &hash)? {
+            info!("Skipping session: duplicate of {}", existing);
+            return Ok(None);
+        }
 
-        // Parse response
-        let summary_response: SessionSummaryResponse =
-            serde_json::from_str(&json_str).context("Failed to parse summary response")?;
+        // Looked up once so the prompt and the archive agree
+        let git_branch = crate::archive::session::get_git_branch(cwd);
+        let (summary_response, usage) = self
+            .generate_summary(&transcript_text, cwd, git_branch.as_deref())
+            .await?;
 
-        // Build title from time + AI-generated topic
-        // Format: HH_MM-topic (e.g., "14_55-fix-auth-bug")
+        // Build title from archive.title_format, e.g. "14_55-fix-auth-bug"
         let now = chrono::Local::now();
-        let time_prefix = now.format("%H_%M").to_string();
-        let topic = sanitize_topic(&summary_response.topic);
-        let title = format!("{}-{}", time_prefix, topic);
+        let topic = sanitize_topic(
+            overrides
+                .title
+                .as_deref()
+                .unwrap_or(&summary_response.topic),
+        );
+        let title = session_title(
+            &self.config.archive.title_format,
+            &now.format("%H_%M").to_string(),
+            &topic,
+            git_branch.as_deref(),
+            cwd,
+        );
 
         // Build archive
-        let today = now.format("%Y-%m-%d").to_string();
         let session_id = transcript_path
             .file_stem()
             .map(|s| s.to_string_lossy().to_string())
             .unwrap_or_else(|| "unknown".to_string());
 
+        let (changes, line_counts) = if self.config.archive.include_git_info {
+            (
+                crate::archive::session::get_git_diffstat(cwd, self.config.archive.max_diff_chars),
+                crate::archive::session::get_git_numstat(cwd).unwrap_or_default(),
+            )
+        } else {
+            (None, Vec::new())
+        };
+
         let archive = SessionArchive::new(title, today, session_id, cwd.to_string())
             .with_transcript_path(transcript_path.to_string_lossy().to_string())
             .with_transcript_data(&transcript_data)
+            .with_line_counts(&transcript_data, &line_counts)
+            .with_session_kind(
+                transcript_data.classify_session(&self.config.summarization.meaningful_tools),
+            )
             .with_summary(
                 summary_response.summary,
                 summary_response.decisions,
                 summary_response.learnings,
                 summary_response.skill_hints,
-            );
+            )
+            .with_confidence(normalize_confidence(summary_response.confidence.as_deref()))
+            .with_changes(changes)
+            .with_tags(&normalize_tags(&summary_response.tags))
+            .with_tags(&overrides.tags)
+            .with_content_hash(hash);
 
         // Set git branch
         let mut archive = archive;
         archive.git_branch = git_branch;
+        archive.duration = transcript_data.duration();
+        archive.usage = usage;
 
+        Ok(Some(archive))
+    }
+
+    /// Summarize an archived session again from its transcript with the current prompts.
+    ///
+    /// Title, date, session ID, git details, code changes and tags are kept; the
+    /// generated sections, kind, confidence, hash and usage are replaced.
+    pub async fn resummarize_session(
+        &self,
+        original: &SessionArchive,
+        transcript_path: &std::path::Path,
+    ) -> Result<SessionArchive> {
+        let transcript_data = parse_transcript(&self.config, transcript_path)?;
+        let transcript_text = self.condensed_transcript(&transcript_data);
+        let (summary_response, usage) = self
+            .generate_summary(
+                &transcript_text,
+                &original.cwd,
+                original.git_branch.as_deref(),
+            )
+            .await?;
+
+        let mut archive = original
+            .clone()
+            .with_session_kind(
+                transcript_data.classify_session(&self.config.summarization.meaningful_tools),
+            )
+            .with_summary(
+                summary_response.summary,
+                summary_response.decisions,
+                summary_response.learnings,
+                summary_response.skill_hints,
+            )
+            .with_confidence(normalize_confidence(summary_response.confidence.as_deref()))
+            .with_tags(&normalize_tags(&summary_response.tags))
+            .with_content_hash(content_hash(&transcript_text));
+        archive.duration = transcript_data.duration();
+        archive.usage = usage;
         Ok(archive)
     }
 
+    /// Ask Claude for a session summary of a condensed transcript
+    async fn generate_summary(
+        &self,
+        transcript_text: &str,
+        cwd: &str,
+        git_branch: Option<&str>,
+    ) -> Result<(SessionSummaryResponse, Option<SessionUsage>)> {
+        let prompt = self.session_prompt_for_text(transcript_text, cwd, git_branch)?;
+
+        let (response, metrics) = self.invoke_claude_with_usage(&prompt).await?;
+        let summary_response: SessionSummaryResponse =
+            parse_summary_response(&response).context("Failed to parse summary response")?;
+
+        // Plain-text output from older CLIs reports no usage
+        let usage = (metrics.total_tokens() > 0).then(|| SessionUsage {
+            input_tokens: metrics.input_tokens,
+            output_tokens: metrics.output_tokens,
+            cost_usd: self
+                .config
+                .summarization
+                .estimate_cost(metrics.input_tokens, metrics.output_tokens),
+        });
+        Ok((summary_response, usage))
+    }
+
     /// Update daily summary with all sessions
     pub async fn update_daily_summary(&self, date: &str) -> Result<DailySummary> {
+        // If no sessions and no existing summary, return empty
+        let Some(prompt) = self.daily_summary_prompt(date).await? else {
+            return Ok(DailySummary::new(date.to_string()));
+        };
+
+        let response = self.invoke_claude(&prompt).await?;
+        let daily_response: DailySummaryResponse =
+            parse_summary_response(&response).context("Failed to parse daily summary response")?;
+
+        // Build daily summary
+        let mut summary = DailySummary::new(date.to_string());
+        summary.sessions = ArchiveManager::new(self.config.clone()).list_sessions(date)?;
+        summary = summary.with_content(
+            daily_response.overview,
+            daily_response.session_details,
+            daily_response.insights,
+            daily_response.skills,
+            daily_response.commands,
+            daily_response.reflections,
+            daily_response.tomorrow_focus,
+        );
+
+        Ok(summary)
+    }
+
+    /// Build the daily summary prompt for a date, merging in any existing daily.md.
+    ///
+    /// Returns `None` when the date has neither sessions nor a daily summary.
+    pub async fn daily_summary_prompt(&self, date: &str) -> Result<Option<String>> {
         let manager = ArchiveManager::new(self.config.clone());
 
         // Get all sessions for this date
@@ -183,69 +833,166 @@ impl SummarizerEngine {
         // Read existing daily summary if it exists (for incremental digest or regeneration)
         let existing_summary = manager.read_daily_summary(date).ok();
 
-        // If no sessions and no existing summary, return empty
         if sessions.is_empty() && existing_summary.is_none() {
-            return Ok(DailySummary::new(date.to_string()));
+            return Ok(None);
         }
 
         // Collect session summaries, filtering out trivial sessions (1-2 turns)
         let mut session_data = Vec::new();
-        for session_name in &sessions {
-            if let Ok(content) = manager.read_session(date, session_name) {
-                // Extract summary from markdown (simplified extraction)
-                let summary = extract_summary_from_markdown(&content);
-                // Skip trivial sessions: very short summaries indicate 1-2 turn or empty sessions
-                if summary.len() < 80 {
-                    continue;
-                }
-                session_data.push(serde_json::json!({
-                    "content": summary
-                }));
+        for excerpt in self.read_session_summaries(date, &sessions).await? {
+            // Skip trivial sessions: very short summaries indicate 1-2 turn or empty sessions
+            if excerpt.summary.len() < 80 {
+                continue;
+            }
+            let mut entry = serde_json::json!({
+                "content": excerpt.summary
+            });
+            // The quality gate already ran per session; the digest builds on its results
+            if let Some(skill_hints) = excerpt.skill_hints {
+                entry["skill_hints"] = serde_json::Value::String(skill_hints);
             }
+            session_data.push(entry);
         }
 
         let sessions_json = serde_json::to_string_pretty(&session_data)?;
 
-        // Build prompt and invoke Claude (with existing summary if present, using custom template if configured)
+        // With no session files left, rewrite the existing summary instead of appending
+        let regenerate = sessions.is_empty();
+
+        // Build prompt (with existing summary if present, using custom template if configured)
         let language = &self.config.summarization.summary_language;
-        let custom_template = self.config.prompt_templates.daily_summary.as_deref();
-        let prompt = Prompts::daily_summary_with_template(
+        let custom_template = self.custom_template("daily_summary")?;
+        Ok(Some(Prompts::daily_summary_with_template(
             custom_template,
             &sessions_json,
             date,
             existing_summary.as_deref(),
+            regenerate,
             language,
-        );
-        let response = self.invoke_claude(&prompt)?;
-        let json_str = self.extract_json(&response)?;
+        )))
+    }
 
-        // Parse response
-        let daily_response: DailySummaryResponse =
-            serde_json::from_str(&json_str).context("Failed to parse daily summary response")?;
+    /// Read session files in parallel batches and extract their summaries and skill hints.
+    ///
+    /// Unreadable sessions are skipped; the result keeps the input order.
+    async fn read_session_summaries(
+        &self,
+        date: &str,
+        sessions: &[String],
+    ) -> Result<Vec<SessionExcerpt>> {
+        let manager = Arc::new(ArchiveManager::new(self.config.clone()));
+        let concurrency = self.config.summarization.digest_read_concurrency.max(1);
+        let mut summaries = Vec::with_capacity(sessions.len());
 
-        // Build daily summary
-        let mut summary = DailySummary::new(date.to_string());
-        summary.sessions = sessions;
-        summary = summary.with_content(
-            daily_response.overview,
-            daily_response.session_details,
-            daily_response.insights,
-            daily_response.skills,
-            daily_response.commands,
-            daily_response.reflections,
-            daily_response.tomorrow_focus,
-        );
+        for batch in sessions.chunks(concurrency) {
+            let handles: Vec<_> = batch
+                .iter()
+                .map(|session_name| {
+                    let manager = Arc::clone(&manager);
+                    let (date, session_name) = (date.to_string(), session_name.clone());
+                    // Falls back to the tarball once `daily archive` compressed the date
+                    tokio::task::spawn_blocking(move || {
+                        manager
+                            .read_session(&date, &session_name)
+                            .ok()
+                            .map(|content| SessionExcerpt {
+                                summary: extract_summary_from_markdown(&content),
+                                skill_hints: extract_skill_hints_from_markdown(&content),
+                            })
+                    })
+                })
+                .collect();
 
-        Ok(summary)
+            // Awaiting in spawn order keeps the output deterministic
+            for handle in handles {
+                if let Some(summary) = handle.await? {
+                    summaries.push(summary);
+                }
+            }
+        }
+
+        Ok(summaries)
+    }
+
+    /// Generate a weekly digest with sessions grouped by topic
+    pub async fn summarize_week(
+        &self,
+        week: &str,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<WeeklySummary> {
+        let manager = ArchiveManager::new(self.config.clone());
+
+        let mut sessions = Vec::new();
+        for date in manager.list_dates()? {
+            if date.as_str() < start_date || date.as_str() > end_date {
+                continue;
+            }
+
+            for session_name in manager.list_sessions(&date)? {
+                let Ok(content) = manager.read_session(&date, &session_name) else {
+                    continue;
+                };
+                let summary = extract_summary_from_markdown(&content);
+                // Skip trivial sessions, same as the daily digest
+                if summary.len() < 80 {
+                    continue;
+                }
+                sessions.push(WeeklySession {
+                    date: date.clone(),
+                    title: session_name,
+                    tags: Frontmatter::parse(&content).get_list("tags"),
+                    summary,
+                });
+            }
+        }
+
+        if sessions.is_empty() {
+            anyhow::bail!("No sessions found between {} and {}", start_date, end_date);
+        }
+
+        let session_count = sessions.len();
+        let clusters = cluster_sessions(sessions);
+        let theme_index = clusters
+            .iter()
+            .map(|c| format!("- **{}**: {} sessions", c.theme, c.sessions.len()))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let themes_json = serde_json::to_string_pretty(&clusters)?;
+        let language = &self.config.summarization.summary_language;
+        let prompt = Prompts::weekly_summary(&themes_json, start_date, end_date, language);
+
+        let response = self.invoke_claude(&prompt).await?;
+        let weekly_response: WeeklySummaryResponse =
+            parse_summary_response(&response).context("Failed to parse weekly summary response")?;
+
+        Ok(WeeklySummary {
+            week: week.to_string(),
+            start_date: start_date.to_string(),
+            end_date: end_date.to_string(),
+            session_count,
+            theme_index,
+            overview: weekly_response.overview,
+            themes: weekly_response.themes,
+            insights: weekly_response.insights,
+            next_week: weekly_response.next_week,
+        })
     }
 
     /// Extract skill from session
     pub async fn extract_skill(&self, session_content: &str, hint: Option<&str>) -> Result<String> {
         let language = &self.config.summarization.summary_language;
-        let custom_template = self.config.prompt_templates.skill_extract.as_deref();
-        let prompt =
-            Prompts::extract_skill_with_template(custom_template, session_content, hint, language);
-        let response = self.invoke_claude(&prompt)?;
+        let custom_template = self.custom_template("skill_extract")?;
+        let today = self.config.today();
+        let prompt = Prompts::extract_skill_with_template(
+            custom_template,
+            session_content,
+            hint,
+            language,
+            &today,
+        );
+        let response = self.invoke_claude(&prompt).await?;
 
         // Extract markdown from response
         extract_markdown_from_response(&response)
@@ -258,22 +1005,84 @@ impl SummarizerEngine {
         hint: Option<&str>,
     ) -> Result<String> {
         let language = &self.config.summarization.summary_language;
-        let custom_template = self.config.prompt_templates.command_extract.as_deref();
+        let custom_template = self.custom_template("command_extract")?;
         let prompt = Prompts::extract_command_with_template(
             custom_template,
             session_content,
             hint,
             language,
         );
-        let response = self.invoke_claude(&prompt)?;
+        let response = self.invoke_claude(&prompt).await?;
 
         // Extract markdown from response
         extract_markdown_from_response(&response)
     }
 }
 
+/// Render `archive.title_format` into a session title, which is also its file name.
+///
+/// Values are sanitized like topics; a missing branch renders empty and the separator
+/// it leaves is collapsed. Falls back to `time-topic` if the result is not file-safe.
+fn session_title(format: &str, time: &str, topic: &str, branch: Option<&str>, cwd: &str) -> String {
+    let branch = branch.map(sanitize_topic).unwrap_or_default();
+    let project = std::path::Path::new(cwd)
+        .file_name()
+        .map(|name| sanitize_topic(&name.to_string_lossy()))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let mut vars = std::collections::HashMap::new();
+    vars.insert("time", time);
+    vars.insert("topic", topic);
+    vars.insert("branch", branch.as_str());
+    vars.insert("project", project.as_str());
+    let mut title = TemplateEngine::render(format, &vars);
+    while title.contains("--") {
+        title = title.replace("--", "-");
+    }
+    let title = title.trim_matches('-').to_string();
+
+    let safe = !title.is_empty()
+        && !title.starts_with('.')
+        && !title.contains("..")
+        && title
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if safe {
+        title
+    } else {
+        format!("{}-{}", time, topic)
+    }
+}
+
+/// Where the user's login shell (`$SHELL -lc`) finds `claude`.
+///
+/// Hooks and launchd/systemd services often run with a minimal PATH, while the login
+/// shell's profile adds directories such as nvm's or volta's.
+#[cfg(unix)]
+fn login_shell_claude_path() -> Option<String> {
+    let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+    let output = Command::new(shell)
+        .args(["-lc", "command -v claude"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    // Profiles may print a banner first; the path is the last line
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .rfind(|line| line.starts_with('/'))
+        .map(str::to_string)
+}
+
+#[cfg(not(unix))]
+fn login_shell_claude_path() -> Option<String> {
+    None
+}
+
 /// Sanitize topic for use in filename
-fn sanitize_topic(topic: &str) -> String {
+pub fn sanitize_topic(topic: &str) -> String {
     // Convert to lowercase, replace spaces with hyphens, remove invalid chars
     let sanitized: String = topic
         .trim()
@@ -312,6 +1121,261 @@ fn sanitize_topic(topic: &str) -> String {
     }
 }
 
+/// Characters of a raw response quoted in a parse error
+const RESPONSE_PREVIEW_CHARS: usize = 500;
+
+/// Parse the JSON object in a Claude response.
+///
+/// Candidates are tried in order: a fenced ```json block, each balanced `{...}` span
+/// (string-aware, so braces inside values or stray braces in prose don't cut it
+/// short), then the whole response; each is parsed strictly, then repaired. The first
+/// that parses and satisfies `accept` wins.
+fn parse_response<T: DeserializeOwned>(response: &str, accept: impl Fn(&T) -> bool) -> Result<T> {
+    let mut first_error = None;
+    for candidate in json_candidates(response) {
+        match parse_json(candidate) {
+            Ok(value) if accept(&value) => return Ok(value),
+            Ok(_) => {
+                first_error
+                    .get_or_insert_with(|| "JSON has none of the expected fields".to_string());
+            }
+            Err(e) => {
+                first_error.get_or_insert_with(|| e.to_string());
+            }
+        }
+    }
+
+    let reason = first_error.unwrap_or_else(|| "no JSON object found".to_string());
+    let mut preview: String = response.chars().take(RESPONSE_PREVIEW_CHARS).collect();
+    if preview.len() < response.len() {
+        preview.push_str("...");
+    }
+    anyhow::bail!("{}. Claude returned:\n{}", reason, preview)
+}
+
+/// Parse a summary response, filling in fields Claude left out instead of failing.
+///
+/// Only objects with at least one expected field count, so a stray `{}` in prose isn't
+/// taken for the answer. Missing, null or blank fields get the schema's placeholder and
+/// are logged; a list of strings where text was expected becomes a bullet list.
+fn parse_summary_response<T: ResponseSchema>(response: &str) -> Result<T> {
+    let mut value = parse_response(response, |value: &serde_json::Value| {
+        T::FIELDS
+            .iter()
+            .any(|(field, _)| value.get(field).is_some())
+    })?;
+
+    let missing = fill_missing_fields(&mut value, T::FIELDS);
+    if !missing.is_empty() {
+        warn!(
+            "{} response is missing {}; using placeholders",
+            T::NAME,
+            missing.join(", ")
+        );
+    }
+    serde_json::from_value(value).context(format!("{} response has invalid fields", T::NAME))
+}
+
+/// Normalize the expected text fields of a response object, returning those that were missing
+fn fill_missing_fields(
+    value: &mut serde_json::Value,
+    fields: &[(&'static str, &'static str)],
+) -> Vec<&'static str> {
+    use serde_json::Value;
+
+    let Some(object) = value.as_object_mut() else {
+        return Vec::new();
+    };
+    let mut missing = Vec::new();
+    for (field, placeholder) in fields {
+        let text = match object.get(*field) {
+            None | Some(Value::Null) => None,
+            Some(Value::String(text)) => Some(text.clone()).filter(|t| !t.trim().is_empty()),
+            Some(Value::Array(items)) => {
+                let lines: Vec<String> = items
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(|item| format!("- {}", item))
+                    .collect();
+                (!lines.is_empty()).then(|| lines.join("\n"))
+            }
+            // Left for deserialization to reject
+            Some(_) => continue,
+        };
+        let text = text.unwrap_or_else(|| {
+            missing.push(*field);
+            placeholder.to_string()
+        });
+        object.insert(field.to_string(), Value::String(text));
+    }
+    missing
+}
+
+/// Substrings of a response that may hold the JSON object, most likely first
+fn json_candidates(response: &str) -> Vec<&str> {
+    let mut candidates = Vec::new();
+
+    if let Some(start) = response.find("```json") {
+        let body = &response[start + 7..];
+        if let Some(end) = body.find("```") {
+            candidates.push(body[..end].trim());
+        }
+    }
+
+    // Every start is tried: a stray `{` in prose may swallow the real object
+    for (start, c) in response.char_indices() {
+        if c != '{' {
+            continue;
+        }
+        if let Some(len) = balanced_object_len(&response[start..]) {
+            candidates.push(&response[start..start + len]);
+        }
+    }
+
+    candidates.push(response.trim());
+    candidates
+}
+
+/// Byte length of the `{...}` object `text` starts with, skipping braces in strings
+fn balanced_object_len(text: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in text.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' => depth += 1,
+            '}' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parse JSON from Claude, repairing common formatting mistakes if strict parsing fails
+fn parse_json<T: DeserializeOwned>(json_str: &str) -> serde_json::Result<T> {
+    match serde_json::from_str(json_str) {
+        Ok(value) => Ok(value),
+        Err(strict_err) => {
+            let repaired = repair_json(json_str);
+            serde_json::from_str(&repaired).map_err(|_| strict_err)
+        }
+    }
+}
+
+/// Fix common JSON mistakes: smart-quote delimiters, trailing commas,
+/// and raw control characters (e.g. newlines) inside string values
+fn repair_json(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars();
+    // Some(true) when inside a string opened by a smart quote
+    let mut in_string: Option<bool> = None;
+
+    while let Some(c) = chars.next() {
+        match in_string {
+            None => match c {
+                '"' => {
+                    in_string = Some(false);
+                    output.push('"');
+                }
+                '\u{201C}' | '\u{201D}' => {
+                    in_string = Some(true);
+                    output.push('"');
+                }
+                ',' => {
+                    // Drop trailing commas before a closing bracket
+                    let next = chars.clone().find(|c| !c.is_whitespace());
+                    if !matches!(next, Some('}') | Some(']')) {
+                        output.push(',');
+                    }
+                }
+                _ => output.push(c),
+            },
+            Some(smart) => match c {
+                '\\' => {
+                    output.push(c);
+                    if let Some(next) = chars.next() {
+                        output.push(next);
+                    }
+                }
+                '"' if !smart => {
+                    in_string = None;
+                    output.push('"');
+                }
+                '"' => output.push_str("\\\""),
+                '\u{201C}' | '\u{201D}' if smart => {
+                    in_string = None;
+                    output.push('"');
+                }
+                '\n' => output.push_str("\\n"),
+                '\r' => output.push_str("\\r"),
+                '\t' => output.push_str("\\t"),
+                c if c.is_control() => output.push_str(&format!("\\u{:04x}", c as u32)),
+                _ => output.push(c),
+            },
+        }
+    }
+
+    output
+}
+
+/// Normalize a self-assessed confidence to low/medium/high
+fn normalize_confidence(confidence: Option<&str>) -> Option<String> {
+    let confidence = confidence?.trim().to_lowercase();
+    ["low", "medium", "high"]
+        .into_iter()
+        .find(|level| confidence.starts_with(level))
+        .map(|level| level.to_string())
+}
+
+/// Clean up generated tags: lowercase kebab-case, no list syntax, at most 4
+fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag
+            .trim()
+            .trim_start_matches('#')
+            .to_lowercase()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join("-")
+            .replace([',', '[', ']', '"', '\''], "");
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized.truncate(4);
+    normalized
+}
+
+/// The parts of a session file the daily digest is built from
+#[derive(Debug, PartialEq)]
+struct SessionExcerpt {
+    summary: String,
+    skill_hints: Option<String>,
+}
+
+/// Extract the skill hints section from session markdown, unless none were identified
+fn extract_skill_hints_from_markdown(content: &str) -> Option<String> {
+    let hints = markdown_section(content, "Potential Skills/Commands");
+    (!hints.is_empty() && !hints.starts_with("None identified")).then_some(hints)
+}
+
 /// Extract summary section from session markdown
 fn extract_summary_from_markdown(content: &str) -> String {
     // Look for ## Summary section
@@ -373,6 +1437,258 @@ More content.
         assert!(summary.contains("This is the summary"));
     }
 
+    #[tokio::test]
+    async fn test_read_session_summaries_keeps_order() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+        config.summarization.digest_read_concurrency = 2;
+
+        let manager = ArchiveManager::new(config.clone());
+        let sessions: Vec<String> = (0..5).map(|i| format!("1{}_00-task", i)).collect();
+        for (i, name) in sessions.iter().enumerate() {
+            let content = format!("## Summary\n\nSession {}\n", i);
+            manager
+                .write_session("2026-01-16", "proj", name, &content)
+                .unwrap();
+        }
+        let mut requested = sessions.clone();
+        requested.insert(2, "missing".to_string());
+
+        let engine = SummarizerEngine::new(config);
+        let summaries = engine
+            .read_session_summaries("2026-01-16", &requested)
+            .await
+            .unwrap();
+
+        let expected: Vec<String> = (0..5).map(|i| format!("Session {}", i)).collect();
+        let summaries: Vec<String> = summaries.into_iter().map(|e| e.summary).collect();
+        assert_eq!(summaries, expected);
+    }
+
+    #[tokio::test]
+    async fn test_daily_summary_prompt_reads_compressed_date() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+
+        let manager = ArchiveManager::new(config.clone());
+        let summary = "Moved the OAuth token refresh into a background task so requests no longer block on it.";
+        manager
+            .write_session(
+                "2026-01-16",
+                "proj",
+                "10_00-auth",
+                &format!("\u{feff}## Summary\n\n{}\n", summary),
+            )
+            .unwrap();
+        manager.compress_date("2026-01-16").unwrap();
+
+        let engine = SummarizerEngine::new(config);
+        let prompt = engine
+            .daily_summary_prompt("2026-01-16")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(prompt.contains(summary));
+    }
+
+    #[test]
+    fn test_extract_skill_hints_from_markdown() {
+        let content = "## Summary\n\nFixed it.\n\n## Potential Skills/Commands\n\n- `/flaky-test`: rerun and bisect\n\n---\n*Archived by daily*\n";
+        assert_eq!(
+            extract_skill_hints_from_markdown(content).as_deref(),
+            Some("- `/flaky-test`: rerun and bisect")
+        );
+        let none = "## Summary\n\nFixed it.\n\n## Potential Skills/Commands\n\nNone identified in this session.\n";
+        assert_eq!(extract_skill_hints_from_markdown(none), None);
+        assert_eq!(
+            extract_skill_hints_from_markdown("## Summary\n\nFixed it.\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_repair_json_trailing_commas() {
+        let value: serde_json::Value = parse_json(r#"{"a": [1, 2,], "b": "x",}"#).unwrap();
+        assert_eq!(value["a"][1], 2);
+        assert_eq!(value["b"], "x");
+    }
+
+    #[test]
+    fn test_repair_json_smart_quotes() {
+        let value: serde_json::Value =
+            parse_json("{\u{201C}topic\u{201D}: \u{201C}say \"hi\"\u{201D}}").unwrap();
+        assert_eq!(value["topic"], "say \"hi\"");
+    }
+
+    #[test]
+    fn test_repair_json_raw_newlines() {
+        let value: serde_json::Value =
+            parse_json("{\"summary\": \"line one\nline two\ttabbed\"}").unwrap();
+        assert_eq!(value["summary"], "line one\nline two\ttabbed");
+    }
+
+    #[test]
+    fn test_repair_json_preserves_valid_content() {
+        let input = r#"{"text": "a, ] \"quoted\" \u201C ok", "n": [1]}"#;
+        assert_eq!(repair_json(input), input);
+        let value: serde_json::Value = parse_json(input).unwrap();
+        assert_eq!(value["text"], "a, ] \"quoted\" \u{201C} ok");
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Topic {
+        topic: String,
+    }
+
+    impl ResponseSchema for Topic {
+        const NAME: &'static str = "Topic";
+        const FIELDS: &'static [(&'static str, &'static str)] = &[("topic", "session")];
+    }
+
+    #[test]
+    fn test_parse_response_braces_in_strings() {
+        let response =
+            r#"Here it is: {"topic": "fix {nested} \"}\" braces", "extra": {"a": 1}} Done."#;
+        let parsed: Topic = parse_summary_response(response).unwrap();
+        assert_eq!(parsed.topic, r#"fix {nested} "}" braces"#);
+    }
+
+    #[test]
+    fn test_parse_response_stray_braces_in_prose() {
+        let response = "A stray } and { here.\n{\"topic\": \"auth\"}\nAnd one more }.";
+        let parsed: Topic = parse_summary_response(response).unwrap();
+        assert_eq!(parsed.topic, "auth");
+
+        // The first object isn't the one asked for
+        let response = r#"{"note": "draft"} then {"topic": "final",}"#;
+        let parsed: Topic = parse_summary_response(response).unwrap();
+        assert_eq!(parsed.topic, "final");
+    }
+
+    #[test]
+    fn test_parse_response_fenced_block() {
+        let response = "Sure:\n```json\n{\"topic\": \"fenced\"}\n```\n{\"topic\": \"later\"}";
+        let parsed: Topic = parse_summary_response(response).unwrap();
+        assert_eq!(parsed.topic, "fenced");
+    }
+
+    #[test]
+    fn test_parse_summary_response_fills_missing_fields() {
+        let response = r#"Summary: {"topic": "Fix auth", "summary": "Fixed login.", "decisions": ["Use JWT", "Drop cookies"], "learnings": null}"#;
+        let parsed: SessionSummaryResponse = parse_summary_response(response).unwrap();
+        assert_eq!(parsed.topic, "Fix auth");
+        assert_eq!(parsed.summary, "Fixed login.");
+        assert_eq!(parsed.decisions, "- Use JWT\n- Drop cookies");
+        assert_eq!(parsed.learnings, NOT_PROVIDED);
+        assert_eq!(parsed.skill_hints, "None identified");
+        assert!(parsed.tags.is_empty());
+
+        // An object with none of the expected fields isn't the answer
+        let response = r#"Config was {} then {"overview": "Busy day"}"#;
+        let parsed: DailySummaryResponse = parse_summary_response(response).unwrap();
+        assert_eq!(parsed.overview, "Busy day");
+        assert_eq!(parsed.tomorrow_focus, NOT_PROVIDED);
+        assert!(parse_summary_response::<WeeklySummaryResponse>(r#"{"unrelated": 1}"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_response_error_includes_response() {
+        let error = parse_summary_response::<Topic>("I couldn't summarize this session.")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("I couldn't summarize this session."),
+            "{}",
+            error
+        );
+
+        let long = "x".repeat(2000);
+        let error = parse_summary_response::<Topic>(&long)
+            .unwrap_err()
+            .to_string();
+        assert!(error.ends_with("..."));
+        assert!(error.len() < 700);
+    }
+
+    #[test]
+    fn test_parse_json_unrepairable_errors() {
+        assert!(parse_json::<serde_json::Value>("{not json").is_err());
+    }
+
+    #[test]
+    fn test_normalize_tags() {
+        let tags = [
+            "Rust",
+            "#auth",
+            "error handling",
+            "rust",
+            "[ci]",
+            "",
+            "docs",
+            "extra",
+        ]
+        .map(String::from);
+        assert_eq!(
+            normalize_tags(&tags),
+            vec!["rust", "auth", "error-handling", "ci"]
+        );
+    }
+
+    #[test]
+    fn test_session_title_formats() {
+        let default = "{{time}}-{{topic}}";
+        assert_eq!(
+            session_title(default, "14_55", "fix-auth", Some("main"), "/w/app"),
+            "14_55-fix-auth"
+        );
+
+        let branched = "{{branch}}-{{time}}-{{topic}}";
+        assert_eq!(
+            session_title(
+                branched,
+                "14_55",
+                "fix-auth",
+                Some("feature/Login"),
+                "/w/app"
+            ),
+            "feature-login-14_55-fix-auth"
+        );
+        // Outside a git repo the branch renders empty without leaving a stray separator
+        assert_eq!(
+            session_title(branched, "14_55", "fix-auth", None, "/w/app"),
+            "14_55-fix-auth"
+        );
+
+        assert_eq!(
+            session_title(
+                "{{project}}_{{topic}}",
+                "14_55",
+                "fix-auth",
+                None,
+                "/w/My App"
+            ),
+            "my-app_fix-auth"
+        );
+        // Unsafe literal text falls back to the default format
+        assert_eq!(
+            session_title("{{time}}/{{topic}}", "14_55", "fix-auth", None, "/w/app"),
+            "14_55-fix-auth"
+        );
+    }
+
+    #[test]
+    fn test_normalize_confidence() {
+        assert_eq!(normalize_confidence(Some("High")), Some("high".to_string()));
+        assert_eq!(
+            normalize_confidence(Some("low - transcript too short")),
+            Some("low".to_string())
+        );
+        assert_eq!(normalize_confidence(Some("unsure")), None);
+        assert_eq!(normalize_confidence(None), None);
+    }
+
     #[test]
     fn test_extract_markdown_from_response() {
         let response = r#"Here is the skill:
@@ -388,4 +1704,173 @@ Done!"#;
         let md = extract_markdown_from_response(response).unwrap();
         assert!(md.contains("name: test-skill"));
     }
+
+    #[test]
+    fn test_parse_cli_output_usage() {
+        let stdout = r#"{"type":"result","is_error":false,"result":"{\"a\":1}","usage":{"input_tokens":10,"cache_read_input_tokens":90,"output_tokens":25}}"#;
+        let parsed: ClaudeCliOutput = serde_json::from_str(stdout).unwrap();
+        assert_eq!(parsed.result, "{\"a\":1}");
+
+        let metrics = parsed.usage.to_metrics();
+        assert_eq!(metrics.invocations, 1);
+        assert_eq!(metrics.input_tokens, 100);
+        assert_eq!(metrics.total_tokens(), 125);
+    }
+
+    #[test]
+    fn test_api_backend_selection() {
+        let mut config = Config::default();
+        assert_eq!(ApiBackend::from_config(&config), ApiBackend::Cli);
+
+        config.summarization.backend = "api".to_string();
+        config.summarization.api_key = Some("sk-test".to_string());
+        config.summarization.api_base_url = "https://example.com/".to_string();
+        if std::env::var("ANTHROPIC_API_KEY").is_err() {
+            assert_eq!(
+                ApiBackend::from_config(&config),
+                ApiBackend::Api {
+                    api_key: "sk-test".to_string(),
+                    base_url: "https://example.com".to_string(),
+                }
+            );
+        }
+
+        assert_eq!(api_model_id("sonnet"), "claude-sonnet-4-5");
+        assert_eq!(
+            api_model_id("claude-3-5-haiku-latest"),
+            "claude-3-5-haiku-latest"
+        );
+    }
+
+    #[test]
+    fn test_parse_api_response() {
+        let body = r#"{"content":[{"type":"text","text":"{\"a\":"},{"type":"text","text":"1}"}],"usage":{"input_tokens":12,"output_tokens":3}}"#;
+        let message: ApiMessageResponse = serde_json::from_str(body).unwrap();
+        assert_eq!(message.text(), "{\"a\":1}");
+        assert_eq!(message.usage.to_metrics().total_tokens(), 15);
+    }
+
+    #[test]
+    fn test_streamed_message() {
+        let stream = concat!(
+            "event: message_start\n",
+            "data: {\"type\":\"message_start\",\"message\":{\"usage\":{\"input_tokens\":12,\"output_tokens\":1}}}\n\n",
+            "event: content_block_delta\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"{\\\"a\\\":\"}}\n\n",
+            "event: content_block_delta\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"1}\"}}\n\n",
+            "event: message_delta\n",
+            "data: {\"type\":\"message_delta\",\"usage\":{\"output_tokens\":3}}\n\n",
+            "event: message_stop\n",
+            "data: {\"type\":\"message_stop\"}\n\n",
+        );
+
+        // Chunks may split lines anywhere
+        let mut message = StreamedMessage::default();
+        let mut streamed = String::new();
+        for chunk in stream.as_bytes().chunks(7) {
+            streamed.push_str(&message.push(chunk).unwrap());
+        }
+        assert_eq!(streamed, "{\"a\":1}");
+        assert_eq!(message.text, "{\"a\":1}");
+        assert!(message.stopped);
+        assert_eq!(message.usage.to_metrics().total_tokens(), 15);
+
+        let mut failed = StreamedMessage::default();
+        let error = "data: {\"type\":\"error\",\"error\":{\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}}\n";
+        assert!(failed.push(error.as_bytes()).is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_invoke_claude_retries_transient_failures() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let count_file = temp_dir.path().join("count");
+        let script = temp_dir.path().join("fake-claude");
+        std::fs::write(
+            &script,
+            format!("#!/bin/sh\necho x >> {}\nexit 1\n", count_file.display()),
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut config = Config::default();
+        config.summarization.max_retries = 2;
+        let mut engine = SummarizerEngine::new(config);
+        engine.cli_program = script.clone();
+        engine.retry_delay = Duration::from_millis(1);
+
+        let error = engine.invoke_claude("prompt").await.unwrap_err();
+        assert!(error.to_string().contains("after 3 attempts"));
+        let calls = std::fs::read_to_string(&count_file).unwrap();
+        assert_eq!(calls.lines().count(), 3);
+
+        // A missing CLI is not retried
+        engine.config.summarization.claude_binary_path = Some(temp_dir.path().join("missing"));
+        engine.cli_program = temp_dir.path().join("missing");
+        let error = engine.invoke_claude("prompt").await.unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("check summarization.claude_binary_path"));
+    }
+
+    #[tokio::test]
+    async fn test_daily_summary_prompt_without_claude() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().to_path_buf();
+        let engine = SummarizerEngine::new(config.clone());
+
+        assert!(engine
+            .daily_summary_prompt("2026-01-16")
+            .await
+            .unwrap()
+            .is_none());
+
+        ArchiveManager::new(config)
+            .write_daily_summary("2026-01-16", "## Overview\n\nShipped the parser rewrite.\n")
+            .unwrap();
+        let prompt = engine
+            .daily_summary_prompt("2026-01-16")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(prompt.contains("2026-01-16"));
+        assert!(prompt.contains("Shipped the parser rewrite."));
+    }
+
+    #[test]
+    fn test_custom_template_requires_placeholders() {
+        let mut config = Config::default();
+        config.prompt_templates.daily_summary = Some("Digest {{sessions_json}}".to_string());
+        config.prompt_templates.session_summary = Some("Summarize {{cwd}}".to_string());
+        let engine = SummarizerEngine::new(config);
+
+        assert_eq!(
+            engine.custom_template("daily_summary").unwrap(),
+            Some("Digest {{sessions_json}}")
+        );
+        assert!(engine.custom_template("skill_extract").unwrap().is_none());
+        let err = engine
+            .custom_template("session_summary")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("prompt_templates.session_summary"));
+        assert!(err.contains("{{transcript or transcript_text}}"));
+    }
+
+    #[test]
+    fn test_cli_command_uses_configured_binary() {
+        let engine = SummarizerEngine::new(Config::default());
+        assert_eq!(engine.cli_command().get_program(), "claude");
+
+        let mut config = Config::default();
+        config.summarization.claude_binary_path = Some(PathBuf::from("/opt/volta/bin/claude"));
+        let engine = SummarizerEngine::new(config);
+        let command = engine.cli_command();
+        assert_eq!(command.get_program(), "/opt/volta/bin/claude");
+        assert!(command.get_args().any(|arg| arg == "--print"));
+    }
 }
@@ -1,6 +1,8 @@
+mod cluster;
 mod engine;
 mod prompts;
 mod template;
 
-pub use engine::SummarizerEngine;
-pub use prompts::Prompts;
+pub use engine::{sanitize_topic, SummarizerEngine};
+pub use prompts::{language_name, Prompts, SUPPORTED_LANGUAGES, TEMPLATE_KINDS};
+pub use template::TemplateEngine;
@@ -23,7 +23,9 @@ Generate a JSON response with this exact structure:
   "summary": "2-3 sentence overview including CONCRETE RESULTS (answers found, solutions implemented, code written). Never just describe the action - always include what was produced or discovered.",
   "decisions": "Key decisions made and their rationale (markdown list format)",
   "learnings": "Key learnings from this session (markdown list format)",
-  "skill_hints": "Potential reusable skills (only if passes quality gate, see below)"
+  "skill_hints": "Potential reusable skills (only if passes quality gate, see below)",
+  "confidence": "low | medium | high - how complete this summary is (low if the transcript had too little signal to summarize reliably)",
+  "tags": ["2-4 short lowercase topical tags, e.g. 'rust', 'auth', 'ci'"]
 }
 ```
 
@@ -60,7 +62,9 @@ const SESSION_SUMMARY_ZH: &str = r#"你正在分析一个 Claude Code 会话记
   "summary": "2-3句话概述，包含具体成果（找到的答案、实现的解决方案、编写的代码）。不要只描述动作，总是包含产出或发现。",
   "decisions": "关键决策及其理由（markdown 列表格式）",
   "learnings": "本次会话的关键收获（markdown 列表格式）",
-  "skill_hints": "可复用的技能提示（仅当通过质量门禁时）"
+  "skill_hints": "可复用的技能提示（仅当通过质量门禁时）",
+  "confidence": "low | medium | high - 摘要的完整程度（如果会话记录信息太少无法可靠总结，则为 low）",
+  "tags": ["2-4 个简短的小写英文主题标签，例如：'rust'、'auth'、'ci'"]
 }
 ```
 
@@ -367,7 +371,7 @@ Generate a narrative digest that answers: "What did I accomplish today? What did
    - Problems discovered but not yet solved
    - Natural next steps
 
-6. **Skills & Commands**: Reusable patterns that could become skills or commands (if any, otherwise say "None identified"). Only include high-quality suggestions that pass the quality gate (was there a pitfall? will it recur? can you explain it clearly?).
+6. **Skills & Commands**: Reusable patterns that could become skills or commands (if any, otherwise say "None identified"). Only include high-quality suggestions that pass the quality gate (was there a pitfall? will it recur? can you explain it clearly?). Sessions with a `skill_hints` field already passed that gate: build on those hints, merging duplicates across sessions, rather than re-deriving suggestions from the summaries.
 
 Output format (JSON):
 ```json
@@ -425,7 +429,7 @@ const DAILY_SUMMARY_ZH: &str = r#"你正在分析 {{date}} 的 Claude Code 会
    - 发现但尚未解决的问题
    - 自然的下一步
 
-6. **技能与命令**：可复用的模式，可以沉淀为技能或命令（如果有的话，否则说「暂未发现」）。只包含通过质量门禁的高质量建议（踩过坑吗？会复现吗？能说清楚吗？）。
+6. **技能与命令**：可复用的模式，可以沉淀为技能或命令（如果有的话，否则说「暂未发现」）。只包含通过质量门禁的高质量建议（踩过坑吗？会复现吗？能说清楚吗？）。带有 `skill_hints` 字段的会话已通过该门禁：以这些提示为基础，合并跨会话的重复项，而不是从摘要中重新推导。
 
 输出格式（JSON）：
 ```json
@@ -442,7 +446,253 @@ const DAILY_SUMMARY_ZH: &str = r#"你正在分析 {{date}} 的 Claude Code 会
 
 仅输出 JSON 块。确保 JSON 中的所有字符串都正确转义（特别是引号和换行符）。"#;
 
+// Weekly digest templates (sessions pre-grouped by topic)
+const WEEKLY_SUMMARY_EN: &str = r#"You are reviewing a week of Claude Code sessions ({{start_date}} to {{end_date}}). Generate a weekly review.
+
+The sessions have already been grouped by topic:
+{{themes_json}}
+
+## Your Task
+
+Write a thematic review that answers: "What themes did I work on this week? What did I learn? What should carry over?"
+
+### Rules
+
+- Organize the review by the given themes, largest first (e.g. "Auth work: 3 sessions")
+- Merge themes only if they are clearly the same area of work
+- **DO NOT** include session names or timestamps like "17_48-fix-xxx"
+- Focus on outcomes, decisions and discoveries, not a chronological log
+
+Output format (JSON):
+```json
+{
+  "overview": "3-5 sentence narrative of the week",
+  "themes": "markdown: one ### heading per theme with session count, followed by what was accomplished",
+  "insights": "markdown list of the week's key insights",
+  "next_week": "markdown list of carry-over items and priorities"
+}
+```
+
+Output ONLY the JSON block. Ensure all strings in JSON are properly escaped (especially quotes and newlines)."#;
+
+const WEEKLY_SUMMARY_ZH: &str = r#"你正在回顾一周的 Claude Code 会话（{{start_date}} 至 {{end_date}}）。生成周报。
+
+会话已按主题分组：
+{{themes_json}}
+
+## 你的任务
+
+撰写一份按主题组织的回顾，回答："这周在哪些主题上工作？学到了什么？哪些需要延续到下周？"
+
+### 规则
+
+- 按给定主题组织周报，会话数多的主题在前（如「认证相关：3 个会话」）
+- 只有明显属于同一工作领域时才合并主题
+- **禁止** 包含会话名称或类似 "17_48-fix-xxx" 的时间戳
+- 聚焦于成果、决策和发现，而不是按时间顺序的流水账
+
+输出格式（JSON）：
+```json
+{
+  "overview": "3-5 句话的本周叙事概述",
+  "themes": "markdown：每个主题一个 ### 标题并注明会话数，随后描述完成的工作",
+  "insights": "markdown 格式的本周关键洞察列表",
+  "next_week": "markdown 格式的延续事项和优先级列表"
+}
+```
+
+仅输出 JSON 块。确保 JSON 中的所有字符串都正确转义（特别是引号和换行符）。"#;
+
+/// Supported `summary_language` codes
+pub const SUPPORTED_LANGUAGES: [&str; 5] = ["en", "zh", "ja", "es", "fr"];
+
+/// English name of a supported summary language, used in the language directive
+pub fn language_name(language: &str) -> Option<&'static str> {
+    match language {
+        "en" => Some("English"),
+        "zh" => Some("Chinese"),
+        "ja" => Some("Japanese"),
+        "es" => Some("Spanish"),
+        "fr" => Some("French"),
+        _ => None,
+    }
+}
+
+/// Instruction appended to English templates so Claude answers in another language.
+///
+/// English and Chinese have dedicated templates and need no directive.
+fn language_directive(language: &str) -> Option<String> {
+    let instruction = match language {
+        "ja" => "すべての内容を日本語で書いてください。",
+        "es" => "Escribe todo el contenido en español.",
+        "fr" => "Rédige tout le contenu en français.",
+        _ => return None,
+    };
+    let name = language_name(language)?;
+    Some(format!(
+        "\n\n## Output Language\n\nRespond in {}. {}\nKeep JSON keys, the kebab-case topic, markdown syntax and the NOT_EXTRACTABLE marker in English.",
+        name, instruction
+    ))
+}
+
+/// Render a template and add the language directive for languages without their own template
+fn render_localized(template: &str, vars: &HashMap<&str, &str>, language: &str) -> String {
+    let mut prompt = TemplateEngine::render(template, vars);
+    if let Some(directive) = language_directive(language) {
+        prompt.push_str(&directive);
+    }
+    prompt
+}
+
+/// Custom template kinds, matching `prompt_templates` config keys
+pub const TEMPLATE_KINDS: [&str; 4] = [
+    "session_summary",
+    "daily_summary",
+    "skill_extract",
+    "command_extract",
+];
+
+/// Variables and output fields a template kind works with
+struct TemplateSpec {
+    /// Each group must have at least one placeholder present
+    required: &'static [&'static [&'static str]],
+    /// All placeholders substituted for this kind
+    known: &'static [&'static str],
+    /// JSON fields the response is parsed into
+    output_fields: &'static [&'static str],
+}
+
+fn template_spec(kind: &str) -> Option<TemplateSpec> {
+    match kind {
+        "session_summary" => Some(TemplateSpec {
+            required: &[&["transcript", "transcript_text"]],
+            known: &[
+                "transcript",
+                "transcript_text",
+                "cwd",
+                "git_branch",
+                "git_info",
+                "language",
+            ],
+            output_fields: &["topic", "summary", "decisions", "learnings", "skill_hints"],
+        }),
+        "daily_summary" => Some(TemplateSpec {
+            required: &[&["sessions_section", "sessions_json"]],
+            known: &[
+                "date",
+                "current_time",
+                "current_period",
+                "periods_desc",
+                "existing_section",
+                "existing_summary",
+                "sessions_section",
+                "sessions_json",
+                "language",
+            ],
+            output_fields: &[
+                "overview",
+                "session_details",
+                "insights",
+                "skills",
+                "commands",
+                "reflections",
+                "tomorrow_focus",
+            ],
+        }),
+        "skill_extract" => Some(TemplateSpec {
+            required: &[&["session_content"]],
+            known: &["session_content", "skill_hint", "today", "language"],
+            output_fields: &[],
+        }),
+        "command_extract" => Some(TemplateSpec {
+            required: &[&["session_content"]],
+            known: &["session_content", "command_hint", "language"],
+            output_fields: &[],
+        }),
+        _ => None,
+    }
+}
+
+/// Result of validating a custom prompt template
+#[derive(Debug, Default)]
+pub struct TemplateValidation {
+    /// Required placeholders that are missing (template is unusable)
+    pub missing: Vec<String>,
+    /// Placeholders that are never substituted for this template kind
+    pub unknown: Vec<String>,
+    /// Expected JSON response fields the template never mentions
+    pub missing_fields: Vec<String>,
+}
+
+impl TemplateValidation {
+    /// Whether the template can be used safely
+    pub fn is_valid(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Human-readable list of problems, errors first
+    pub fn messages(&self) -> Vec<String> {
+        let mut messages: Vec<String> = self
+            .missing
+            .iter()
+            .map(|v| format!("missing required placeholder {{{{{}}}}}", v))
+            .collect();
+        messages.extend(
+            self.unknown
+                .iter()
+                .map(|v| format!("unknown placeholder {{{{{}}}}} will not be replaced", v)),
+        );
+        messages.extend(
+            self.missing_fields
+                .iter()
+                .map(|f| format!("output field \"{}\" is never mentioned", f)),
+        );
+        messages
+    }
+}
+
 impl Prompts {
+    // ============================================
+    // Template Validation
+    // ============================================
+
+    /// Validate a custom template against the variables its kind provides
+    pub fn validate_template(kind: &str, template: &str) -> TemplateValidation {
+        let Some(spec) = template_spec(kind) else {
+            return TemplateValidation {
+                missing: vec![format!("(unknown template kind '{}')", kind)],
+                ..Default::default()
+            };
+        };
+
+        let variables = TemplateEngine::extract_variables(template);
+
+        let missing = spec
+            .required
+            .iter()
+            .filter(|group| !group.iter().any(|v| variables.iter().any(|var| var == v)))
+            .map(|group| group.join(" or "))
+            .collect();
+
+        let unknown = variables
+            .into_iter()
+            .filter(|v| !spec.known.contains(&v.as_str()))
+            .collect();
+
+        let missing_fields = spec
+            .output_fields
+            .iter()
+            .filter(|field| !template.contains(*field))
+            .map(|field| field.to_string())
+            .collect();
+
+        TemplateValidation {
+            missing,
+            unknown,
+            missing_fields,
+        }
+    }
+
     // ============================================
     // Default Template Getters
     // ============================================
@@ -502,21 +752,25 @@ impl Prompts {
 
         let mut vars = HashMap::new();
         vars.insert("transcript", transcript_text);
+        vars.insert("transcript_text", transcript_text);
         vars.insert("cwd", cwd);
         vars.insert("git_branch", git_str);
+        vars.insert("git_info", git_str);
         vars.insert("language", language);
 
-        TemplateEngine::render(template, &vars)
+        render_localized(template, &vars, language)
     }
 
-    /// Generate prompt for skill extraction with optional custom template
+    /// Generate prompt for skill extraction with optional custom template.
+    ///
+    /// `today` is the skill's creation date (yyyy-mm-dd), as given by `Config::today`.
     pub fn extract_skill_with_template(
         custom_template: Option<&str>,
         session_summary: &str,
         skill_hint: Option<&str>,
         language: &str,
+        today: &str,
     ) -> String {
-        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
         let hint = if language == "zh" {
             skill_hint.unwrap_or("基于会话中的模式")
         } else {
@@ -529,10 +783,10 @@ impl Prompts {
         let mut vars = HashMap::new();
         vars.insert("session_content", session_summary);
         vars.insert("skill_hint", hint);
-        vars.insert("today", today.as_str());
+        vars.insert("today", today);
         vars.insert("language", language);
 
-        TemplateEngine::render(template, &vars)
+        render_localized(template, &vars, language)
     }
 
     /// Generate prompt for command extraction with optional custom template
@@ -556,15 +810,19 @@ impl Prompts {
         vars.insert("command_hint", hint);
         vars.insert("language", language);
 
-        TemplateEngine::render(template, &vars)
+        render_localized(template, &vars, language)
     }
 
-    /// Generate prompt for daily summary with optional custom template
+    /// Generate prompt for daily summary with optional custom template.
+    ///
+    /// `regenerate` rewrites `existing_summary` instead of appending new sessions to it;
+    /// it has no effect without an existing summary.
     pub fn daily_summary_with_template(
         custom_template: Option<&str>,
         sessions_json: &str,
         date: &str,
         existing_summary: Option<&str>,
+        regenerate: bool,
         language: &str,
     ) -> String {
         let now = chrono::Local::now();
@@ -596,8 +854,8 @@ impl Prompts {
             )
         };
 
-        // Check if this is a regenerate scenario (no new sessions but existing summary)
-        let is_regenerate = sessions_json.trim() == "[]" && existing_summary.is_some();
+        // Regenerating only makes sense when there is a summary to rewrite
+        let is_regenerate = regenerate && existing_summary.is_some();
 
         let existing_section = if let Some(existing) = existing_summary {
             if is_regenerate {
@@ -643,11 +901,34 @@ impl Prompts {
         vars.insert("current_period", current_period);
         vars.insert("periods_desc", periods_desc);
         vars.insert("existing_section", existing_section.as_str());
+        vars.insert("existing_summary", existing_summary.unwrap_or(""));
         vars.insert("sessions_section", sessions_section.as_str());
         vars.insert("sessions_json", sessions_json);
         vars.insert("language", language);
 
-        TemplateEngine::render(template, &vars)
+        render_localized(template, &vars, language)
+    }
+
+    /// Generate prompt for the weekly digest from topic clusters
+    pub fn weekly_summary(
+        themes_json: &str,
+        start_date: &str,
+        end_date: &str,
+        language: &str,
+    ) -> String {
+        let template = if language == "zh" {
+            WEEKLY_SUMMARY_ZH
+        } else {
+            WEEKLY_SUMMARY_EN
+        };
+
+        let mut vars = HashMap::new();
+        vars.insert("themes_json", themes_json);
+        vars.insert("start_date", start_date);
+        vars.insert("end_date", end_date);
+        vars.insert("language", language);
+
+        render_localized(template, &vars, language)
     }
 }
 
@@ -683,6 +964,109 @@ mod tests {
         assert!(prompt.contains("Git 分支：main"));
     }
 
+    #[test]
+    fn test_session_summary_prompt_ja() {
+        let prompt = Prompts::session_summary_with_template(
+            None,
+            "User: Help me fix a bug\nAssistant: I'll help you.",
+            "/home/user/project",
+            Some("main"),
+            "ja",
+        );
+
+        assert!(prompt.contains("Respond in Japanese."));
+        assert!(prompt.contains("日本語で書いてください"));
+        assert!(prompt.contains("Working Directory: /home/user/project"));
+        assert!(!prompt.contains("沉淀三问"));
+        assert!(!prompt.contains("质量门禁"));
+    }
+
+    #[test]
+    fn test_english_prompts_have_no_chinese_labels() {
+        let prompt =
+            Prompts::daily_summary_with_template(None, "[]", "2026-01-16", None, false, "en");
+
+        assert!(!prompt.contains("Respond in"));
+        assert!(!prompt.contains("暂未发现"));
+        assert!(!prompt
+            .chars()
+            .any(|c| ('\u{4e00}'..='\u{9fff}').contains(&c)));
+    }
+
+    #[test]
+    fn test_language_names() {
+        for language in SUPPORTED_LANGUAGES {
+            assert!(language_name(language).is_some(), "{}", language);
+        }
+        assert_eq!(language_name("de"), None);
+    }
+
+    #[test]
+    fn test_default_templates_are_valid() {
+        for kind in TEMPLATE_KINDS {
+            for language in ["en", "zh"] {
+                let template = match kind {
+                    "session_summary" => Prompts::default_session_summary_template(language),
+                    "daily_summary" => Prompts::default_daily_summary_template(language),
+                    "skill_extract" => Prompts::default_skill_extract_template(language),
+                    _ => Prompts::default_command_extract_template(language),
+                };
+                let result = Prompts::validate_template(kind, template);
+                assert!(
+                    result.messages().is_empty(),
+                    "{} ({}): {:?}",
+                    kind,
+                    language,
+                    result
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_template_reports_problems() {
+        let result = Prompts::validate_template("session_summary", "Summarize {{transcipt}}");
+
+        assert!(!result.is_valid());
+        assert_eq!(result.missing, vec!["transcript or transcript_text"]);
+        assert_eq!(result.unknown, vec!["transcipt"]);
+        assert!(result.missing_fields.contains(&"topic".to_string()));
+    }
+
+    #[test]
+    fn test_custom_session_template_renders_aliases() {
+        let template = "{{transcript_text}} in {{cwd}} on {{git_info}}";
+        assert!(Prompts::validate_template("session_summary", template).is_valid());
+
+        let prompt = Prompts::session_summary_with_template(
+            Some(template),
+            "User: hi",
+            "/home/user/project",
+            None,
+            "en",
+        );
+        assert_eq!(prompt, "User: hi in /home/user/project on N/A");
+    }
+
+    #[test]
+    fn test_validate_daily_template_accepts_either_sessions_var() {
+        let result = Prompts::validate_template("daily_summary", "Digest {{sessions_json}}");
+        assert!(result.is_valid());
+    }
+
+    #[test]
+    fn test_weekly_summary_prompt() {
+        let prompt = Prompts::weekly_summary(
+            r#"[{"theme": "auth", "sessions": []}]"#,
+            "2026-01-12",
+            "2026-01-18",
+            "en",
+        );
+
+        assert!(prompt.contains("2026-01-12 to 2026-01-18"));
+        assert!(prompt.contains(r#""theme": "auth""#));
+    }
+
     #[test]
     fn test_daily_summary_prompt() {
         let prompt = Prompts::daily_summary_with_template(
@@ -690,6 +1074,7 @@ mod tests {
             r#"[{"title": "test", "summary": "test summary"}]"#,
             "2026-01-16",
             None,
+            false,
             "en",
         );
 
@@ -703,6 +1088,7 @@ mod tests {
             r#"[{"title": "new", "summary": "new summary"}]"#,
             "2026-01-16",
             Some("Previous overview content"),
+            false,
             "en",
         );
 
@@ -718,10 +1104,59 @@ mod tests {
             r#"[{"title": "test", "summary": "test summary"}]"#,
             "2026-01-16",
             None,
+            false,
             "zh",
         );
 
         assert!(prompt.contains("2026-01-16"));
         assert!(prompt.contains("时间上下文"));
     }
+
+    #[test]
+    fn test_daily_summary_prompt_regenerate() {
+        let prompt = Prompts::daily_summary_with_template(
+            None,
+            "[]",
+            "2026-01-16",
+            Some("Previous overview content"),
+            true,
+            "en",
+        );
+
+        assert!(prompt.contains("REGENERATE MODE"));
+        assert!(prompt.contains("Previous overview content"));
+        assert!(!prompt.contains("Sessions (JSON format)"));
+    }
+
+    #[test]
+    fn test_daily_summary_prompt_empty_sessions_not_regenerate() {
+        // An empty session list alone must not trigger regenerate mode
+        let prompt = Prompts::daily_summary_with_template(
+            None,
+            "[]",
+            "2026-01-16",
+            Some("Previous overview content"),
+            false,
+            "en",
+        );
+
+        assert!(!prompt.contains("REGENERATE MODE"));
+        assert!(prompt.contains("Existing Daily Summary"));
+        assert!(prompt.contains("Sessions (JSON format)"));
+    }
+
+    #[test]
+    fn test_daily_summary_prompt_regenerate_without_existing() {
+        let prompt = Prompts::daily_summary_with_template(
+            None,
+            r#"[{"content": "new"}]"#,
+            "2026-01-16",
+            None,
+            true,
+            "en",
+        );
+
+        assert!(!prompt.contains("REGENERATE MODE"));
+        assert!(prompt.contains("Sessions (JSON format)"));
+    }
 }
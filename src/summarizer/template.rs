@@ -24,7 +24,6 @@ impl TemplateEngine {
 
     /// Extract all variable names used in a template
     /// Useful for validation and UI hints
-    #[allow(dead_code)]
     pub fn extract_variables(template: &str) -> Vec<String> {
         let mut variables = Vec::new();
         let mut chars = template.chars().peekable();
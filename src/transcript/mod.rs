@@ -1,4 +1,5 @@
 mod parser;
+pub mod source;
 
-pub use parser::TranscriptData;
 pub use parser::TranscriptParser;
+pub use parser::{SessionKind, TranscriptData};
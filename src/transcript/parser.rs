@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
+use tracing::warn;
 
 /// A single entry in the Claude Code transcript JSONL file
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -31,6 +32,10 @@ pub struct TranscriptData {
     pub tool_calls: Vec<ToolCall>,
     pub files_modified: Vec<String>,
     pub summary: Option<String>,
+    /// Session this transcript was resumed from (`--continue`/`--resume`)
+    pub parent_session_id: Option<String>,
+    /// Assistant reasoning from `thinking` content blocks
+    pub thinking_blocks: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -41,7 +46,149 @@ pub struct ToolCall {
     pub response: Option<serde_json::Value>,
 }
 
+/// Most recent tool outcomes listed in the condensed transcript
+const MAX_TOOL_OUTCOMES: usize = 20;
+
+/// Characters of a tool response kept in the condensed transcript
+const TOOL_RESPONSE_CHARS: usize = 200;
+
+impl ToolCall {
+    /// Whether the tool reported a failure (error flag, interruption or non-zero exit)
+    pub fn failed(&self) -> bool {
+        let Some(response) = &self.response else {
+            return false;
+        };
+        let flag = |key: &str| response.get(key).and_then(|v| v.as_bool()) == Some(true);
+        let exit_code = ["exit_code", "exitCode", "returnCode"]
+            .iter()
+            .find_map(|key| response.get(*key).and_then(|v| v.as_i64()));
+
+        flag("is_error") || flag("interrupted") || exit_code.is_some_and(|code| code != 0)
+    }
+
+    /// Text of the tool's response: Bash stdout/stderr, or the tool_result content
+    pub fn response_text(&self) -> Option<String> {
+        let response = self.response.as_ref()?;
+        if let Some(text) = response.as_str() {
+            return Some(text.to_string());
+        }
+
+        let field = |key: &str| {
+            response
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+        };
+
+        let mut parts = Vec::new();
+        if let Some(stdout) = field("stdout") {
+            parts.push(stdout.to_string());
+        }
+        if let Some(stderr) = field("stderr") {
+            parts.push(format!("stderr: {}", stderr));
+        }
+        if parts.is_empty() {
+            match response.get("content") {
+                Some(serde_json::Value::String(text)) => parts.push(text.trim().to_string()),
+                Some(serde_json::Value::Array(blocks)) => parts.extend(
+                    blocks
+                        .iter()
+                        .filter_map(|block| block.get("text").and_then(|t| t.as_str()))
+                        .map(|text| text.trim().to_string()),
+                ),
+                _ => {}
+            }
+        }
+        if parts.is_empty() {
+            parts.extend(field("error").map(String::from));
+        }
+
+        let text = parts.join("\n");
+        (!text.is_empty()).then_some(text)
+    }
+
+    /// Short description of what the tool was asked to do
+    fn input_summary(&self) -> Option<String> {
+        ["command", "file_path", "pattern", "url"]
+            .iter()
+            .find_map(|key| self.input.get(*key).and_then(|v| v.as_str()))
+            .map(|value| truncate_text(&collapse_whitespace(value), 80))
+    }
+}
+
+/// How much real work a session contains
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionKind {
+    /// No user interaction and no meaningful tool use
+    Empty,
+    /// Conversation without meaningful tool use
+    Chat,
+    /// At least one meaningful tool was used
+    Implementation,
+}
+
+impl SessionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SessionKind::Empty => "empty",
+            SessionKind::Chat => "chat",
+            SessionKind::Implementation => "implementation",
+        }
+    }
+}
+
 impl TranscriptData {
+    /// Classify the session by whether any of `meaningful_tools` were used
+    pub fn classify_session(&self, meaningful_tools: &[String]) -> SessionKind {
+        let did_work = self
+            .tool_calls
+            .iter()
+            .any(|call| meaningful_tools.contains(&call.name));
+
+        if did_work {
+            SessionKind::Implementation
+        } else if self.is_empty() {
+            SessionKind::Empty
+        } else {
+            SessionKind::Chat
+        }
+    }
+
+    /// Rough token count of the conversation text (chars / 4)
+    pub fn estimated_tokens(&self) -> usize {
+        self.user_messages
+            .iter()
+            .chain(&self.assistant_messages)
+            .chain(&self.thinking_blocks)
+            .map(|text| estimate_tokens(text))
+            .sum()
+    }
+
+    /// Wall-clock span between the first and last timestamped entries, e.g. "1h 23m".
+    ///
+    /// `None` when fewer than two entries carry a parseable RFC 3339 timestamp.
+    pub fn duration(&self) -> Option<String> {
+        let times: Vec<_> = self
+            .entries
+            .iter()
+            .filter_map(|entry| entry.timestamp.as_deref())
+            .filter_map(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+            .collect();
+        if times.len() < 2 {
+            return None;
+        }
+        let start = times.iter().min()?;
+        let end = times.iter().max()?;
+
+        let minutes = (*end - *start).num_minutes();
+        Some(match minutes {
+            0 => "<1m".to_string(),
+            m if m < 60 => format!("{}m", m),
+            m => format!("{}h {}m", m / 60, m % 60),
+        })
+    }
+
     /// Check if the session is empty (no meaningful user interaction)
     pub fn is_empty(&self) -> bool {
         // A session is empty if there are no user messages
@@ -101,17 +248,120 @@ impl TranscriptParser {
         None
     }
 
+    /// Extract the reasoning text of `thinking` content blocks from a transcript entry
+    fn extract_thinking(entry: &TranscriptEntry) -> Vec<String> {
+        entry
+            .extra
+            .get("message")
+            .and_then(|message| message.get("content"))
+            .and_then(|content| content.as_array())
+            .map(|blocks| {
+                blocks
+                    .iter()
+                    .filter(|block| block.get("type").and_then(|t| t.as_str()) == Some("thinking"))
+                    .filter_map(|block| block.get("thinking").and_then(|t| t.as_str()))
+                    .filter(|text| !text.trim().is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Extract `tool_use` content blocks (id, name, input) from an assistant entry
+    fn extract_tool_uses(entry: &TranscriptEntry) -> Vec<(String, String, serde_json::Value)> {
+        entry
+            .extra
+            .get("message")
+            .and_then(|message| message.get("content"))
+            .and_then(|content| content.as_array())
+            .map(|blocks| {
+                blocks
+                    .iter()
+                    .filter(|block| block.get("type").and_then(|t| t.as_str()) == Some("tool_use"))
+                    .filter_map(|block| {
+                        let id = block.get("id").and_then(|v| v.as_str())?;
+                        let name = block.get("name").and_then(|v| v.as_str())?;
+                        let input = block.get("input").cloned().unwrap_or_default();
+                        Some((id.to_string(), name.to_string(), input))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Extract `tool_result` content blocks from a user entry, keyed by `tool_use_id`.
+    ///
+    /// Structured Bash output (`toolUseResult` with stdout/stderr) is merged into the block.
+    fn extract_tool_results(entry: &TranscriptEntry) -> Vec<(String, serde_json::Value)> {
+        let Some(blocks) = entry
+            .extra
+            .get("message")
+            .and_then(|message| message.get("content"))
+            .and_then(|content| content.as_array())
+        else {
+            return Vec::new();
+        };
+        let structured = entry.extra.get("toolUseResult").and_then(|r| r.as_object());
+
+        blocks
+            .iter()
+            .filter(|block| block.get("type").and_then(|t| t.as_str()) == Some("tool_result"))
+            .filter_map(|block| {
+                let id = block.get("tool_use_id").and_then(|v| v.as_str())?;
+                let mut result = block.clone();
+                if let (Some(fields), Some(target)) = (structured, result.as_object_mut()) {
+                    for (key, value) in fields {
+                        target.entry(key.clone()).or_insert_with(|| value.clone());
+                    }
+                }
+                Some((id.to_string(), result))
+            })
+            .collect()
+    }
+
+    /// Find a reference to the session this transcript was resumed from.
+    ///
+    /// Uses an explicit parent field when present, otherwise a `sessionId`
+    /// carried over from the resumed conversation that differs from our own.
+    fn extract_parent_session_id(entry: &TranscriptEntry, own_id: &str) -> Option<String> {
+        const PARENT_KEYS: &[&str] = &["parentSessionId", "parent_session_id", "resumedFrom"];
+
+        let explicit = PARENT_KEYS.iter().find_map(|key| {
+            entry
+                .extra
+                .get(*key)
+                .or_else(|| entry.extra.get("metadata").and_then(|m| m.get(*key)))
+                .and_then(|v| v.as_str())
+        });
+
+        explicit
+            .or_else(|| entry.extra.get("sessionId").and_then(|v| v.as_str()))
+            .filter(|id| !id.is_empty() && *id != own_id)
+            .map(String::from)
+    }
+
     /// Parse a transcript file and extract relevant information
     pub fn parse<P: AsRef<Path>>(path: P) -> Result<TranscriptData> {
+        let own_id = path
+            .as_ref()
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
         let file = File::open(path.as_ref()).context("Failed to open transcript file")?;
         let reader = BufReader::new(file);
 
         let mut entries = Vec::new();
         let mut user_messages = Vec::new();
         let mut assistant_messages = Vec::new();
-        let mut tool_calls = Vec::new();
+        let mut tool_calls: Vec<ToolCall> = Vec::new();
         let mut files_modified = Vec::new();
         let mut summary = None;
+        let mut parent_session_id = None;
+        let mut parent_checked = false;
+        let mut thinking_blocks = Vec::new();
+        // tool_use id -> index in tool_calls, to attach the matching tool_result
+        let mut tool_use_ids: std::collections::HashMap<String, usize> =
+            std::collections::HashMap::new();
 
         for line in reader.lines() {
             let line = line.context("Failed to read line")?;
@@ -121,6 +371,16 @@ impl TranscriptParser {
 
             match serde_json::from_str::<TranscriptEntry>(&line) {
                 Ok(entry) => {
+                    // Only the first entry that identifies a session can point at the parent
+                    if !parent_checked
+                        && (entry.extra.contains_key("sessionId")
+                            || entry.extra.contains_key("parentSessionId")
+                            || entry.extra.contains_key("metadata"))
+                    {
+                        parent_checked = true;
+                        parent_session_id = Self::extract_parent_session_id(&entry, &own_id);
+                    }
+
                     // Extract user messages
                     // Support both old format (role: "user") and new format (type: "user")
                     if entry.role.as_deref() == Some("user")
@@ -129,6 +389,11 @@ impl TranscriptParser {
                         if let Some(text) = Self::extract_message_content(&entry) {
                             user_messages.push(text);
                         }
+                        for (id, result) in Self::extract_tool_results(&entry) {
+                            if let Some(&index) = tool_use_ids.get(&id) {
+                                tool_calls[index].response = Some(result);
+                            }
+                        }
                     }
 
                     // Extract assistant messages
@@ -139,6 +404,16 @@ impl TranscriptParser {
                         if let Some(text) = Self::extract_message_content(&entry) {
                             assistant_messages.push(text);
                         }
+                        thinking_blocks.extend(Self::extract_thinking(&entry));
+                        for (id, name, input) in Self::extract_tool_uses(&entry) {
+                            track_modified_file(&name, &input, &mut files_modified);
+                            tool_use_ids.insert(id, tool_calls.len());
+                            tool_calls.push(ToolCall {
+                                name,
+                                input,
+                                response: None,
+                            });
+                        }
                     }
 
                     // Extract tool calls
@@ -150,17 +425,7 @@ impl TranscriptParser {
                         };
 
                         // Track file modifications
-                        if tool_name == "Write" || tool_name == "Edit" {
-                            if let Some(input) = &entry.tool_input {
-                                if let Some(file_path) =
-                                    input.get("file_path").and_then(|v| v.as_str())
-                                {
-                                    if !files_modified.contains(&file_path.to_string()) {
-                                        files_modified.push(file_path.to_string());
-                                    }
-                                }
-                            }
-                        }
+                        track_modified_file(tool_name, &tool_call.input, &mut files_modified);
 
                         tool_calls.push(tool_call);
                     }
@@ -174,7 +439,7 @@ impl TranscriptParser {
                 }
                 Err(e) => {
                     // Log but don't fail on parse errors for individual lines
-                    eprintln!("[daily] Warning: Failed to parse transcript line: {}", e);
+                    warn!("Failed to parse transcript line: {}", e);
                 }
             }
         }
@@ -186,21 +451,106 @@ impl TranscriptParser {
             tool_calls,
             files_modified,
             summary,
+            parent_session_id,
+            thinking_blocks,
         })
     }
 
-    /// Get a condensed text representation of the transcript for summarization
-    pub fn to_condensed_text(data: &TranscriptData) -> String {
+    /// Get a condensed text representation of the transcript for summarization.
+    ///
+    /// With `include_thinking`, the assistant's reasoning is added as a Key Reasoning section.
+    /// With a `max_tokens` budget the whole text is kept within it: the oldest user requests,
+    /// reasoning and modified files are dropped and the existing summary is cut, with
+    /// markers noting how many were omitted. The most recent request is always kept.
+    pub fn to_condensed_text(
+        data: &TranscriptData,
+        include_thinking: bool,
+        max_tokens: Option<usize>,
+    ) -> String {
+        let requests: Vec<String> = data
+            .user_messages
+            .iter()
+            .enumerate()
+            .map(|(i, msg)| format!("{}. {}\n\n", i + 1, truncate_text(msg, 500)))
+            .collect();
+        let reasoning: Vec<String> = if include_thinking {
+            data.thinking_blocks
+                .iter()
+                .map(|block| format!("- {}\n", truncate_text(block.trim(), 500)))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let files: Vec<String> = data
+            .files_modified
+            .iter()
+            .map(|file| format!("- {}\n", file))
+            .collect();
+        // Tool outcomes are already capped at MAX_TOOL_OUTCOMES short lines
+        let tools = Self::tools_section(data);
+        let mut summary = data.summary.clone().unwrap_or_default();
+
+        // The existing summary, reasoning and files get at most a quarter each of what the
+        // tools leave over, and requests the rest
+        let (mut kept_requests, mut kept_reasoning, mut kept_files) =
+            (requests.len(), reasoning.len(), files.len());
+        if let Some(max_tokens) = max_tokens {
+            let mut budget = max_tokens.saturating_sub(estimate_tokens(&tools));
+            let share = budget / 4;
+            if estimate_tokens(&summary) > share {
+                summary = truncate_text(&summary, share * 4);
+            }
+            budget = budget.saturating_sub(estimate_tokens(&summary));
+            kept_reasoning = keep_recent(&reasoning, share, 0);
+            kept_files = keep_recent(&files, share, 0);
+            for lines in [
+                &reasoning[reasoning.len() - kept_reasoning..],
+                &files[files.len() - kept_files..],
+            ] {
+                budget = budget.saturating_sub(lines.iter().map(|l| estimate_tokens(l)).sum());
+            }
+            kept_requests = keep_recent(&requests, budget, 1);
+        }
+
         let mut text = String::new();
+        let omitted = requests.len() - kept_requests;
+        if omitted > 0 {
+            text.push_str(&format!(
+                "[... {} earlier messages omitted ...]\n\n",
+                omitted
+            ));
+        }
 
         // Add user messages
-        if !data.user_messages.is_empty() {
+        if !requests.is_empty() {
             text.push_str("## User Requests\n\n");
-            for (i, msg) in data.user_messages.iter().enumerate() {
-                text.push_str(&format!("{}. {}\n\n", i + 1, truncate_text(msg, 500)));
+            for request in &requests[omitted..] {
+                text.push_str(request);
             }
         }
 
+        text.push_str(&tools);
+
+        // Add assistant reasoning, which often holds the non-obvious debugging steps
+        push_list_section(&mut text, "Key Reasoning", &reasoning, kept_reasoning);
+
+        // Add files modified
+        push_list_section(&mut text, "Files Modified", &files, kept_files);
+
+        // Add existing summary if available
+        if data.summary.is_some() {
+            text.push_str("## Existing Summary\n\n");
+            text.push_str(&summary);
+            text.push('\n');
+        }
+
+        text
+    }
+
+    /// Tools Used and Tool Outcomes sections
+    fn tools_section(data: &TranscriptData) -> String {
+        let mut text = String::new();
+
         // Add tool usage summary
         if !data.tool_calls.is_empty() {
             text.push_str("## Tools Used\n\n");
@@ -215,23 +565,105 @@ impl TranscriptParser {
             text.push('\n');
         }
 
-        // Add files modified
-        if !data.files_modified.is_empty() {
-            text.push_str("## Files Modified\n\n");
-            for file in &data.files_modified {
-                text.push_str(&format!("- {}\n", file));
+        // Add tool outcomes so the summary can tell what worked and what failed
+        let outcomes: Vec<String> = data
+            .tool_calls
+            .iter()
+            .filter(|call| call.response.is_some() && (call.name == "Bash" || call.failed()))
+            .map(|call| {
+                let status = if call.failed() { "failed" } else { "ok" };
+                let mut line = format!("- [{}] {}", status, call.name);
+                if let Some(input) = call.input_summary() {
+                    line.push_str(&format!(" `{}`", input));
+                }
+                if let Some(response) = call.response_text() {
+                    let response = collapse_whitespace(&response);
+                    line.push_str(&format!(
+                        ": {}",
+                        truncate_tail(&response, TOOL_RESPONSE_CHARS)
+                    ));
+                }
+                line.push('\n');
+                line
+            })
+            .collect();
+        if !outcomes.is_empty() {
+            text.push_str("## Tool Outcomes\n\n");
+            let skipped = outcomes.len().saturating_sub(MAX_TOOL_OUTCOMES);
+            if skipped > 0 {
+                text.push_str(&format!("[... {} earlier outcomes omitted ...]\n", skipped));
+            }
+            for line in &outcomes[skipped..] {
+                text.push_str(line);
             }
             text.push('\n');
         }
 
-        // Add existing summary if available
-        if let Some(summary) = &data.summary {
-            text.push_str("## Existing Summary\n\n");
-            text.push_str(summary);
-            text.push('\n');
+        text
+    }
+}
+
+/// How many of the most recent `items` fit in `budget` tokens, keeping at least `min`
+fn keep_recent(items: &[String], budget: usize, min: usize) -> usize {
+    let mut budget = budget;
+    let mut kept = 0;
+    for item in items.iter().rev() {
+        let cost = estimate_tokens(item);
+        if kept >= min && cost > budget {
+            break;
         }
+        budget = budget.saturating_sub(cost);
+        kept += 1;
+    }
+    kept
+}
 
-        text
+/// Append a `## <title>` list of the last `kept` lines, noting how many were omitted
+fn push_list_section(text: &mut String, title: &str, lines: &[String], kept: usize) {
+    if lines.is_empty() {
+        return;
+    }
+    text.push_str(&format!("## {}\n\n", title));
+    let omitted = lines.len() - kept;
+    if omitted > 0 {
+        text.push_str(&format!("[... {} earlier entries omitted ...]\n", omitted));
+    }
+    for line in &lines[omitted..] {
+        text.push_str(line);
+    }
+    text.push('\n');
+}
+
+/// Rough token count of a text (chars / 4)
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Record the file a Write/Edit call touched
+fn track_modified_file(tool_name: &str, input: &serde_json::Value, files: &mut Vec<String>) {
+    if tool_name != "Write" && tool_name != "Edit" {
+        return;
+    }
+    if let Some(file_path) = input.get("file_path").and_then(|v| v.as_str()) {
+        if !files.iter().any(|f| f == file_path) {
+            files.push(file_path.to_string());
+        }
+    }
+}
+
+/// Join all lines and runs of whitespace with single spaces
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Keep the last `max_len` characters, where command output usually has its verdict
+fn truncate_tail(text: &str, max_len: usize) -> String {
+    let count = text.chars().count();
+    if count <= max_len {
+        text.to_string()
+    } else {
+        let tail: String = text.chars().skip(count - max_len).collect();
+        format!("...{}", tail)
     }
 }
 
@@ -264,9 +696,49 @@ mod tests {
             tool_calls: vec![],
             files_modified: vec![],
             summary: None,
+            parent_session_id: None,
+            thinking_blocks: vec![],
+        }
+    }
+
+    fn entry_at(timestamp: Option<&str>) -> TranscriptEntry {
+        TranscriptEntry {
+            entry_type: Some("user".to_string()),
+            role: None,
+            content: None,
+            timestamp: timestamp.map(str::to_string),
+            tool_name: None,
+            tool_input: None,
+            tool_response: None,
+            summary: None,
+            extra: Default::default(),
         }
     }
 
+    #[test]
+    fn test_duration() {
+        let mut data = create_empty_transcript_data();
+        assert_eq!(data.duration(), None);
+
+        data.entries = vec![
+            entry_at(Some("2026-01-15T10:00:00Z")),
+            entry_at(None),
+            entry_at(Some("not a time")),
+            entry_at(Some("2026-01-15T11:23:40Z")),
+            entry_at(Some("2026-01-15T10:30:00Z")),
+        ];
+        assert_eq!(data.duration(), Some("1h 23m".to_string()));
+
+        data.entries = vec![
+            entry_at(Some("2026-01-15T10:00:00+01:00")),
+            entry_at(Some("2026-01-15T09:42:00Z")),
+        ];
+        assert_eq!(data.duration(), Some("42m".to_string()));
+
+        data.entries = vec![entry_at(Some("2026-01-15T10:00:00Z"))];
+        assert_eq!(data.duration(), None);
+    }
+
     #[test]
     fn test_is_empty_no_messages() {
         let data = create_empty_transcript_data();
@@ -287,10 +759,184 @@ mod tests {
         assert!(!data.is_empty());
     }
 
+    #[test]
+    fn test_classify_session() {
+        let tools = vec!["Edit".to_string(), "Bash".to_string()];
+        let mut data = create_empty_transcript_data();
+        assert_eq!(data.classify_session(&tools), SessionKind::Empty);
+
+        data.user_messages = vec!["How does this work?".to_string()];
+        data.tool_calls.push(ToolCall {
+            name: "Read".to_string(),
+            input: serde_json::json!({}),
+            response: None,
+        });
+        assert_eq!(data.classify_session(&tools), SessionKind::Chat);
+
+        // Meaningful tool use counts even without user text
+        data.user_messages.clear();
+        data.tool_calls.push(ToolCall {
+            name: "Bash".to_string(),
+            input: serde_json::json!({"command": "cargo test"}),
+            response: None,
+        });
+        assert_eq!(data.classify_session(&tools), SessionKind::Implementation);
+    }
+
     #[test]
     fn test_is_empty_mixed_messages() {
         let mut data = create_empty_transcript_data();
         data.user_messages = vec!["   ".to_string(), "actual content".to_string()];
         assert!(!data.is_empty());
     }
+
+    #[test]
+    fn test_parse_parent_session_id() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+
+        // Resumed transcript carries over entries from the parent session
+        let resumed = temp_dir.path().join("child-id.jsonl");
+        std::fs::write(
+            &resumed,
+            [
+                r#"{"type":"summary","summary":"Earlier work"}"#,
+                r#"{"type":"user","sessionId":"parent-id","message":{"content":"hi"}}"#,
+                r#"{"type":"user","sessionId":"child-id","message":{"content":"continue"}}"#,
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+        let data = TranscriptParser::parse(&resumed).unwrap();
+        assert_eq!(data.parent_session_id.as_deref(), Some("parent-id"));
+
+        let fresh = temp_dir.path().join("fresh-id.jsonl");
+        std::fs::write(
+            &fresh,
+            r#"{"type":"user","sessionId":"fresh-id","message":{"content":"hi"}}"#,
+        )
+        .unwrap();
+        let data = TranscriptParser::parse(&fresh).unwrap();
+        assert_eq!(data.parent_session_id, None);
+
+        let explicit = temp_dir.path().join("other-id.jsonl");
+        std::fs::write(
+            &explicit,
+            r#"{"type":"system","sessionId":"other-id","metadata":{"parentSessionId":"root-id"}}"#,
+        )
+        .unwrap();
+        let data = TranscriptParser::parse(&explicit).unwrap();
+        assert_eq!(data.parent_session_id.as_deref(), Some("root-id"));
+    }
+
+    #[test]
+    fn test_thinking_blocks() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("abc.jsonl");
+        std::fs::write(
+            &path,
+            r#"{"type":"user","message":{"content":"Why does the test hang?"}}
+{"type":"assistant","message":{"content":[{"type":"thinking","thinking":"The mutex is held across the await."},{"type":"text","text":"Found it."}]}}
+"#,
+        )
+        .unwrap();
+
+        let data = TranscriptParser::parse(&path).unwrap();
+        assert_eq!(data.assistant_messages, vec!["Found it."]);
+        assert_eq!(
+            data.thinking_blocks,
+            vec!["The mutex is held across the await."]
+        );
+
+        let with = TranscriptParser::to_condensed_text(&data, true, None);
+        assert!(with.contains("## Key Reasoning\n\n- The mutex is held across the await."));
+        let without = TranscriptParser::to_condensed_text(&data, false, None);
+        assert!(!without.contains("Key Reasoning"));
+    }
+
+    #[test]
+    fn test_tool_outcomes() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("abc.jsonl");
+        std::fs::write(
+            &path,
+            r#"{"type":"user","message":{"content":"Make the tests pass"}}
+{"type":"assistant","message":{"content":[{"type":"tool_use","id":"t1","name":"Bash","input":{"command":"cargo test"}}]}}
+{"type":"user","message":{"content":[{"type":"tool_result","tool_use_id":"t1","content":"error[E0425]: cannot find value `x`","is_error":true}]},"toolUseResult":{"stdout":"","stderr":"error[E0425]: cannot find value `x`","interrupted":false}}
+{"type":"assistant","message":{"content":[{"type":"tool_use","id":"t2","name":"Edit","input":{"file_path":"/src/lib.rs"}},{"type":"tool_use","id":"t3","name":"Bash","input":{"command":"cargo test"}}]}}
+{"type":"user","message":{"content":[{"type":"tool_result","tool_use_id":"t2","content":"File updated"}]}}
+{"type":"user","message":{"content":[{"type":"tool_result","tool_use_id":"t3","content":"test result: ok. 3 passed"}]},"toolUseResult":{"stdout":"test result: ok. 3 passed","stderr":""}}
+"#,
+        )
+        .unwrap();
+
+        let data = TranscriptParser::parse(&path).unwrap();
+        assert_eq!(data.user_messages, vec!["Make the tests pass"]);
+        assert_eq!(data.tool_calls.len(), 3);
+        assert_eq!(data.files_modified, vec!["/src/lib.rs"]);
+        assert!(data.tool_calls[0].failed());
+        assert_eq!(
+            data.tool_calls[0].response_text().as_deref(),
+            Some("stderr: error[E0425]: cannot find value `x`")
+        );
+        assert!(!data.tool_calls[1].failed());
+        assert!(!data.tool_calls[2].failed());
+
+        let text = TranscriptParser::to_condensed_text(&data, false, None);
+        assert!(text.contains(
+            "## Tool Outcomes\n\n- [failed] Bash `cargo test`: stderr: error[E0425]: cannot find value `x`\n- [ok] Bash `cargo test`: test result: ok. 3 passed\n"
+        ));
+        assert!(!text.contains("[ok] Edit"));
+    }
+
+    #[test]
+    fn test_truncate_tail() {
+        assert_eq!(truncate_tail("short", 10), "short");
+        assert_eq!(truncate_tail("0123456789abc", 3), "...abc");
+    }
+
+    #[test]
+    fn test_condensed_text_budget_keeps_recent_messages() {
+        let mut data = create_empty_transcript_data();
+        data.user_messages = (1..=10)
+            .map(|i| format!("request {} {}", i, "x".repeat(80)))
+            .collect();
+        assert!(data.estimated_tokens() > 200);
+
+        let full = TranscriptParser::to_condensed_text(&data, false, None);
+        assert!(full.contains("1. request 1 "));
+        assert!(!full.contains("omitted"));
+
+        let text = TranscriptParser::to_condensed_text(&data, false, Some(100));
+        assert!(text.starts_with("[... 6 earlier messages omitted ...]"));
+        assert!(!text.contains("request 4 "));
+        assert!(text.contains("7. request 7 "));
+        assert!(text.contains("10. request 10 "));
+
+        // The latest request survives even a tiny budget
+        let tiny = TranscriptParser::to_condensed_text(&data, false, Some(1));
+        assert!(tiny.contains("10. request 10 "));
+    }
+
+    #[test]
+    fn test_condensed_text_budget_covers_details() {
+        let mut data = create_empty_transcript_data();
+        data.user_messages = vec!["Fix the flaky test".to_string()];
+        data.thinking_blocks = (1..=200)
+            .map(|i| format!("thought {} {}", i, "y".repeat(400)))
+            .collect();
+        data.files_modified = (1..=200)
+            .map(|i| format!("/src/module_{}/{}.rs", i, "z".repeat(60)))
+            .collect();
+
+        let full = TranscriptParser::to_condensed_text(&data, true, None);
+        assert!(estimate_tokens(&full) > 20_000);
+
+        let text = TranscriptParser::to_condensed_text(&data, true, Some(2000));
+        assert!(estimate_tokens(&text) <= 2000);
+        assert!(text.contains("1. Fix the flaky test"));
+        assert!(text.contains("earlier entries omitted"));
+        assert!(text.contains("thought 200 "));
+        assert!(!text.contains("thought 1 "));
+        assert!(text.contains("module_200/"));
+    }
 }
@@ -0,0 +1,355 @@
+use anyhow::{Context, Result};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use super::parser::{ToolCall, TranscriptData, TranscriptEntry, TranscriptParser};
+use crate::config::Config;
+use tracing::warn;
+
+/// A place where an AI coding agent writes session transcripts
+pub trait TranscriptSource {
+    /// Identifier used in `summarization.sources`
+    fn name(&self) -> &'static str;
+
+    /// Directory this source writes transcripts under
+    fn root(&self) -> &Path;
+
+    /// Find all transcript files written by this source
+    fn discover(&self) -> Result<Vec<PathBuf>>;
+
+    /// Check whether a transcript file belongs to this source
+    fn owns(&self, path: &Path) -> bool;
+
+    /// Parse a transcript file into the common transcript format
+    fn parse(&self, path: &Path) -> Result<TranscriptData>;
+}
+
+/// Claude Code transcripts in ~/.claude/projects/<project>/<session>.jsonl
+pub struct ClaudeSource {
+    root: PathBuf,
+}
+
+impl ClaudeSource {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Source rooted at the default Claude Code projects directory
+    pub fn default_root() -> Option<Self> {
+        dirs::home_dir().map(|home| Self::new(home.join(".claude").join("projects")))
+    }
+}
+
+impl TranscriptSource for ClaudeSource {
+    fn name(&self) -> &'static str {
+        "claude"
+    }
+
+    fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn discover(&self) -> Result<Vec<PathBuf>> {
+        if !self.root.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut transcripts = Vec::new();
+
+        for entry in fs::read_dir(&self.root)? {
+            let path = entry?.path();
+            if !path.is_dir() {
+                continue;
+            }
+
+            // Search in project subdirectories
+            for sub_entry in fs::read_dir(&path)? {
+                let sub_path = sub_entry?.path();
+
+                if sub_path.extension().is_some_and(|ext| ext == "jsonl") {
+                    // Skip agent transcripts (temporary agent sessions)
+                    if !sub_path
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .is_some_and(|name| name.starts_with("agent-"))
+                    {
+                        transcripts.push(sub_path);
+                    }
+                }
+            }
+        }
+
+        Ok(transcripts)
+    }
+
+    fn owns(&self, path: &Path) -> bool {
+        path.starts_with(&self.root)
+    }
+
+    fn parse(&self, path: &Path) -> Result<TranscriptData> {
+        TranscriptParser::parse(path)
+    }
+}
+
+/// Codex CLI rollouts in ~/.codex/sessions/YYYY/MM/DD/rollout-*.jsonl
+pub struct CodexSource {
+    root: PathBuf,
+}
+
+impl CodexSource {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Source rooted at the default Codex sessions directory
+    pub fn default_root() -> Option<Self> {
+        dirs::home_dir().map(|home| Self::new(home.join(".codex").join("sessions")))
+    }
+}
+
+impl TranscriptSource for CodexSource {
+    fn name(&self) -> &'static str {
+        "codex"
+    }
+
+    fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn discover(&self) -> Result<Vec<PathBuf>> {
+        let mut transcripts = Vec::new();
+        if self.root.exists() {
+            collect_jsonl(&self.root, &mut transcripts)?;
+        }
+        Ok(transcripts)
+    }
+
+    fn owns(&self, path: &Path) -> bool {
+        path.starts_with(&self.root)
+    }
+
+    fn parse(&self, path: &Path) -> Result<TranscriptData> {
+        let file = File::open(path).context("Failed to open transcript file")?;
+        let reader = BufReader::new(file);
+
+        let mut data = TranscriptData {
+            entries: Vec::new(),
+            user_messages: Vec::new(),
+            assistant_messages: Vec::new(),
+            tool_calls: Vec::new(),
+            files_modified: Vec::new(),
+            summary: None,
+            parent_session_id: None,
+            thinking_blocks: Vec::new(),
+        };
+
+        for line in reader.lines() {
+            let line = line.context("Failed to read line")?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let entry = match serde_json::from_str::<TranscriptEntry>(&line) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    warn!("Failed to parse transcript line: {}", e);
+                    continue;
+                }
+            };
+
+            if entry.entry_type.as_deref() == Some("response_item") {
+                if let Some(payload) = entry.extra.get("payload") {
+                    apply_codex_payload(payload, &mut data);
+                }
+            }
+
+            data.entries.push(entry);
+        }
+
+        Ok(data)
+    }
+}
+
+/// Map a Codex `response_item` payload onto transcript data
+fn apply_codex_payload(payload: &serde_json::Value, data: &mut TranscriptData) {
+    match payload.get("type").and_then(|t| t.as_str()) {
+        Some("message") => {
+            let text = payload
+                .get("content")
+                .and_then(|c| c.as_array())
+                .map(|blocks| {
+                    blocks
+                        .iter()
+                        .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                })
+                .unwrap_or_default();
+
+            // Skip injected context blocks, they are not user requests
+            if text.trim().is_empty() || text.trim_start().starts_with('<') {
+                return;
+            }
+
+            match payload.get("role").and_then(|r| r.as_str()) {
+                Some("user") => data.user_messages.push(text),
+                Some("assistant") => data.assistant_messages.push(text),
+                _ => {}
+            }
+        }
+        Some("function_call") => {
+            let name = payload
+                .get("name")
+                .and_then(|n| n.as_str())
+                .unwrap_or("unknown")
+                .to_string();
+            let arguments = payload
+                .get("arguments")
+                .and_then(|a| a.as_str())
+                .unwrap_or_default();
+            let input = serde_json::from_str(arguments)
+                .unwrap_or_else(|_| serde_json::Value::String(arguments.to_string()));
+
+            let mut files = Vec::new();
+            collect_patched_files(&input, &mut files);
+            for file in files {
+                if !data.files_modified.contains(&file) {
+                    data.files_modified.push(file);
+                }
+            }
+
+            data.tool_calls.push(ToolCall {
+                name,
+                input,
+                response: None,
+            });
+        }
+        Some("function_call_output") => {
+            if let Some(call) = data.tool_calls.last_mut() {
+                call.response = payload.get("output").cloned();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Collect file paths touched by apply_patch from tool call arguments
+fn collect_patched_files(value: &serde_json::Value, out: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(text) => {
+            for line in text.lines() {
+                if let Some(path) = line
+                    .strip_prefix("*** Update File: ")
+                    .or_else(|| line.strip_prefix("*** Add File: "))
+                {
+                    out.push(path.trim().to_string());
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            items.iter().for_each(|v| collect_patched_files(v, out));
+        }
+        serde_json::Value::Object(map) => {
+            map.values().for_each(|v| collect_patched_files(v, out));
+        }
+        _ => {}
+    }
+}
+
+/// Recursively collect .jsonl files under a directory
+pub fn collect_jsonl(dir: &Path, out: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_jsonl(&path, out)?;
+        } else if path.extension().is_some_and(|ext| ext == "jsonl") {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Build the transcript sources enabled in config
+pub fn configured_sources(config: &Config) -> Vec<Box<dyn TranscriptSource>> {
+    let mut sources: Vec<Box<dyn TranscriptSource>> = Vec::new();
+
+    for name in &config.summarization.sources {
+        match name.as_str() {
+            "claude" => {
+                if let Some(source) = ClaudeSource::default_root() {
+                    sources.push(Box::new(source));
+                }
+            }
+            "codex" => {
+                if let Some(source) = CodexSource::default_root() {
+                    sources.push(Box::new(source));
+                }
+            }
+            other => {
+                warn!("Unknown transcript source '{}'", other);
+            }
+        }
+    }
+
+    sources
+}
+
+/// Parse a transcript with the source that owns it, falling back to Claude Code format
+pub fn parse_transcript(config: &Config, path: &Path) -> Result<TranscriptData> {
+    configured_sources(config)
+        .iter()
+        .find(|source| source.owns(path))
+        .map(|source| source.parse(path))
+        .unwrap_or_else(|| TranscriptParser::parse(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_claude_source_discover_skips_agents() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("-home-user-project");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join("abc.jsonl"), "").unwrap();
+        fs::write(project.join("agent-xyz.jsonl"), "").unwrap();
+
+        let source = ClaudeSource::new(temp_dir.path().to_path_buf());
+        let found = source.discover().unwrap();
+
+        assert_eq!(found.len(), 1);
+        assert!(found[0].ends_with("abc.jsonl"));
+        assert!(source.owns(&found[0]));
+    }
+
+    #[test]
+    fn test_codex_source_parse() {
+        let temp_dir = TempDir::new().unwrap();
+        let day_dir = temp_dir.path().join("2026").join("01").join("16");
+        fs::create_dir_all(&day_dir).unwrap();
+
+        let lines = [
+            r#"{"type":"session_meta","payload":{"cwd":"/home/user/project"}}"#,
+            r#"{"type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"<environment_context>cwd</environment_context>"}]}}"#,
+            r#"{"type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"Fix the login bug"}]}}"#,
+            r#"{"type":"response_item","payload":{"type":"function_call","name":"apply_patch","arguments":"{\"input\":\"*** Begin Patch\\n*** Update File: src/login.rs\\n*** End Patch\"}"}}"#,
+            r#"{"type":"response_item","payload":{"type":"function_call_output","output":"Done"}}"#,
+            r#"{"type":"response_item","payload":{"type":"message","role":"assistant","content":[{"type":"output_text","text":"Fixed it."}]}}"#,
+        ];
+        let path = day_dir.join("rollout-1.jsonl");
+        fs::write(&path, lines.join("\n")).unwrap();
+
+        let source = CodexSource::new(temp_dir.path().to_path_buf());
+        assert_eq!(source.discover().unwrap(), vec![path.clone()]);
+
+        let data = source.parse(&path).unwrap();
+        assert_eq!(data.user_messages, vec!["Fix the login bug"]);
+        assert_eq!(data.assistant_messages, vec!["Fixed it."]);
+        assert_eq!(data.tool_calls.len(), 1);
+        assert_eq!(data.files_modified, vec!["src/login.rs"]);
+        assert!(data.tool_calls[0].response.is_some());
+    }
+}
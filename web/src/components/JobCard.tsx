@@ -19,6 +19,8 @@ export function JobCard({ job, onKill }: JobCardProps) {
         return 'Auto Summarize'
       case 'manual':
         return 'Manual'
+      case 'digest':
+        return 'Digest'
       default:
         return type
     }
@@ -30,6 +32,8 @@ export function JobCard({ job, onKill }: JobCardProps) {
         return 'bg-blue-500/20 text-blue-400'
       case 'session_end':
         return 'bg-purple-500/20 text-purple-400'
+      case 'digest':
+        return 'bg-orange-500/20 text-orange-400'
       default:
         return 'bg-gray-500/20 text-gray-400'
     }
@@ -87,6 +91,14 @@ export function JobCard({ job, onKill }: JobCardProps) {
             <div className="flex items-center gap-4">
               <span>Started: {job.started_at}</span>
               <span className="tabular-nums">{job.elapsed}</span>
+              {job.invocations > 0 && (
+                <span
+                  className="tabular-nums"
+                  title={`${job.invocations} claude calls, ${job.retries} retries`}
+                >
+                  {job.total_tokens.toLocaleString()} tokens
+                </span>
+              )}
             </div>
             {isFailed && (
               <div className="text-red-400 mt-2">{job.status}</div>
@@ -29,23 +29,51 @@ export interface SessionDetail {
     cwd?: string
     git_branch?: string
     duration?: string
+    confidence?: 'low' | 'medium' | 'high'
+    parent_session_id?: string
   }
   file_path?: string
+  continued_from?: {
+    date: string
+    name: string
+    title: string
+  } | null
 }
 
 export interface Job {
   id: string
   task_name: string
   status: string
-  status_type: 'running' | 'completed' | 'failed'
-  job_type: 'session_end' | 'auto_summarize' | 'manual'
+  status_type: 'queued' | 'running' | 'completed' | 'failed'
+  job_type: 'session_end' | 'auto_summarize' | 'manual' | 'digest'
   started_at: string
   elapsed: string
+  invocations: number
+  retries: number
+  total_tokens: number
+}
+
+export interface SearchResult {
+  date: string
+  session_name: string
+  title: string
+  session_id?: string
+  snippet: string
+  line_number: number
+  match_count: number
+  score: number
+}
+
+export interface SearchResponse {
+  total: number
+  results: SearchResult[]
 }
 
 export interface DigestResponse {
   message: string
   session_count: number
+  job_id: string
+  already_running: boolean
 }
 
 export interface PromptTemplates {
@@ -221,6 +249,18 @@ interface RequestOptions extends RequestInit {
   headers?: Record<string, string>
 }
 
+const AUTH_TOKEN_KEY = 'daily-auth-token'
+
+// `daily show --token` opens the dashboard with ?token=...; keep it for API requests
+function authHeaders(): Record<string, string> {
+  const fromUrl = new URLSearchParams(window.location.search).get('token')
+  if (fromUrl) {
+    localStorage.setItem(AUTH_TOKEN_KEY, fromUrl)
+  }
+  const token = fromUrl ?? localStorage.getItem(AUTH_TOKEN_KEY)
+  return token ? { Authorization: `Bearer ${token}` } : {}
+}
+
 export function useApi() {
   const [loading, setLoading] = useState(false)
   const [error, setError] = useState<string | null>(null)
@@ -230,11 +270,12 @@ export function useApi() {
     setError(null)
     try {
       const res = await fetch(`${API_BASE}${endpoint}`, {
+        ...options,
         headers: {
           'Content-Type': 'application/json',
+          ...authHeaders(),
           ...options.headers,
         },
-        ...options,
       })
       const data: ApiResponse<T> = await res.json()
       if (!data.success) {
@@ -285,7 +326,10 @@ export function useApi() {
   )
 
   const triggerDigest = useCallback(
-    (date: string) => request<DigestResponse>(`/dates/${date}/digest`, { method: 'POST' }),
+    (date: string, allowToday = false) =>
+      request<DigestResponse>(`/dates/${date}/digest${allowToday ? '?allow_today=true' : ''}`, {
+        method: 'POST',
+      }),
     [request]
   )
 
@@ -323,6 +367,16 @@ export function useApi() {
     [request]
   )
 
+  const searchArchives = useCallback(
+    (q: string, from?: string, to?: string) => {
+      const params = new URLSearchParams({ q })
+      if (from) params.set('from', from)
+      if (to) params.set('to', to)
+      return request<SearchResponse>(`/search?${params}`)
+    },
+    [request]
+  )
+
   return {
     loading,
     error,
@@ -341,5 +395,6 @@ export function useApi() {
     fetchInsights,
     fetchConversation,
     fetchDateInsights,
+    searchArchives,
   }
 }
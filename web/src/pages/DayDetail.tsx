@@ -2,7 +2,7 @@ import { useState, useEffect } from 'react'
 import { useParams, Link } from 'react-router-dom'
 import { motion } from 'framer-motion'
 import { useApi } from '../hooks/useApi'
-import type { DailySummary, DateInsights, DateSessionInsight } from '../hooks/useApi'
+import type { DailySummary, DateInsights, DateSessionInsight, DigestResponse } from '../hooks/useApi'
 import { MarkdownRenderer } from '../components/MarkdownRenderer'
 import { cn } from '../lib/utils'
 
@@ -207,8 +207,26 @@ export function DayDetail() {
     setDigestLoading(true)
     setDigestMessage(null)
     try {
-      const response = await triggerDigest(date)
-      setDigestMessage(`Daily summary regeneration started. Processing ${response.session_count} sessions.`)
+      let response: DigestResponse
+      try {
+        response = await triggerDigest(date)
+      } catch (err) {
+        // The server refuses to digest today unless asked, since the day isn't over yet
+        if (!(err instanceof Error && err.message.includes('allow_today'))) throw err
+        const confirmed = window.confirm(
+          `${date} is today, so sessions archived later won't be in this digest. Digest the day so far?`
+        )
+        if (!confirmed) {
+          setDigestLoading(false)
+          return
+        }
+        response = await triggerDigest(date, true)
+      }
+      setDigestMessage(
+        response.already_running
+          ? 'A digest for this date is already running. Waiting for it to finish...'
+          : `Daily summary regeneration started. Processing ${response.session_count} sessions.`
+      )
 
       const previousRaw = summary?.raw_content
       const pollInterval = 3000
@@ -30,8 +30,9 @@ export function JobsMonitor() {
     }
   }
 
-  const runningJobs = jobs.filter((j) => j.status_type === 'running')
-  const completedJobs = jobs.filter((j) => j.status_type !== 'running')
+  const isActive = (j: Job) => j.status_type === 'running' || j.status_type === 'queued'
+  const runningJobs = jobs.filter(isActive)
+  const completedJobs = jobs.filter((j) => !isActive(j))
 
   return (
     <div className="max-w-4xl mx-auto px-6 py-8">
@@ -141,6 +141,26 @@ export function SessionDetail() {
                   <span>{session.metadata.duration}</span>
                 </div>
               )}
+              {session.metadata?.confidence === 'low' && (
+                <span
+                  className="px-2 py-0.5 rounded text-xs bg-amber-100 text-amber-700 dark:bg-amber-900/30 dark:text-amber-400"
+                  title="This summary had little transcript signal; consider re-summarizing"
+                >
+                  Low confidence
+                </span>
+              )}
+              {session.continued_from && (
+                <Link
+                  to={`/day/${session.continued_from.date}/session/${encodeURIComponent(session.continued_from.name)}`}
+                  className="flex items-center gap-2 text-orange-400 hover:text-orange-300"
+                  title={`Resumed from ${session.continued_from.date}`}
+                >
+                  <svg className="size-4" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                    <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M10 19l-7-7m0 0l7-7m-7 7h18" />
+                  </svg>
+                  <span>Continued from {session.continued_from.title}</span>
+                </Link>
+              )}
             </div>
           </div>
 
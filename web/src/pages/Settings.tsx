@@ -20,7 +20,7 @@ const TEMPLATE_VARIABLES = {
     { name: 'transcript', description: 'The session transcript content' },
     { name: 'cwd', description: 'Current working directory' },
     { name: 'git_branch', description: 'Current git branch name' },
-    { name: 'language', description: 'Output language (en/zh)' },
+    { name: 'language', description: 'Output language (en/zh/ja/es/fr)' },
   ],
   daily_summary: [
     { name: 'date', description: 'The date being summarized' },
@@ -30,21 +30,29 @@ const TEMPLATE_VARIABLES = {
     { name: 'existing_section', description: 'Existing summary content (if any)' },
     { name: 'sessions_section', description: 'Sessions data section' },
     { name: 'sessions_json', description: 'Sessions in JSON format' },
-    { name: 'language', description: 'Output language (en/zh)' },
+    { name: 'language', description: 'Output language (en/zh/ja/es/fr)' },
   ],
   skill_extract: [
     { name: 'session_content', description: 'The session summary content' },
     { name: 'skill_hint', description: 'Hint about what skill to extract' },
     { name: 'today', description: "Today's date" },
-    { name: 'language', description: 'Output language (en/zh)' },
+    { name: 'language', description: 'Output language (en/zh/ja/es/fr)' },
   ],
   command_extract: [
     { name: 'session_content', description: 'The session summary content' },
     { name: 'command_hint', description: 'Hint about what command to extract' },
-    { name: 'language', description: 'Output language (en/zh)' },
+    { name: 'language', description: 'Output language (en/zh/ja/es/fr)' },
   ],
 }
 
+const SUMMARY_LANGUAGES = [
+  { code: 'en', label: 'English' },
+  { code: 'zh', label: 'Chinese / 中文' },
+  { code: 'ja', label: 'Japanese / 日本語' },
+  { code: 'es', label: 'Spanish / Español' },
+  { code: 'fr', label: 'French / Français' },
+]
+
 const NAV_SECTIONS = {
   general: [
     { id: 'language', label: 'Summary Language', icon: '🌐' },
@@ -405,31 +413,21 @@ export function Settings() {
               <p className="text-gray-500 dark:text-gray-400 text-sm mb-4">
                 Choose the language for AI-generated summaries and digests
               </p>
-              <div className="flex gap-4">
-                <label className="flex items-center gap-2 cursor-pointer">
-                  <input
-                    type="radio"
-                    name="summary_language"
-                    value="en"
-                    checked={config.summary_language === 'en'}
-                    onChange={(e) => handleChange('summary_language', e.target.value)}
-                    disabled={saving}
-                    className="w-4 h-4 text-orange-500 bg-white dark:bg-daily-dark border-gray-300 dark:border-gray-600 focus:ring-orange-500"
-                  />
-                  <span className="text-gray-700 dark:text-gray-200">English</span>
-                </label>
-                <label className="flex items-center gap-2 cursor-pointer">
-                  <input
-                    type="radio"
-                    name="summary_language"
-                    value="zh"
-                    checked={config.summary_language === 'zh'}
-                    onChange={(e) => handleChange('summary_language', e.target.value)}
-                    disabled={saving}
-                    className="w-4 h-4 text-orange-500 bg-white dark:bg-daily-dark border-gray-300 dark:border-gray-600 focus:ring-orange-500"
-                  />
-                  <span className="text-gray-700 dark:text-gray-200">Chinese / 中文</span>
-                </label>
+              <div className="flex flex-wrap gap-4">
+                {SUMMARY_LANGUAGES.map(({ code, label }) => (
+                  <label key={code} className="flex items-center gap-2 cursor-pointer">
+                    <input
+                      type="radio"
+                      name="summary_language"
+                      value={code}
+                      checked={config.summary_language === code}
+                      onChange={(e) => handleChange('summary_language', e.target.value)}
+                      disabled={saving}
+                      className="w-4 h-4 text-orange-500 bg-white dark:bg-daily-dark border-gray-300 dark:border-gray-600 focus:ring-orange-500"
+                    />
+                    <span className="text-gray-700 dark:text-gray-200">{label}</span>
+                  </label>
+                ))}
               </div>
             </section>
 